@@ -8,10 +8,12 @@ use super::internals;
 
 #[get("/v1/block/{id}")]
 async fn get_block(pool: web::Data<ConnectionPool>, path: web::Path<String>) -> impl Responder {
-    let conn = &pool.connection.get().unwrap();
     let id = path.into_inner();
 
-    let result = internals::get_block_internal(conn, id);
+    let result = pool
+        .connection
+        .run(move |conn| internals::get_block_internal(conn, id))
+        .await;
 
     match result {
         Ok(block) => HttpResponse::Ok().json(block),
@@ -26,10 +28,13 @@ async fn get_block_transactions(
     pool: web::Data<ConnectionPool>,
     path: web::Path<String>,
 ) -> impl Responder {
-    let conn = &pool.connection.get().unwrap();
     let id = path.into_inner();
 
-    let transactions = internals::get_block_transactions_internal(conn, id).unwrap();
+    let transactions = pool
+        .connection
+        .run(move |conn| internals::get_block_transactions_internal(conn, id))
+        .await
+        .unwrap();
 
     match transactions.is_empty() {
         false => HttpResponse::Ok().json(TransactionList { transactions }),
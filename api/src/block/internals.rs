@@ -1,90 +1,13 @@
-use r2d2::PooledConnection;
-use r2d2_sqlite::SqliteConnectionManager;
+use lib::db::Storage;
 
 use super::models::Block;
 use crate::error::Error;
 use crate::shared::checker;
 use crate::shared::models::Transaction;
 
-pub fn get_block_internal(
-    conn: &PooledConnection<SqliteConnectionManager>,
-    path: String,
-) -> Result<Block, Error> {
-    match path.trim().parse::<u64>() {
-        Ok(id) => {
-            let sql = "SELECT * FROM blocks WHERE id = ?";
-            let mut stmt = conn.prepare(sql).unwrap();
-
-            let result = stmt.query_row([id], |row| {
-                Ok(Block {
-                    index: row.get(0)?,
-                    hash: row.get(1)?,
-                    size: row.get(2)?,
-                    version: row.get(3)?,
-                    merkle_root: row.get(4)?,
-                    time: row.get(5)?,
-                    nonce: row.get(6)?,
-                    speaker: row.get(7)?,
-                    next_consensus: row.get(8)?,
-                    reward: row.get(9)?,
-                    reward_receiver: row.get(10)?,
-                    witnesses: row.get(11)?,
-                })
-            });
-
-            result.map_err(|_| Error {
-                error: "Block does not exist.".to_string(),
-            })
-        }
-        Err(_) => {
-            if !checker::is_neo_txid_hash(&path) {
-                return Err(Error {
-                    error: "Invalid block hash.".to_string(),
-                });
-            }
-
-            let sql = "SELECT * FROM blocks WHERE hash = ?";
-            let mut stmt = conn.prepare(sql).unwrap();
-
-            let result = stmt.query_row([path], |row| {
-                Ok(Block {
-                    index: row.get(0)?,
-                    hash: row.get(1)?,
-                    size: row.get(2)?,
-                    version: row.get(3)?,
-                    merkle_root: row.get(4)?,
-                    time: row.get(5)?,
-                    nonce: row.get(6)?,
-                    speaker: row.get(7)?,
-                    next_consensus: row.get(8)?,
-                    reward: row.get(9)?,
-                    reward_receiver: row.get(10)?,
-                    witnesses: row.get(11)?,
-                })
-            });
-
-            result.map_err(|_| Error {
-                error: "Block does not exist.".to_string(),
-            })
-        }
-    }
-}
-
-pub fn get_block_time(
-    conn: &PooledConnection<SqliteConnectionManager>,
-    path: String,
-) -> Result<u64, Error> {
-    match path.trim().parse::<u64>() {
-        Ok(id) => {
-            let sql = "SELECT time FROM blocks WHERE id = ?";
-            let mut stmt = conn.prepare(sql).unwrap();
-
-            let result = stmt.query_row([id], |row| row.get(0));
-
-            result.map_err(|_| Error {
-                error: "Block does not exist.".to_string(),
-            })
-        }
+pub fn get_block_internal(conn: &dyn Storage, path: String) -> Result<Block, Error> {
+    let result = match path.trim().parse::<u64>() {
+        Ok(id) => conn.query_row("SELECT * FROM blocks WHERE id = $1", &[id.into()]),
         Err(_) => {
             if !checker::is_neo_txid_hash(&path) {
                 return Err(Error {
@@ -92,52 +15,28 @@ pub fn get_block_time(
                 });
             }
 
-            let sql = "SELECT time FROM blocks WHERE hash = ?";
-            let mut stmt = conn.prepare(sql).unwrap();
-
-            let result = stmt.query_row([path], |row| row.get(0));
-
-            result.map_err(|_| Error {
-                error: "Block does not exist.".to_string(),
-            })
+            conn.query_row("SELECT * FROM blocks WHERE hash = $1", &[path.into()])
         }
-    }
+    };
+
+    result
+        .ok()
+        .flatten()
+        .and_then(|row| Block::from_row(&row).ok())
+        .ok_or_else(|| Error {
+            error: "Block does not exist.".to_string(),
+        })
 }
 
 pub fn get_block_transactions_internal(
-    conn: &PooledConnection<SqliteConnectionManager>,
+    conn: &dyn Storage,
     path: String,
 ) -> Result<Vec<Transaction>, Error> {
-    match path.trim().parse::<u64>() {
-        Ok(id) => {
-            let sql = "SELECT * FROM transactions WHERE block_index = ?";
-            let mut stmt = conn.prepare(sql).unwrap();
-
-            let mut rows = stmt.query([id]).unwrap();
-            let mut transactions = Vec::new();
-
-            while let Some(row) = rows.next().unwrap() {
-                transactions.push(Transaction {
-                    index: row.get(0).unwrap(),
-                    hash: row.get(1).unwrap(),
-                    block_index: row.get(2).unwrap(),
-                    vm_state: row.get(3).unwrap(),
-                    size: row.get(4).unwrap(),
-                    version: row.get(5).unwrap(),
-                    nonce: row.get(6).unwrap(),
-                    sender: row.get(7).unwrap(),
-                    sysfee: row.get(8).unwrap(),
-                    netfee: row.get(9).unwrap(),
-                    valid_until: row.get(10).unwrap(),
-                    signers: row.get(11).unwrap(),
-                    script: row.get(12).unwrap(),
-                    witnesses: row.get(13).unwrap(),
-                    stack_result: row.get(14).unwrap(),
-                    notifications: row.get(15).unwrap(),
-                })
-            }
-            Ok(transactions)
-        }
+    let rows = match path.trim().parse::<u64>() {
+        Ok(id) => conn.query(
+            "SELECT * FROM transactions WHERE block_index = $1",
+            &[id.into()],
+        ),
         Err(_) => {
             if !checker::is_neo_txid_hash(&path) {
                 return Err(Error {
@@ -145,33 +44,15 @@ pub fn get_block_transactions_internal(
                 });
             }
 
-            let sql = "SELECT * FROM transactions WHERE block_index = (SELECT id FROM blocks WHERE hash = ?)";
-            let mut stmt = conn.prepare(sql).unwrap();
-
-            let mut rows = stmt.query([path]).unwrap();
-            let mut transactions = Vec::new();
-
-            while let Some(row) = rows.next().unwrap() {
-                transactions.push(Transaction {
-                    index: row.get(0).unwrap(),
-                    hash: row.get(1).unwrap(),
-                    block_index: row.get(2).unwrap(),
-                    vm_state: row.get(3).unwrap(),
-                    size: row.get(4).unwrap(),
-                    version: row.get(5).unwrap(),
-                    nonce: row.get(6).unwrap(),
-                    sender: row.get(7).unwrap(),
-                    sysfee: row.get(8).unwrap(),
-                    netfee: row.get(9).unwrap(),
-                    valid_until: row.get(10).unwrap(),
-                    signers: row.get(11).unwrap(),
-                    script: row.get(12).unwrap(),
-                    witnesses: row.get(13).unwrap(),
-                    stack_result: row.get(14).unwrap(),
-                    notifications: row.get(15).unwrap(),
-                })
-            }
-            Ok(transactions)
+            conn.query(
+                "SELECT * FROM transactions WHERE block_index = (SELECT id FROM blocks WHERE hash = $1)",
+                &[path.into()],
+            )
         }
-    }
+    };
+
+    rows.and_then(|rows| rows.iter().map(Transaction::from_row).collect())
+        .map_err(|e| Error {
+            error: e.to_string(),
+        })
 }
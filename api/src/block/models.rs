@@ -1,3 +1,5 @@
+use lib::db::{Row, StorageError};
+use lib::integer::{self, BigInt, GAS_DECIMALS};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 #[derive(Serialize, Deserialize, Clone)]
@@ -11,7 +13,29 @@ pub struct Block {
     pub nonce: String,
     pub speaker: u8,
     pub next_consensus: String,
-    pub reward: f64,
+    pub reward: String, // in GAS, fixed-point decimal
     pub reward_receiver: String,
     pub witnesses: Value,
 }
+
+impl Block {
+    // columns in table order, i.e. `SELECT * FROM blocks`, where the lossless `reward_amount` is last
+    pub fn from_row(row: &Row) -> Result<Self, StorageError> {
+        let reward: BigInt = row.get(12)?;
+
+        Ok(Block {
+            index: row.get(0)?,
+            hash: row.get(1)?,
+            size: row.get(2)?,
+            version: row.get(3)?,
+            merkle_root: row.get(4)?,
+            time: row.get(5)?,
+            nonce: row.get(6)?,
+            speaker: row.get(7)?,
+            next_consensus: row.get(8)?,
+            reward: integer::format_fixed(&reward, GAS_DECIMALS),
+            reward_receiver: row.get(10)?,
+            witnesses: row.get(11)?,
+        })
+    }
+}
@@ -0,0 +1,72 @@
+use actix_web::{get, web, HttpResponse, Responder};
+
+use crate::error::Error;
+use crate::shared::{checker, models};
+use crate::ConnectionPool;
+
+use super::internals;
+use super::models::EventQuery;
+
+#[get("/v1/contract/{hash}/events")]
+async fn get_contract_events(
+    pool: web::Data<ConnectionPool>,
+    path: web::Path<String>,
+    query: web::Query<EventQuery>,
+) -> impl Responder {
+    let hash = path.into_inner();
+    let query = query.into_inner();
+
+    if !checker::is_neo_script_hash(&hash) {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid contract hash.".to_string(),
+        });
+    }
+
+    let limit = models::page_limit(query.limit);
+    let offset = query.offset.unwrap_or(0);
+
+    let events = pool
+        .connection
+        .run(move |conn| {
+            internals::get_contract_events_internal(conn, hash, query.name, limit, offset)
+        })
+        .await;
+
+    match events {
+        Ok(events) => HttpResponse::Ok().json(events),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
+#[get("/v1/contract/{hash}/oracle-requests")]
+async fn get_oracle_requests(
+    pool: web::Data<ConnectionPool>,
+    path: web::Path<String>,
+    query: web::Query<models::PageQuery>,
+) -> impl Responder {
+    let hash = path.into_inner();
+
+    if !checker::is_neo_script_hash(&hash) {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid contract hash.".to_string(),
+        });
+    }
+
+    let limit = models::page_limit(query.limit);
+    let offset = query.offset.unwrap_or(0);
+
+    let requests = pool
+        .connection
+        .run(move |conn| internals::get_oracle_requests_internal(conn, hash, limit, offset))
+        .await;
+
+    match requests {
+        Ok(requests) => HttpResponse::Ok().json(requests),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_contract_events)
+        .service(get_oracle_requests);
+}
@@ -0,0 +1,80 @@
+use lib::db::Storage;
+
+use super::models::{ContractEvent, ContractEventList, OracleRequest, OracleRequestList};
+use crate::error::Error;
+
+const COLUMNS: &str =
+    "txid, block_index, notification_index, contract, event_name, state, parameters";
+
+// oldest first, in the order they were emitted
+pub fn get_contract_events_internal(
+    conn: &dyn Storage,
+    hash: String,
+    name: Option<String>,
+    limit: u64,
+    offset: u64,
+) -> Result<ContractEventList, Error> {
+    if !conn.table_exists("notifications")? {
+        return Err(Error {
+            error: "Notifications are not indexed yet. Run the latest Indexer.".to_string(),
+        });
+    }
+
+    let rows = match name {
+        Some(name) => conn.query(
+            &format!(
+                "SELECT {COLUMNS} FROM notifications WHERE contract = $1 AND event_name = $2
+                ORDER BY id LIMIT $3 OFFSET $4"
+            ),
+            &[hash.into(), name.into(), limit.into(), offset.into()],
+        ),
+        None => conn.query(
+            &format!(
+                "SELECT {COLUMNS} FROM notifications WHERE contract = $1
+                ORDER BY id LIMIT $2 OFFSET $3"
+            ),
+            &[hash.into(), limit.into(), offset.into()],
+        ),
+    };
+
+    let events = rows.and_then(|rows| rows.iter().map(ContractEvent::from_row).collect())?;
+
+    Ok(ContractEventList {
+        events,
+        limit,
+        offset,
+    })
+}
+
+// most recent first, with their response when there is one
+pub fn get_oracle_requests_internal(
+    conn: &dyn Storage,
+    hash: String,
+    limit: u64,
+    offset: u64,
+) -> Result<OracleRequestList, Error> {
+    if !conn.table_exists("oracle_requests")? {
+        return Err(Error {
+            error: "Oracle requests are not indexed yet. Run the latest Indexer.".to_string(),
+        });
+    }
+
+    let requests = conn
+        .query(
+            &format!(
+                "SELECT {} FROM oracle_requests q
+                LEFT JOIN oracle_responses r ON r.request_id = q.request_id
+                WHERE q.contract = $1
+                ORDER BY q.block_index DESC, q.id DESC LIMIT $2 OFFSET $3",
+                OracleRequest::COLUMNS
+            ),
+            &[hash.into(), limit.into(), offset.into()],
+        )
+        .and_then(|rows| rows.iter().map(OracleRequest::from_row).collect())?;
+
+    Ok(OracleRequestList {
+        requests,
+        limit,
+        offset,
+    })
+}
@@ -0,0 +1,123 @@
+use lib::db::{Row, StorageError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContractEvent {
+    pub txid: String,
+    pub block_index: u64,
+    pub index: u64, // position in the transaction's notifications
+    pub contract: String,
+    pub event_name: String,
+    pub state: Value,
+    pub parameters: Option<Value>, // by name, when the contract's manifest declares the event
+}
+
+impl ContractEvent {
+    pub fn from_row(row: &Row) -> Result<Self, StorageError> {
+        Ok(ContractEvent {
+            txid: row.get(0)?,
+            block_index: row.get(1)?,
+            index: row.get(2)?,
+            contract: row.get(3)?,
+            event_name: row.get(4)?,
+            state: row.get(5)?,
+            parameters: row.get(6)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct ContractEventList {
+    pub events: Vec<ContractEvent>,
+    pub limit: u64,
+    pub offset: u64,
+}
+
+#[derive(Deserialize)]
+pub struct EventQuery {
+    pub name: Option<String>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OracleRequest {
+    pub request_id: u64,
+    pub txid: String,
+    pub block_index: u64,
+    pub block_time: u64,
+    pub contract: String,
+    pub url: String,
+    pub filter: Option<String>,
+    pub gas_for_response: Option<String>,
+    pub status: String, // the response code, or Pending
+    pub response: Option<OracleResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OracleResponse {
+    pub txid: String,
+    pub block_index: u64,
+    pub block_time: u64,
+    pub code: Option<String>,
+    pub result: Option<String>, // base64
+    pub latency_ms: u64,
+    pub latency_blocks: u64,
+}
+
+impl OracleRequest {
+    pub const COLUMNS: &'static str =
+        "q.request_id, q.txid, q.block_index, q.block_time, q.contract,
+        q.url, q.filter, q.gas_for_response, r.txid, r.block_index, r.block_time, r.code, r.result";
+
+    pub fn from_row(row: &Row) -> Result<Self, StorageError> {
+        let block_index: u64 = row.get(2)?;
+        let block_time: u64 = row.get(3)?;
+
+        let response = match row.get::<Option<String>>(8)? {
+            Some(txid) => {
+                let response_block_index: u64 = row.get(9)?;
+                let response_block_time: u64 = row.get(10)?;
+
+                Some(OracleResponse {
+                    txid,
+                    block_index: response_block_index,
+                    block_time: response_block_time,
+                    code: row.get(11)?,
+                    result: row.get(12)?,
+                    latency_ms: response_block_time.saturating_sub(block_time),
+                    latency_blocks: response_block_index.saturating_sub(block_index),
+                })
+            }
+            None => None,
+        };
+        let status = match &response {
+            Some(response) => response
+                .code
+                .clone()
+                .unwrap_or_else(|| "Unknown".to_string()),
+            None => "Pending".to_string(),
+        };
+
+        Ok(OracleRequest {
+            request_id: row.get(0)?,
+            txid: row.get(1)?,
+            block_index,
+            block_time,
+            contract: row.get(4)?,
+            url: row.get(5)?,
+            filter: row.get(6)?,
+            gas_for_response: row.get(7)?,
+            status,
+            response,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct OracleRequestList {
+    pub requests: Vec<OracleRequest>,
+    pub limit: u64,
+    pub offset: u64,
+}
@@ -1,10 +1,27 @@
+use lib::db::StorageError;
 use serde::{Deserialize, Serialize};
 
+use std::fmt;
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Error {
     pub error: String,
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.error)
+    }
+}
+
+impl From<StorageError> for Error {
+    fn from(e: StorageError) -> Self {
+        Error {
+            error: e.to_string(),
+        }
+    }
+}
+
 #[allow(dead_code, clippy::enum_variant_names)]
 pub enum Errors {
     SqlError,
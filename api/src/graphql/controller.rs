@@ -0,0 +1,39 @@
+use actix_web::{get, post, web, HttpResponse, Responder};
+use async_graphql::{EmptyMutation, EmptySubscription, Schema};
+use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse};
+use once_cell::sync::Lazy;
+
+use crate::ConnectionPool;
+
+use super::models::QueryRoot;
+
+// Each list costs its limit (100 by default) times what is asked of every item, so nested
+// lists need explicit limits to stay under the maximum
+const MAX_COMPLEXITY: usize = 5_000;
+const MAX_DEPTH: usize = 10;
+
+pub type ShrikeSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+pub static SCHEMA: Lazy<ShrikeSchema> = Lazy::new(|| {
+    Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .limit_complexity(MAX_COMPLEXITY)
+        .limit_depth(MAX_DEPTH)
+        .finish()
+});
+
+// queries run against the network the request was made to
+#[post("/graphql")]
+async fn graphql(pool: web::Data<ConnectionPool>, request: GraphQLRequest) -> GraphQLResponse {
+    SCHEMA.execute(request.into_inner().data(pool)).await.into()
+}
+
+#[get("/graphql/schema")]
+async fn graphql_schema() -> impl Responder {
+    HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .body(SCHEMA.sdl())
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(graphql).service(graphql_schema);
+}
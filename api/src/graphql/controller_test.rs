@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use crate::graphql::controller::SCHEMA;
+
+    #[test]
+    fn test_query_limits() {
+        use futures::executor::block_on;
+
+        let errors = |query: &str| -> Vec<String> {
+            block_on(SCHEMA.execute(query))
+                .errors
+                .into_iter()
+                .map(|error| error.message)
+                .collect()
+        };
+
+        // 100 blocks of 100 transactions each
+        let nested = "{ blocks { transactions { hash } } }";
+        assert_eq!(errors(nested), vec![String::from("Query is too complex.")]);
+
+        let deep = "{ blocks(limit: 1) { transactions(limit: 1) { transfers { transaction {
+            block { transactions(limit: 1) { transfers { token { events(limit: 1) {
+            transaction { hash } } } } } } } } } } }";
+        assert_eq!(
+            errors(deep),
+            vec![String::from("Query is nested too deep.")]
+        );
+
+        // within the limits the query runs, and only fails for want of a database here
+        let paged = "{ blocks(limit: 10) { hash transactions(limit: 10) { hash } } }";
+        let errors = errors(paged);
+        assert_eq!(errors.len(), 1);
+        assert!(!errors[0].contains("too"));
+    }
+}
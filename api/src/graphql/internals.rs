@@ -0,0 +1,185 @@
+use lib::db::{Storage, Value};
+use lib::neo;
+use serde_json::Value as Json;
+
+use crate::block::models::Block;
+use crate::contract::models::ContractEvent;
+use crate::error::Error;
+use crate::shared::models::Transaction;
+
+const EVENT_COLUMNS: &str =
+    "n.txid, n.block_index, n.notification_index, n.contract, n.event_name, n.state, n.parameters";
+
+fn require_table(conn: &dyn Storage, table: &str, what: &str) -> Result<(), Error> {
+    match conn.table_exists(table)? {
+        true => Ok(()),
+        false => Err(Error {
+            error: format!("{what} are not indexed yet. Run the latest Indexer."),
+        }),
+    }
+}
+
+fn query_blocks(conn: &dyn Storage, sql: &str, params: &[Value]) -> Result<Vec<Block>, Error> {
+    conn.query(sql, params)
+        .and_then(|rows| rows.iter().map(Block::from_row).collect())
+        .map_err(Error::from)
+}
+
+fn query_transactions(
+    conn: &dyn Storage,
+    sql: &str,
+    params: &[Value],
+) -> Result<Vec<Transaction>, Error> {
+    conn.query(sql, params)
+        .and_then(|rows| rows.iter().map(Transaction::from_row).collect())
+        .map_err(Error::from)
+}
+
+fn query_events(
+    conn: &dyn Storage,
+    sql: &str,
+    params: &[Value],
+) -> Result<Vec<ContractEvent>, Error> {
+    conn.query(sql, params)
+        .and_then(|rows| rows.iter().map(ContractEvent::from_row).collect())
+        .map_err(Error::from)
+}
+
+pub fn get_block(conn: &dyn Storage, index: u64) -> Result<Option<Block>, Error> {
+    let blocks = query_blocks(conn, "SELECT * FROM blocks WHERE id = $1", &[index.into()])?;
+    Ok(blocks.into_iter().next())
+}
+
+pub fn get_block_by_hash(conn: &dyn Storage, hash: String) -> Result<Option<Block>, Error> {
+    let blocks = query_blocks(conn, "SELECT * FROM blocks WHERE hash = $1", &[hash.into()])?;
+    Ok(blocks.into_iter().next())
+}
+
+// most recent first
+pub fn get_blocks(conn: &dyn Storage, limit: u64, offset: u64) -> Result<Vec<Block>, Error> {
+    query_blocks(
+        conn,
+        "SELECT * FROM blocks ORDER BY id DESC LIMIT $1 OFFSET $2",
+        &[limit.into(), offset.into()],
+    )
+}
+
+pub fn get_transaction(conn: &dyn Storage, hash: String) -> Result<Option<Transaction>, Error> {
+    let transactions = query_transactions(
+        conn,
+        "SELECT * FROM transactions WHERE hash = $1",
+        &[hash.into()],
+    )?;
+    Ok(transactions.into_iter().next())
+}
+
+// most recent first
+pub fn get_transactions(
+    conn: &dyn Storage,
+    limit: u64,
+    offset: u64,
+) -> Result<Vec<Transaction>, Error> {
+    query_transactions(
+        conn,
+        "SELECT * FROM transactions ORDER BY block_index DESC, id DESC LIMIT $1 OFFSET $2",
+        &[limit.into(), offset.into()],
+    )
+}
+
+// in block order
+pub fn get_block_transactions(
+    conn: &dyn Storage,
+    index: u64,
+    limit: u64,
+    offset: u64,
+) -> Result<Vec<Transaction>, Error> {
+    query_transactions(
+        conn,
+        "SELECT * FROM transactions WHERE block_index = $1 ORDER BY id LIMIT $2 OFFSET $3",
+        &[index.into(), limit.into(), offset.into()],
+    )
+}
+
+pub fn get_block_transaction_count(conn: &dyn Storage, index: u64) -> Result<u64, Error> {
+    conn.query_row(
+        "SELECT COUNT(*) FROM transactions WHERE block_index = $1",
+        &[index.into()],
+    )?
+    .map_or(Ok(0), |row| row.get(0))
+    .map_err(Error::from)
+}
+
+// in the order they were emitted
+pub fn get_transaction_notifications(
+    conn: &dyn Storage,
+    txid: String,
+) -> Result<Vec<ContractEvent>, Error> {
+    require_table(conn, "notifications", "Notifications")?;
+
+    query_events(
+        conn,
+        &format!("SELECT {EVENT_COLUMNS} FROM notifications n WHERE n.txid = $1 ORDER BY n.id"),
+        &[txid.into()],
+    )
+}
+
+// Transfer notifications of successful transactions that mention the address, most recent first.
+// The address is matched in the raw state, so the page can hold a transfer it is not a party to.
+pub fn get_address_transfer_events(
+    conn: &dyn Storage,
+    address: &str,
+    limit: u64,
+    offset: u64,
+) -> Result<Vec<ContractEvent>, Error> {
+    require_table(conn, "notifications", "Notifications")?;
+
+    query_events(
+        conn,
+        &format!(
+            "SELECT {EVENT_COLUMNS} FROM notifications n JOIN transactions t ON t.hash = n.txid
+            WHERE n.event_name = 'Transfer' AND n.state LIKE $1 AND t.vm_state = 'HALT'
+            ORDER BY n.block_index DESC, n.id DESC LIMIT $2 OFFSET $3"
+        ),
+        &[
+            format!("%{}%", neo::address_to_base64(address)).into(),
+            limit.into(),
+            offset.into(),
+        ],
+    )
+}
+
+// the block it was deployed in and the standards it supports, for deployed contracts
+pub fn get_contract(conn: &dyn Storage, hash: String) -> Result<Option<(u64, String)>, Error> {
+    conn.query_row(
+        "SELECT block_index, contract_type FROM contracts WHERE hash = $1",
+        &[hash.into()],
+    )?
+    .map(|row| Ok((row.get(0)?, row.get(1)?)))
+    .transpose()
+}
+
+// most recently deployed first
+pub fn get_contracts(conn: &dyn Storage, limit: u64, offset: u64) -> Result<Vec<String>, Error> {
+    conn.query(
+        "SELECT hash FROM contracts ORDER BY block_index DESC, id DESC LIMIT $1 OFFSET $2",
+        &[limit.into(), offset.into()],
+    )
+    .and_then(|rows| rows.iter().map(|row| row.get(0)).collect())
+    .map_err(Error::from)
+}
+
+// the latest manifest read from a deploy or update script
+pub fn get_contract_manifest(conn: &dyn Storage, hash: String) -> Result<Option<Json>, Error> {
+    if !conn.table_exists("contract_states")? {
+        return Ok(None);
+    }
+
+    conn.query_row(
+        "SELECT manifest FROM contract_states WHERE hash = $1 AND manifest IS NOT NULL
+        ORDER BY block_index DESC, id DESC LIMIT 1",
+        &[hash.into()],
+    )?
+    .map(|row| row.get(0))
+    .transpose()
+    .map_err(Error::from)
+}
@@ -0,0 +1,5 @@
+pub mod controller;
+mod controller_test;
+mod internals;
+pub mod models;
+mod models_test;
@@ -0,0 +1,571 @@
+use actix_web::web;
+use async_graphql::{
+    ComplexObject, Context, Error as GraphQLError, Json, Object, Result, SimpleObject,
+};
+use lib::db::Storage;
+use lib::network::native_contract_name;
+use lib::stack_item::StackItem;
+use serde_json::Value;
+
+use crate::block::models::Block as BlockRow;
+use crate::contract::internals::get_contract_events_internal;
+use crate::contract::models::ContractEvent;
+use crate::error::Error;
+use crate::shared::checker;
+use crate::shared::events::{format_amount, format_gas, token_decimals};
+use crate::shared::models::{page_limit, Event, Transaction as TransactionRow};
+use crate::shared::nns;
+use crate::transaction::internals::get_signer_transactions_internal;
+use crate::ConnectionPool;
+
+use super::internals;
+
+// runs a query on the network the request was made to
+async fn run<T, F>(ctx: &Context<'_>, query: F) -> Result<T>
+where
+    F: FnOnce(&dyn Storage) -> std::result::Result<T, Error> + Send + 'static,
+    T: Send + 'static,
+{
+    let pool = ctx.data::<web::Data<ConnectionPool>>()?;
+    Ok(pool.connection.run(query).await?)
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    // by index or by hash
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        index: Option<u64>,
+        hash: Option<String>,
+    ) -> Result<Option<Block>> {
+        let block = match (index, hash) {
+            (Some(index), None) => run(ctx, move |conn| internals::get_block(conn, index)).await?,
+            (None, Some(hash)) => {
+                if !checker::is_neo_txid_hash(&hash) {
+                    return Err(GraphQLError::new("Invalid block hash."));
+                }
+                run(ctx, move |conn| internals::get_block_by_hash(conn, hash)).await?
+            }
+            _ => return Err(GraphQLError::new("Pass either an index or a hash.")),
+        };
+
+        Ok(block.map(Block::from))
+    }
+
+    // most recent first
+    #[graphql(complexity = "page_limit(limit) as usize * child_complexity")]
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<Vec<Block>> {
+        let (limit, offset) = (page_limit(limit), offset.unwrap_or(0));
+        let blocks = run(ctx, move |conn| internals::get_blocks(conn, limit, offset)).await?;
+
+        Ok(blocks.into_iter().map(Block::from).collect())
+    }
+
+    async fn transaction(&self, ctx: &Context<'_>, hash: String) -> Result<Option<Transaction>> {
+        if !checker::is_neo_txid_hash(&hash) {
+            return Err(GraphQLError::new("Invalid transaction hash."));
+        }
+        let transaction = run(ctx, move |conn| internals::get_transaction(conn, hash)).await?;
+
+        Ok(transaction.map(Transaction::from))
+    }
+
+    // most recent first
+    #[graphql(complexity = "page_limit(limit) as usize * child_complexity")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<Vec<Transaction>> {
+        let (limit, offset) = (page_limit(limit), offset.unwrap_or(0));
+        let transactions = run(ctx, move |conn| {
+            internals::get_transactions(conn, limit, offset)
+        })
+        .await?;
+
+        Ok(transactions.into_iter().map(Transaction::from).collect())
+    }
+
+    // an address, or an NNS name to resolve into one
+    async fn address(&self, ctx: &Context<'_>, address: String) -> Result<Address> {
+        if !checker::is_address_or_name(&address) {
+            return Err(GraphQLError::new("Invalid address."));
+        }
+        let address = run(ctx, move |conn| nns::resolve_address(conn, address)).await?;
+
+        Ok(Address { address })
+    }
+
+    // deployed or native
+    async fn contract(&self, ctx: &Context<'_>, hash: String) -> Result<Option<Contract>> {
+        if !checker::is_neo_script_hash(&hash) {
+            return Err(GraphQLError::new("Invalid contract hash."));
+        }
+        if native_contract_name(&hash).is_some() {
+            return Ok(Some(Contract { hash }));
+        }
+
+        let lookup = hash.clone();
+        let deployed = run(ctx, move |conn| internals::get_contract(conn, lookup)).await?;
+
+        Ok(deployed.map(|_| Contract { hash }))
+    }
+
+    // deployed contracts, most recent first
+    #[graphql(complexity = "page_limit(limit) as usize * child_complexity")]
+    async fn contracts(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<Vec<Contract>> {
+        let (limit, offset) = (page_limit(limit), offset.unwrap_or(0));
+        let hashes = run(ctx, move |conn| {
+            internals::get_contracts(conn, limit, offset)
+        })
+        .await?;
+
+        Ok(hashes.into_iter().map(|hash| Contract { hash }).collect())
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Block {
+    pub index: u64,
+    pub hash: String,
+    pub size: u32,
+    pub version: u8,
+    pub merkle_root: String,
+    pub time: u64, // milliseconds
+    pub nonce: String,
+    pub speaker: u8,
+    pub next_consensus: String,
+    pub reward: String, // in GAS
+    pub reward_receiver: String,
+    pub witnesses: Json<Value>,
+}
+
+impl From<BlockRow> for Block {
+    fn from(block: BlockRow) -> Self {
+        Block {
+            index: block.index,
+            hash: block.hash,
+            size: block.size,
+            version: block.version,
+            merkle_root: block.merkle_root,
+            time: block.time,
+            nonce: block.nonce,
+            speaker: block.speaker,
+            next_consensus: block.next_consensus,
+            reward: block.reward,
+            reward_receiver: block.reward_receiver,
+            witnesses: Json(block.witnesses),
+        }
+    }
+}
+
+#[ComplexObject]
+impl Block {
+    // in block order
+    #[graphql(complexity = "page_limit(limit) as usize * child_complexity")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<Vec<Transaction>> {
+        let (index, limit, offset) = (self.index, page_limit(limit), offset.unwrap_or(0));
+        let transactions = run(ctx, move |conn| {
+            internals::get_block_transactions(conn, index, limit, offset)
+        })
+        .await?;
+
+        Ok(transactions.into_iter().map(Transaction::from).collect())
+    }
+
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Result<u64> {
+        let index = self.index;
+        run(ctx, move |conn| {
+            internals::get_block_transaction_count(conn, index)
+        })
+        .await
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Transaction {
+    pub hash: String,
+    pub block_index: u64,
+    pub block_time: u64,
+    pub vm_state: String,
+    pub exception: Option<String>,
+    pub size: u32,
+    pub version: u8,
+    pub nonce: u64,
+    pub sender: String,
+    pub sysfee: String, // in GAS
+    pub netfee: String,
+    pub gas_consumed: Option<String>, // unknown for transactions indexed before it was stored
+    pub valid_until: u64,
+    pub script: String, // hex
+    pub signers: Json<Value>,
+    pub attributes: Option<Json<Value>>,
+    pub witnesses: Json<Value>,
+    pub stack_result: Json<Value>,
+    #[graphql(skip)]
+    pub notifications: Value,
+}
+
+impl From<TransactionRow> for Transaction {
+    fn from(tx: TransactionRow) -> Self {
+        Transaction {
+            hash: tx.hash,
+            block_index: tx.block_index,
+            block_time: tx.block_time,
+            vm_state: tx.vm_state,
+            exception: tx.exception,
+            size: tx.size,
+            version: tx.version,
+            nonce: tx.nonce,
+            sender: tx.sender,
+            sysfee: format_gas(&tx.sysfee),
+            netfee: format_gas(&tx.netfee),
+            gas_consumed: tx.gas_consumed.as_deref().map(format_gas),
+            valid_until: tx.valid_until,
+            script: tx.script,
+            signers: Json(tx.signers),
+            attributes: tx.attributes.map(Json),
+            witnesses: Json(tx.witnesses),
+            stack_result: Json(tx.stack_result),
+            notifications: tx.notifications,
+        }
+    }
+}
+
+#[ComplexObject]
+impl Transaction {
+    async fn block(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        let index = self.block_index;
+        let block = run(ctx, move |conn| internals::get_block(conn, index)).await?;
+
+        Ok(block.map(Block::from))
+    }
+
+    // in the order they were emitted
+    async fn notifications(&self, ctx: &Context<'_>) -> Result<Vec<Notification>> {
+        let txid = self.hash.clone();
+        let events = run(ctx, move |conn| {
+            internals::get_transaction_notifications(conn, txid)
+        })
+        .await?;
+
+        Ok(events.into_iter().map(Notification::from).collect())
+    }
+
+    // NEP-17 and NEP-11 transfers, read from the stored notifications
+    async fn transfers(&self) -> Vec<Transfer> {
+        // notifications that don't parse can't be transfers
+        let events: Vec<Event> =
+            serde_json::from_value(self.notifications.clone()).unwrap_or_default();
+
+        events
+            .iter()
+            .enumerate()
+            .filter(|(_, event)| event.eventname == "Transfer")
+            .filter_map(|(index, event)| {
+                Transfer::from_event(
+                    &self.hash,
+                    self.block_index,
+                    index as u64,
+                    &event.contract,
+                    &event.state,
+                )
+            })
+            .collect()
+    }
+}
+
+#[derive(SimpleObject, Clone, Debug, PartialEq)]
+#[graphql(complex)]
+pub struct Transfer {
+    pub txid: String,
+    pub block_index: u64,
+    pub index: u64, // position in the transaction's notifications
+    #[graphql(skip)]
+    pub contract: String,
+    pub from: Option<String>, // null for mints
+    pub to: Option<String>,   // null for burns
+    pub amount: String, // fixed-point decimal when the decimals are known, else the raw amount
+    pub raw_amount: String,
+    pub decimals: Option<u32>, // null when unknown, NEP-11 amounts are never scaled
+    pub token_id: Option<String>, // base64, NEP-11 only
+}
+
+impl Transfer {
+    // a Transfer state: from, to and amount, and the token id for NEP-11
+    pub fn from_event(
+        txid: &str,
+        block_index: u64,
+        index: u64,
+        contract: &str,
+        state: &StackItem,
+    ) -> Option<Self> {
+        let (from, to, amount, token_id) = match state.as_array()? {
+            [from, to, amount] => (from, to, amount, None),
+            [from, to, amount, token_id] => (from, to, amount, Some(token_id.as_bytes()?)),
+            _ => return None,
+        };
+
+        let account = |item: &StackItem| match item.is_null() {
+            true => Some(None),
+            false => item.as_address().map(Some),
+        };
+        let (from, to) = (account(from)?, account(to)?);
+        if from.is_none() && to.is_none() {
+            return None;
+        }
+
+        let raw_amount = amount.as_integer()?;
+        let decimals = match token_id {
+            None => token_decimals(contract),
+            Some(_) => None,
+        };
+
+        Some(Transfer {
+            txid: txid.to_string(),
+            block_index,
+            index,
+            contract: contract.to_string(),
+            from,
+            to,
+            amount: format_amount(&raw_amount, decimals),
+            raw_amount: raw_amount.to_string(),
+            decimals,
+            token_id: token_id.map(base64::encode),
+        })
+    }
+}
+
+#[ComplexObject]
+impl Transfer {
+    async fn token(&self) -> Contract {
+        Contract {
+            hash: self.contract.clone(),
+        }
+    }
+
+    async fn transaction(&self, ctx: &Context<'_>) -> Result<Option<Transaction>> {
+        let txid = self.txid.clone();
+        let transaction = run(ctx, move |conn| internals::get_transaction(conn, txid)).await?;
+
+        Ok(transaction.map(Transaction::from))
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct Notification {
+    pub txid: String,
+    pub block_index: u64,
+    pub index: u64, // position in the transaction's notifications
+    #[graphql(skip)]
+    pub contract: String,
+    pub event_name: String,
+    pub state: Json<Value>,
+    pub parameters: Option<Json<Value>>, // by name, when the contract's manifest declares the event
+}
+
+impl From<ContractEvent> for Notification {
+    fn from(event: ContractEvent) -> Self {
+        Notification {
+            txid: event.txid,
+            block_index: event.block_index,
+            index: event.index,
+            contract: event.contract,
+            event_name: event.event_name,
+            state: Json(event.state),
+            parameters: event.parameters.map(Json),
+        }
+    }
+}
+
+impl Notification {
+    fn transfer(&self) -> Option<Transfer> {
+        if self.event_name != "Transfer" {
+            return None;
+        }
+        let state = StackItem::from_json(&self.state).ok()?;
+
+        Transfer::from_event(
+            &self.txid,
+            self.block_index,
+            self.index,
+            &self.contract,
+            &state,
+        )
+    }
+}
+
+#[ComplexObject]
+impl Notification {
+    async fn contract(&self) -> Contract {
+        Contract {
+            hash: self.contract.clone(),
+        }
+    }
+
+    async fn transaction(&self, ctx: &Context<'_>) -> Result<Option<Transaction>> {
+        let txid = self.txid.clone();
+        let transaction = run(ctx, move |conn| internals::get_transaction(conn, txid)).await?;
+
+        Ok(transaction.map(Transaction::from))
+    }
+}
+
+pub struct Contract {
+    pub hash: String,
+}
+
+#[Object]
+impl Contract {
+    async fn hash(&self) -> &str {
+        &self.hash
+    }
+
+    // null for native contracts
+    async fn block_index(&self, ctx: &Context<'_>) -> Result<Option<u64>> {
+        let hash = self.hash.clone();
+        let deployed = run(ctx, move |conn| internals::get_contract(conn, hash)).await?;
+
+        Ok(deployed.map(|(block_index, _)| block_index))
+    }
+
+    // the standards it supports, e.g. NEP-17
+    async fn contract_type(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        let hash = self.hash.clone();
+        let deployed = run(ctx, move |conn| internals::get_contract(conn, hash)).await?;
+
+        Ok(deployed.map(|(_, contract_type)| contract_type))
+    }
+
+    async fn name(&self, ctx: &Context<'_>) -> Result<Option<String>> {
+        if let Some(name) = native_contract_name(&self.hash) {
+            return Ok(Some(name.to_string()));
+        }
+
+        let manifest = self.manifest(ctx).await?;
+        Ok(manifest.and_then(|manifest| manifest["name"].as_str().map(str::to_string)))
+    }
+
+    // the latest manifest, null for native contracts and contracts deployed by other contracts
+    async fn manifest(&self, ctx: &Context<'_>) -> Result<Option<Json<Value>>> {
+        let hash = self.hash.clone();
+        let manifest = run(ctx, move |conn| {
+            internals::get_contract_manifest(conn, hash)
+        })
+        .await?;
+
+        Ok(manifest.map(Json))
+    }
+
+    // oldest first, optionally by event name
+    #[graphql(complexity = "page_limit(limit) as usize * child_complexity")]
+    async fn events(
+        &self,
+        ctx: &Context<'_>,
+        name: Option<String>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<Vec<Notification>> {
+        let (hash, limit, offset) = (self.hash.clone(), page_limit(limit), offset.unwrap_or(0));
+        let events = run(ctx, move |conn| {
+            get_contract_events_internal(conn, hash, name, limit, offset)
+        })
+        .await?;
+
+        Ok(events.events.into_iter().map(Notification::from).collect())
+    }
+
+    // oldest first
+    #[graphql(complexity = "page_limit(limit) as usize * child_complexity")]
+    async fn transfers(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<Vec<Transfer>> {
+        let events = self
+            .events(ctx, Some(String::from("Transfer")), limit, offset)
+            .await?;
+
+        Ok(events.iter().filter_map(Notification::transfer).collect())
+    }
+}
+
+pub struct Address {
+    pub address: String,
+}
+
+#[Object]
+impl Address {
+    async fn address(&self) -> &str {
+        &self.address
+    }
+
+    // signed as sender or cosigner, most recent first
+    #[graphql(complexity = "page_limit(limit) as usize * child_complexity")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<Vec<Transaction>> {
+        let (address, limit, offset) =
+            (self.address.clone(), page_limit(limit), offset.unwrap_or(0));
+        let signed = run(ctx, move |conn| {
+            get_signer_transactions_internal(conn, address, limit, offset)
+        })
+        .await?;
+
+        Ok(signed
+            .transactions
+            .into_iter()
+            .map(Transaction::from)
+            .collect())
+    }
+
+    // sent or received by successful transactions, most recent first
+    #[graphql(complexity = "page_limit(limit) as usize * child_complexity")]
+    async fn transfers(
+        &self,
+        ctx: &Context<'_>,
+        limit: Option<u64>,
+        offset: Option<u64>,
+    ) -> Result<Vec<Transfer>> {
+        let (address, limit, offset) =
+            (self.address.clone(), page_limit(limit), offset.unwrap_or(0));
+        let events = run(ctx, move |conn| {
+            internals::get_address_transfer_events(conn, &address, limit, offset)
+        })
+        .await?;
+
+        let party = Some(self.address.as_str());
+        Ok(events
+            .into_iter()
+            .map(Notification::from)
+            .filter_map(|notification| notification.transfer())
+            .filter(|transfer| transfer.from.as_deref() == party || transfer.to.as_deref() == party)
+            .collect())
+    }
+}
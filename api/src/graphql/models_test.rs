@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use lib::stack_item::StackItem;
+    use serde_json::Value;
+
+    use crate::graphql::models::Transfer;
+
+    #[test]
+    fn test_transfer_from_event() {
+        let state = |items: Value| {
+            StackItem::from_json(&serde_json::json!({ "type": "Array", "value": items })).unwrap()
+        };
+        let address =
+            serde_json::json!({ "type": "ByteString", "value": "axI92L7HGGSIUrvHhZXjU2oFj58=" });
+        let gas = "0xd2a4cff31913016155e38e474a2c06d08be276cf";
+
+        let mint = state(serde_json::json!([
+            { "type": "Any" },
+            address,
+            { "type": "Integer", "value": "40300000" }
+        ]));
+        let transfer = Transfer::from_event("0x01", 5, 1, gas, &mint).unwrap();
+        assert_eq!(transfer.from, None);
+        assert_eq!(
+            transfer.to.as_deref(),
+            Some("NVg7LjGcUSrgxgjX3zEgqaksfMaiS8Z6e1")
+        );
+        assert_eq!(transfer.amount, "0.403");
+        assert_eq!(transfer.raw_amount, "40300000");
+        assert_eq!(transfer.decimals, Some(8));
+        assert_eq!(transfer.token_id, None);
+
+        // tokens without known decimals keep the integer amount
+        let token = "0x0000000000000000000000000000000000000001";
+        let transfer = Transfer::from_event("0x01", 5, 1, token, &mint).unwrap();
+        assert_eq!(transfer.amount, "40300000");
+        assert_eq!(transfer.decimals, None);
+
+        // NEP-11 amounts aren't scaled
+        let nft = state(serde_json::json!([
+            address,
+            { "type": "Any" },
+            { "type": "Integer", "value": "1" },
+            { "type": "ByteString", "value": "AQ==" }
+        ]));
+        let transfer = Transfer::from_event("0x01", 5, 2, gas, &nft).unwrap();
+        assert_eq!(transfer.to, None);
+        assert_eq!(transfer.amount, "1");
+        assert_eq!(transfer.token_id.as_deref(), Some("AQ=="));
+
+        let nowhere = state(serde_json::json!([
+            { "type": "Any" },
+            { "type": "Any" },
+            { "type": "Integer", "value": "1" }
+        ]));
+        assert_eq!(Transfer::from_event("0x01", 5, 3, gas, &nowhere), None);
+
+        let not_an_account = state(serde_json::json!([
+            { "type": "Integer", "value": "1" },
+            address,
+            { "type": "Integer", "value": "1" }
+        ]));
+        assert_eq!(
+            Transfer::from_event("0x01", 5, 4, gas, &not_an_account),
+            None
+        );
+    }
+}
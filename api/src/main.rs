@@ -1,63 +1,204 @@
 mod block;
+mod contract;
 mod error;
+mod graphql;
+mod main_test;
+mod mempool;
+mod nns;
+mod rpc;
 mod shared;
 mod stat;
+mod stateroot;
+mod storage;
 mod transaction;
 
 use actix_cors::Cors;
 use actix_web::{http::header, web, App, HttpServer};
-use lib::db::DB_PATH;
-use r2d2::Pool;
-use r2d2_sqlite::SqliteConnectionManager;
-use rusqlite::OpenFlags;
+use lib::db::{db_path, is_postgres_url, SCHEMA_VERSION};
+use lib::network::{is_valid_network_name, NetworkProfile};
 use tokio::{task, time};
 
-use std::time::Duration;
+use std::{env, sync::RwLock, time::Duration};
+
+use stat::models::{NetworkStatistics, ShrikeStats};
+use storage::StoragePool;
 
 const REFRESH_INTERVAL: u64 = 3; // how often we check for a new block and refresh stats in seconds
+const DEFAULT_NETWORKS: &str = "mainnet"; // comma separated, overridden by SHRIKE_NETWORKS
+const MIN_SCHEMA_VERSION: u32 = 7; // oldest schema the queries here can read
 
 pub struct ConnectionPool {
-    connection: Pool<SqliteConnectionManager>,
+    connection: StoragePool,
+    network: String,
+    rpc_url: Option<String>, // node that proof queries are passed through to
+    stats: RwLock<ShrikeStats>,
+    network_statistics: RwLock<NetworkStatistics>,
+}
+
+// SHRIKE_<NETWORK>_DATABASE_URL points a network at a Postgres database or another SQLite file
+fn database_url(network: &str) -> String {
+    let var = format!(
+        "SHRIKE_{}_DATABASE_URL",
+        network.to_ascii_uppercase().replace('-', "_")
+    );
+
+    env::var(var).unwrap_or_else(|_| {
+        db_path(network)
+            .to_str()
+            .expect("Failed to convert database path to str")
+            .to_string()
+    })
+}
+
+// SHRIKE_<NETWORK>_RPC_URL, defaulting to the local node of known networks
+fn rpc_url(network: &str) -> Option<String> {
+    let var = format!(
+        "SHRIKE_{}_RPC_URL",
+        network.to_ascii_uppercase().replace('-', "_")
+    );
+
+    env::var(var)
+        .ok()
+        .or_else(|| NetworkProfile::from_name(network).map(|profile| profile.rpc_url))
+}
+
+fn open_network(network: &str) -> Result<ConnectionPool, String> {
+    if !is_valid_network_name(network) {
+        return Err(format!("Invalid network name {network}."));
+    }
+
+    let url = database_url(network);
+    // the url can hold credentials, keep it out of the logs
+    let location = if is_postgres_url(&url) {
+        format!("Postgres database for {network}")
+    } else {
+        format!("database at {url}")
+    };
+
+    let pool = StoragePool::open(&url).map_err(|e| format!("{network}: {e}"))?;
+
+    let conn = pool.get().map_err(|e| e.to_string())?;
+    let schema_version = conn.schema_version().map_err(|e| e.to_string())?;
+    check_schema_version(schema_version).map_err(|e| format!("{location}: {e}"))?;
+
+    // databases written before network tracking have no network table
+    let stored_network = conn
+        .query_row("SELECT name FROM network WHERE id = 0", &[])
+        .ok()
+        .flatten()
+        .and_then(|row| row.get::<String>(0).ok());
+    match stored_network {
+        Some(name) if name != network => {
+            return Err(format!("The {location} belongs to {name}, not {network}."))
+        }
+        Some(_) => {}
+        None => println!("The {location} does not record its network, assuming {network}."),
+    }
+
+    Ok(ConnectionPool {
+        connection: pool,
+        network: network.to_string(),
+        rpc_url: rpc_url(network),
+        stats: RwLock::new(ShrikeStats::default()),
+        network_statistics: RwLock::new(NetworkStatistics::default()),
+    })
+}
+
+fn check_schema_version(version: u32) -> Result<(), String> {
+    if version < MIN_SCHEMA_VERSION {
+        Err(format!(
+            "database schema version {version} is older than the minimum supported version {MIN_SCHEMA_VERSION}. Run the Indexer to migrate it."
+        ))
+    } else if version > SCHEMA_VERSION {
+        Err(format!(
+            "database schema version {version} is newer than the latest supported version {SCHEMA_VERSION}. Upgrade the API."
+        ))
+    } else {
+        Ok(())
+    }
 }
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    let db_path = DB_PATH
-        .to_str()
-        .expect("Failed to convert database path to str");
-    let manager =
-        SqliteConnectionManager::file(db_path).with_flags(OpenFlags::SQLITE_OPEN_READ_ONLY);
-    let pool = Pool::new(manager).unwrap();
-
-    let connection_pool = web::Data::new(ConnectionPool { connection: pool });
-
-    let internal_connection = connection_pool.clone();
-
-    task::spawn(async move {
-        let mut interval = time::interval(Duration::from_secs(REFRESH_INTERVAL));
-        loop {
-            let c = internal_connection.clone();
-            interval.tick().await;
-            stat::internals::set_stats_internal(c).await;
+    let networks = env::var("SHRIKE_NETWORKS").unwrap_or_else(|_| DEFAULT_NETWORKS.to_string());
+
+    let mut pools = Vec::new();
+    for network in networks.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        let network = network.to_string();
+        let opened = web::block(move || open_network(&network))
+            .await
+            .expect("Failed to open network");
+
+        match opened {
+            Ok(pool) => pools.push(web::Data::new(pool)),
+            Err(e) => {
+                eprintln!("{e}");
+                std::process::exit(1);
+            }
         }
-    });
+    }
+
+    if pools.is_empty() {
+        eprintln!("No networks configured.");
+        std::process::exit(1);
+    }
+
+    for pool in &pools {
+        let internal_connection = pool.clone();
 
+        task::spawn(async move {
+            let mut interval = time::interval(Duration::from_secs(REFRESH_INTERVAL));
+            loop {
+                let c = internal_connection.clone();
+                interval.tick().await;
+                stat::internals::set_stats_internal(c).await;
+            }
+        });
+    }
+
+    // the first network is also served without a prefix
+    let default_pool = pools[0].clone();
+
+    for pool in &pools {
+        println!(
+            "Serving {} under http://0.0.0.0:8080/{}/v1.",
+            pool.network, pool.network
+        );
+    }
     println!("Opening to requests on http://0.0.0.0:8080.");
 
     HttpServer::new(move || {
         let cors = Cors::default()
             .allow_any_origin()
-            .allowed_methods(vec!["GET"])
+            .allowed_methods(vec!["GET", "POST"])
             .allowed_header(header::CONTENT_TYPE)
             .max_age(3600);
-        App::new()
-            .wrap(cors)
-            .app_data(connection_pool.clone())
-            .configure(block::controller::config)
-            .configure(transaction::controller::config)
-            .configure(stat::controller::config)
+
+        let mut app = App::new().wrap(cors);
+        for pool in &pools {
+            app = app.service(
+                web::scope(&format!("/{}", pool.network))
+                    .app_data(pool.clone())
+                    .configure(configure_routes),
+            );
+        }
+
+        app.app_data(default_pool.clone())
+            .configure(configure_routes)
     })
     .bind(("0.0.0.0", 8080))?
     .run()
     .await
 }
+
+fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.configure(block::controller::config)
+        .configure(transaction::controller::config)
+        .configure(contract::controller::config)
+        .configure(nns::controller::config)
+        .configure(mempool::controller::config)
+        .configure(stateroot::controller::config)
+        .configure(rpc::controller::config)
+        .configure(graphql::controller::config)
+        .configure(stat::controller::config);
+}
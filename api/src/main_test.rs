@@ -0,0 +1,13 @@
+#[cfg(test)]
+mod tests {
+    use crate::{check_schema_version, MIN_SCHEMA_VERSION};
+    use lib::db::SCHEMA_VERSION;
+
+    #[test]
+    fn test_check_schema_version() {
+        assert!(check_schema_version(MIN_SCHEMA_VERSION).is_ok());
+        assert!(check_schema_version(SCHEMA_VERSION).is_ok());
+        assert!(check_schema_version(0).is_err());
+        assert!(check_schema_version(SCHEMA_VERSION + 1).is_err());
+    }
+}
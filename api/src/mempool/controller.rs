@@ -0,0 +1,67 @@
+use actix_web::{get, web, HttpResponse, Responder};
+
+use crate::error::Error;
+use crate::shared::{checker, models, nns};
+use crate::ConnectionPool;
+
+use super::internals;
+
+#[get("/v1/mempool/{hash}")]
+async fn get_pending_transaction(
+    pool: web::Data<ConnectionPool>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let hash = path.into_inner();
+
+    if !checker::is_neo_txid_hash(&hash) {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid transaction hash.".to_string(),
+        });
+    }
+
+    let transaction = pool
+        .connection
+        .run(move |conn| internals::get_pending_transaction_internal(conn, hash))
+        .await;
+
+    match transaction {
+        Ok(tx) => HttpResponse::Ok().json(tx),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
+#[get("/v1/mempool/address/{address}")]
+async fn get_address_pending_transactions(
+    pool: web::Data<ConnectionPool>,
+    path: web::Path<String>,
+    query: web::Query<models::PageQuery>,
+) -> impl Responder {
+    let address = path.into_inner();
+
+    if !checker::is_address_or_name(&address) {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid address.".to_string(),
+        });
+    }
+
+    let limit = models::page_limit(query.limit);
+    let offset = query.offset.unwrap_or(0);
+
+    let transactions = pool
+        .connection
+        .run(move |conn| {
+            let address = nns::resolve_address(conn, address)?;
+            internals::get_address_pending_transactions_internal(conn, address, limit, offset)
+        })
+        .await;
+
+    match transactions {
+        Ok(txs) => HttpResponse::Ok().json(txs),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_address_pending_transactions)
+        .service(get_pending_transaction);
+}
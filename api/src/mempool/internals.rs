@@ -0,0 +1,58 @@
+use lib::db::Storage;
+
+use crate::error::Error;
+
+use super::models::{PendingTransaction, PendingTransactionList};
+
+const COLUMNS: &str = "hash, sender, sysfee, netfee, valid_until, first_seen, status, block_index, latency, expired_at";
+
+fn check_indexed(conn: &dyn Storage) -> Result<(), Error> {
+    match conn.table_exists("mempool")? {
+        true => Ok(()),
+        false => Err(Error {
+            error: "Pending transactions are not indexed yet. Run the latest Indexer.".to_string(),
+        }),
+    }
+}
+
+pub fn get_pending_transaction_internal(
+    conn: &dyn Storage,
+    hash: String,
+) -> Result<PendingTransaction, Error> {
+    check_indexed(conn)?;
+
+    conn.query_row(
+        &format!("SELECT {COLUMNS} FROM mempool WHERE hash = $1"),
+        &[hash.into()],
+    )
+    .and_then(|row| row.as_ref().map(PendingTransaction::from_row).transpose())?
+    .ok_or_else(|| Error {
+        error: "Transaction was not seen in the mempool.".to_string(),
+    })
+}
+
+pub fn get_address_pending_transactions_internal(
+    conn: &dyn Storage,
+    address: String,
+    limit: u64,
+    offset: u64,
+) -> Result<PendingTransactionList, Error> {
+    check_indexed(conn)?;
+
+    let transactions = conn
+        .query(
+            &format!(
+                "SELECT {COLUMNS} FROM mempool WHERE sender = $1
+                ORDER BY first_seen DESC, id DESC LIMIT $2 OFFSET $3"
+            ),
+            &[(&address).into(), limit.into(), offset.into()],
+        )
+        .and_then(|rows| rows.iter().map(PendingTransaction::from_row).collect())?;
+
+    Ok(PendingTransactionList {
+        address,
+        transactions,
+        limit,
+        offset,
+    })
+}
@@ -0,0 +1,3 @@
+pub mod controller;
+mod internals;
+pub mod models;
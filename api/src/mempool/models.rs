@@ -0,0 +1,41 @@
+use lib::db::{Row, StorageError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PendingTransaction {
+    pub hash: String,
+    pub sender: String,
+    pub sysfee: String,
+    pub netfee: String,
+    pub valid_until: u64,
+    pub first_seen: u64,
+    pub status: String, // pending, confirmed or expired
+    pub block_index: Option<u64>,
+    pub latency_ms: Option<u64>, // from first seen to the including block
+    pub expired_at: Option<u64>,
+}
+
+impl PendingTransaction {
+    pub fn from_row(row: &Row) -> Result<Self, StorageError> {
+        Ok(PendingTransaction {
+            hash: row.get(0)?,
+            sender: row.get(1)?,
+            sysfee: row.get(2)?,
+            netfee: row.get(3)?,
+            valid_until: row.get(4)?,
+            first_seen: row.get(5)?,
+            status: row.get(6)?,
+            block_index: row.get(7)?,
+            latency_ms: row.get(8)?,
+            expired_at: row.get(9)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct PendingTransactionList {
+    pub address: String,
+    pub transactions: Vec<PendingTransaction>,
+    pub limit: u64,
+    pub offset: u64,
+}
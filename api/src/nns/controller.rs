@@ -0,0 +1,56 @@
+use actix_web::{get, web, HttpResponse, Responder};
+
+use crate::error::Error;
+use crate::shared::checker;
+use crate::ConnectionPool;
+
+use super::internals;
+
+#[get("/v1/nns/{name}")]
+async fn get_domain(pool: web::Data<ConnectionPool>, path: web::Path<String>) -> impl Responder {
+    let name = path.into_inner();
+
+    if !checker::is_nns_name(&name) {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid name.".to_string(),
+        });
+    }
+
+    let domain = pool
+        .connection
+        .run(move |conn| internals::get_domain_internal(conn, name))
+        .await;
+
+    match domain {
+        Ok(domain) => HttpResponse::Ok().json(domain),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
+#[get("/v1/nns/address/{address}")]
+async fn get_address_names(
+    pool: web::Data<ConnectionPool>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let address = path.into_inner();
+
+    if !checker::is_neo_address(&address) {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid address.".to_string(),
+        });
+    }
+
+    let names = pool
+        .connection
+        .run(move |conn| internals::get_address_names_internal(conn, address))
+        .await;
+
+    match names {
+        Ok(names) => HttpResponse::Ok().json(names),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_domain).service(get_address_names);
+}
@@ -0,0 +1,79 @@
+use lib::db::{Storage, StorageError};
+use lib::neo::nns_domain;
+
+use crate::error::Error;
+use crate::shared::nns::{self, TXT_RECORD};
+use crate::shared::time::now;
+
+use super::models::{NnsDomain, NnsNameList, NnsRecord};
+
+fn check_indexed(conn: &dyn Storage) -> Result<(), Error> {
+    match conn.table_exists("nns_domains")? {
+        true => Ok(()),
+        false => Err(Error {
+            error: "NNS names are not indexed yet. Run the latest Indexer.".to_string(),
+        }),
+    }
+}
+
+pub fn get_domain_internal(conn: &dyn Storage, name: String) -> Result<NnsDomain, Error> {
+    check_indexed(conn)?;
+
+    let name = name.to_lowercase();
+    let domain = nns_domain(&name);
+
+    let (owner, expiration) = conn
+        .query_row(
+            "SELECT owner, expiration FROM nns_domains WHERE name = $1 AND expiration > $2",
+            &[domain.as_str().into(), now().into()],
+        )?
+        .map(|row| Ok::<_, StorageError>((row.get(0)?, row.get(1)?)))
+        .transpose()?
+        .ok_or_else(|| Error {
+            error: "Name is not registered.".to_string(),
+        })?;
+
+    let records = conn
+        .query(
+            "SELECT name, record_type, data FROM nns_records WHERE domain = $1
+            ORDER BY name, record_type",
+            &[domain.as_str().into()],
+        )
+        .and_then(|rows| rows.iter().map(NnsRecord::from_row).collect())?;
+
+    Ok(NnsDomain {
+        address: nns::resolve_name(conn, &name)?,
+        name,
+        domain,
+        owner,
+        expiration,
+        records,
+    })
+}
+
+// the names that resolve to the address
+pub fn get_address_names_internal(
+    conn: &dyn Storage,
+    address: String,
+) -> Result<NnsNameList, Error> {
+    check_indexed(conn)?;
+
+    let candidates: Vec<String> = conn
+        .query(
+            "SELECT name FROM nns_domains WHERE owner = $1 AND expiration > $2
+            UNION
+            SELECT name FROM nns_records WHERE record_type = $3 AND data = $1
+            ORDER BY name",
+            &[(&address).into(), now().into(), TXT_RECORD.into()],
+        )
+        .and_then(|rows| rows.iter().map(|row| row.get::<String>(0)).collect())?;
+
+    let mut names = Vec::new();
+    for name in candidates {
+        if nns::resolve_name(conn, &name)?.as_deref() == Some(address.as_str()) {
+            names.push(name);
+        }
+    }
+
+    Ok(NnsNameList { address, names })
+}
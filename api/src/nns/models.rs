@@ -0,0 +1,35 @@
+use lib::db::{Row, StorageError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NnsRecord {
+    pub name: String,
+    pub record_type: u64, // 1 A, 5 CNAME, 16 TXT, 28 AAAA
+    pub data: String,
+}
+
+impl NnsRecord {
+    pub fn from_row(row: &Row) -> Result<Self, StorageError> {
+        Ok(NnsRecord {
+            name: row.get(0)?,
+            record_type: row.get(1)?,
+            data: row.get(2)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NnsDomain {
+    pub name: String,
+    pub address: Option<String>, // what the name resolves to
+    pub domain: String,
+    pub owner: String,
+    pub expiration: u64,
+    pub records: Vec<NnsRecord>, // of the domain and its subdomains
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct NnsNameList {
+    pub address: String,
+    pub names: Vec<String>,
+}
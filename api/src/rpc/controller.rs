@@ -0,0 +1,116 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use futures::future::join_all;
+use lib::payloads::hash160_from_hex;
+use lib::script::{CallFlags, ScriptBuilder};
+use lib::stack_item::StackItem;
+use serde_json::{json, Value};
+
+use crate::shared::node;
+use crate::ConnectionPool;
+
+use super::internals;
+use super::models::{
+    Nep17Balance, RpcError, RpcRequest, RpcResponse, INVALID_PARAMS, INVALID_REQUEST, PARSE_ERROR,
+};
+
+// NeoGo's read-only methods, answered from the index. A batch is answered in one array.
+#[post("/rpc")]
+async fn rpc(pool: web::Data<ConnectionPool>, body: web::Bytes) -> impl Responder {
+    let Ok(request) = serde_json::from_slice::<Value>(&body) else {
+        let error = RpcError::new(PARSE_ERROR, "");
+        return HttpResponse::Ok().json(RpcResponse::new(Value::Null, Err(error)));
+    };
+
+    match request {
+        Value::Array(requests) if !requests.is_empty() => {
+            let responses = join_all(requests.into_iter().map(|request| handle(&pool, request)));
+            HttpResponse::Ok().json(responses.await)
+        }
+        request => HttpResponse::Ok().json(handle(&pool, request).await),
+    }
+}
+
+async fn handle(pool: &ConnectionPool, request: Value) -> RpcResponse {
+    let id = request.get("id").cloned().unwrap_or_default();
+
+    let request = match serde_json::from_value::<RpcRequest>(request) {
+        Ok(request) if request.jsonrpc == "2.0" => request,
+        _ => return RpcResponse::new(id, Err(RpcError::new(INVALID_REQUEST, ""))),
+    };
+
+    // positional params only
+    let params = match request.params {
+        None => Vec::new(),
+        Some(Value::Array(params)) => params,
+        Some(_) => {
+            let error = RpcError::new(INVALID_PARAMS, "params must be an array");
+            return RpcResponse::new(id, Err(error));
+        }
+    };
+
+    let method = request.method;
+    let result = match method.as_str() {
+        "getnep17balances" => get_nep17_balances(pool, params).await,
+        _ => {
+            pool.connection
+                .run(move |conn| internals::call(conn, &method, &params))
+                .await
+        }
+    };
+
+    RpcResponse::new(request.id, result)
+}
+
+async fn get_nep17_balances(pool: &ConnectionPool, params: Vec<Value>) -> Result<Value, RpcError> {
+    let mut balances = pool
+        .connection
+        .run(move |conn| internals::get_nep17_balances(conn, &params))
+        .await?;
+
+    let rpc_url = pool.rpc_url.as_deref();
+    join_all(
+        balances
+            .balance
+            .iter_mut()
+            .filter(|balance| balance.symbol.is_none())
+            .map(|balance| token_metadata(rpc_url, balance)),
+    )
+    .await;
+
+    internals::to_result(balances)
+}
+
+// symbol and decimals of other tokens, when there is a node to run them on
+async fn token_metadata(rpc_url: Option<&str>, balance: &mut Nep17Balance) {
+    let Some(hash) = hash160_from_hex(&balance.assethash) else {
+        return;
+    };
+
+    let mut builder = ScriptBuilder::new();
+    for method in ["symbol", "decimals"] {
+        if builder
+            .contract_call(&hash, method, CallFlags::ReadOnly, &[])
+            .is_err()
+        {
+            return;
+        }
+    }
+    let script = base64::encode(builder.into_bytes());
+
+    let Ok(result) = node::call(rpc_url, "invokescript", json!([script])).await else {
+        return;
+    };
+    if result["state"] != "HALT" {
+        return;
+    }
+
+    let stack: Vec<StackItem> = serde_json::from_value(result["stack"].clone()).unwrap_or_default();
+    if let [symbol, decimals] = stack.as_slice() {
+        balance.symbol = symbol.as_utf8();
+        balance.decimals = decimals.as_integer().map(|decimals| decimals.to_string());
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(rpc);
+}
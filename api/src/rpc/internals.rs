@@ -0,0 +1,630 @@
+use lib::db::{Storage, StorageError};
+use lib::integer::BigInt;
+use lib::neo;
+use lib::network::{GAS_TOKEN, NEO_TOKEN};
+use lib::stack_item::StackItem;
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use std::collections::BTreeMap;
+
+use crate::shared::checker;
+use crate::shared::models::{Transaction, MAX_PAGE_LIMIT};
+use crate::shared::time::now;
+
+use super::models::{
+    ApplicationLog, ContractState, Execution, Nep17Balance, Nep17Balances, Nep17Transfer,
+    Nep17Transfers, RpcBlock, RpcError, RpcTransaction, INTERNAL_ERROR, INVALID_PARAMS,
+    METHOD_NOT_FOUND, UNKNOWN_BLOCK, UNKNOWN_CONTRACT, UNKNOWN_TRANSACTION,
+};
+
+const ZERO_HASH: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+const TRANSFER_WINDOW: u64 = 7 * 24 * 60 * 60 * 1000; // getnep17transfers default, in milliseconds
+const BLOCK_COLUMNS: &str =
+    "id, hash, size, version, merkle_root, time, nonce, speaker, next_consensus, witnesses";
+
+fn sql_error(e: StorageError) -> RpcError {
+    RpcError::new(INTERNAL_ERROR, &e.to_string())
+}
+
+fn invalid_params(data: &str) -> RpcError {
+    RpcError::new(INVALID_PARAMS, data)
+}
+
+pub fn to_result(value: impl Serialize) -> Result<Value, RpcError> {
+    serde_json::to_value(value).map_err(|e| RpcError::new(INTERNAL_ERROR, &e.to_string()))
+}
+
+// the methods answered from the database alone, getnep17balances has a step of its own
+pub fn call(conn: &dyn Storage, method: &str, params: &[Value]) -> Result<Value, RpcError> {
+    match method {
+        "getblockcount" => to_result(get_block_count(conn)?),
+        "getblockhash" => {
+            let index = param(params, 0)
+                .and_then(index_param)
+                .ok_or_else(|| invalid_params("block index expected"))?;
+            let hash = block_hash(conn, index)?.ok_or_else(|| RpcError::new(UNKNOWN_BLOCK, ""))?;
+            to_result(hash)
+        }
+        "getblock" => to_result(get_block(conn, params)?),
+        "getrawtransaction" => to_result(get_raw_transaction(conn, params)?),
+        "getapplicationlog" => to_result(get_application_log(conn, params)?),
+        "getnep17transfers" => to_result(get_nep17_transfers(conn, params)?),
+        "getcontractstate" => to_result(get_contract_state(conn, params)?),
+        _ => Err(RpcError::new(METHOD_NOT_FOUND, "")),
+    }
+}
+
+// a missing param and an explicit null mean the same
+fn param(params: &[Value], index: usize) -> Option<&Value> {
+    params.get(index).filter(|value| !value.is_null())
+}
+
+// numbers, or numbers in strings as some clients send them
+fn index_param(value: &Value) -> Option<u64> {
+    value.as_u64().or_else(|| value.as_str()?.parse().ok())
+}
+
+pub fn optional_index_param(params: &[Value], index: usize) -> Result<Option<u64>, RpcError> {
+    param(params, index)
+        .map(|value| index_param(value).ok_or_else(|| invalid_params("integer expected")))
+        .transpose()
+}
+
+pub fn verbose_param(params: &[Value], index: usize) -> Result<bool, RpcError> {
+    match param(params, index) {
+        None => Ok(false),
+        Some(Value::Bool(verbose)) => Ok(*verbose),
+        Some(value) => index_param(value)
+            .map(|verbose| verbose != 0)
+            .ok_or_else(|| invalid_params("boolean expected")),
+    }
+}
+
+// with or without the 0x prefix, as stored: prefixed and lowercase
+pub fn hash_param(params: &[Value], index: usize, length: usize) -> Option<String> {
+    let hash = param(params, index)?.as_str()?;
+    let hash = format!("0x{}", hash.trim_start_matches("0x").to_ascii_lowercase());
+
+    match length {
+        64 => checker::is_neo_txid_hash(&hash),
+        _ => checker::is_neo_script_hash(&hash),
+    }
+    .then_some(hash)
+}
+
+fn hash256_param(params: &[Value], index: usize) -> Result<String, RpcError> {
+    hash_param(params, index, 64).ok_or_else(|| invalid_params("hash expected"))
+}
+
+// an address, or a script hash to turn into one
+pub fn address_param(params: &[Value], index: usize) -> Result<String, RpcError> {
+    if let Some(address) = param(params, index).and_then(Value::as_str) {
+        if checker::is_neo_address(address) {
+            return Ok(address.to_string());
+        }
+    }
+
+    hash_param(params, index, 40)
+        .map(|hash| neo::scripthash_to_address(&neo::reverse_hex(&hash[2..])))
+        .ok_or_else(|| invalid_params("address or script hash expected"))
+}
+
+fn require_table(conn: &dyn Storage, table: &str, what: &str) -> Result<(), RpcError> {
+    match conn.table_exists(table).map_err(sql_error)? {
+        true => Ok(()),
+        false => Err(RpcError::new(
+            INTERNAL_ERROR,
+            &format!("{what} are not indexed yet. Run the latest Indexer."),
+        )),
+    }
+}
+
+fn get_block_count(conn: &dyn Storage) -> Result<u64, RpcError> {
+    conn.query_row("SELECT COALESCE(MAX(id) + 1, 0) FROM blocks", &[])
+        .map_err(sql_error)?
+        .map_or(Ok(0), |row| row.get(0))
+        .map_err(sql_error)
+}
+
+fn block_hash(conn: &dyn Storage, index: u64) -> Result<Option<String>, RpcError> {
+    conn.query_row("SELECT hash FROM blocks WHERE id = $1", &[index.into()])
+        .map_err(sql_error)?
+        .map(|row| row.get(0))
+        .transpose()
+        .map_err(sql_error)
+}
+
+// the transaction as it was sent, the script back in base64
+fn rpc_transaction(tx: Transaction) -> RpcTransaction {
+    RpcTransaction {
+        script: neo::hex_to_base64(&tx.script),
+        hash: tx.hash,
+        size: tx.size,
+        version: tx.version,
+        nonce: tx.nonce,
+        sender: tx.sender,
+        sysfee: tx.sysfee,
+        netfee: tx.netfee,
+        validuntilblock: tx.valid_until,
+        signers: tx.signers,
+        attributes: tx.attributes.unwrap_or_else(|| json!([])),
+        witnesses: tx.witnesses,
+        blockhash: None,
+        confirmations: None,
+        blocktime: None,
+        vmstate: None,
+    }
+}
+
+// only the verbose form, the index doesn't hold what the binary one needs to be rebuilt
+fn require_verbose(params: &[Value], index: usize) -> Result<(), RpcError> {
+    match verbose_param(params, index)? {
+        true => Ok(()),
+        false => Err(invalid_params(
+            "only verbose output is served from the index, pass verbose = true",
+        )),
+    }
+}
+
+fn get_block(conn: &dyn Storage, params: &[Value]) -> Result<RpcBlock, RpcError> {
+    let block = param(params, 0).ok_or_else(|| invalid_params("block hash or index expected"))?;
+    require_verbose(params, 1)?;
+
+    let row = match index_param(block) {
+        Some(index) => conn.query_row(
+            &format!("SELECT {BLOCK_COLUMNS} FROM blocks WHERE id = $1"),
+            &[index.into()],
+        ),
+        None => conn.query_row(
+            &format!("SELECT {BLOCK_COLUMNS} FROM blocks WHERE hash = $1"),
+            &[hash256_param(params, 0)?.into()],
+        ),
+    }
+    .map_err(sql_error)?
+    .ok_or_else(|| RpcError::new(UNKNOWN_BLOCK, ""))?;
+
+    let index: u64 = row.get(0).map_err(sql_error)?;
+    let previousblockhash = match index.checked_sub(1) {
+        Some(previous) => block_hash(conn, previous)?.unwrap_or_default(),
+        None => ZERO_HASH.to_string(),
+    };
+
+    let tx = conn
+        .query(
+            "SELECT * FROM transactions WHERE block_index = $1 ORDER BY id",
+            &[index.into()],
+        )
+        .and_then(|rows| {
+            rows.iter()
+                .map(Transaction::from_row)
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .map_err(sql_error)?
+        .into_iter()
+        .map(rpc_transaction)
+        .collect();
+
+    let block = (|| {
+        Ok::<_, StorageError>(RpcBlock {
+            hash: row.get(1)?,
+            size: row.get(2)?,
+            version: row.get(3)?,
+            previousblockhash,
+            merkleroot: row.get(4)?,
+            time: row.get(5)?,
+            nonce: row.get(6)?,
+            index,
+            primary: row.get(7)?,
+            nextconsensus: row.get(8)?,
+            witnesses: row.get(9)?,
+            tx,
+            confirmations: 0,
+            nextblockhash: None,
+        })
+    })()
+    .map_err(sql_error)?;
+
+    Ok(RpcBlock {
+        confirmations: get_block_count(conn)? - index,
+        nextblockhash: block_hash(conn, index + 1)?,
+        ..block
+    })
+}
+
+fn get_raw_transaction(conn: &dyn Storage, params: &[Value]) -> Result<RpcTransaction, RpcError> {
+    let hash = hash256_param(params, 0)?;
+    require_verbose(params, 1)?;
+
+    // the block hash follows the transaction columns
+    let row = conn
+        .query_row(
+            "SELECT t.*, b.hash FROM transactions t JOIN blocks b ON b.id = t.block_index
+            WHERE t.hash = $1",
+            &[hash.into()],
+        )
+        .map_err(sql_error)?
+        .ok_or_else(|| RpcError::new(UNKNOWN_TRANSACTION, ""))?;
+
+    let tx = Transaction::from_row(&row).map_err(sql_error)?;
+    let blockhash: String = row.get(20).map_err(sql_error)?;
+    let confirmations = get_block_count(conn)? - tx.block_index;
+    let (blocktime, vmstate) = (tx.block_time, tx.vm_state.clone());
+
+    Ok(RpcTransaction {
+        blockhash: Some(blockhash),
+        confirmations: Some(confirmations),
+        blocktime: Some(blocktime),
+        vmstate: Some(vmstate),
+        ..rpc_transaction(tx)
+    })
+}
+
+fn get_application_log(conn: &dyn Storage, params: &[Value]) -> Result<ApplicationLog, RpcError> {
+    let hash = hash256_param(params, 0)?;
+
+    let tx = conn
+        .query_row(
+            "SELECT * FROM transactions WHERE hash = $1",
+            &[(&hash).into()],
+        )
+        .map_err(sql_error)?
+        .map(|row| Transaction::from_row(&row))
+        .transpose()
+        .map_err(sql_error)?;
+
+    let Some(tx) = tx else {
+        // OnPersist and PostPersist aren't indexed
+        let block = conn
+            .query_row("SELECT id FROM blocks WHERE hash = $1", &[hash.into()])
+            .map_err(sql_error)?;
+
+        return Err(match block {
+            Some(_) => invalid_params("block application logs are not indexed"),
+            None => RpcError::new(UNKNOWN_TRANSACTION, ""),
+        });
+    };
+
+    // a transaction only runs in the Application trigger
+    let trigger = param(params, 1).and_then(Value::as_str);
+    let executions = match trigger {
+        None | Some("Application") | Some("All") => vec![Execution {
+            trigger: String::from("Application"),
+            vmstate: tx.vm_state,
+            exception: tx.exception,
+            gasconsumed: tx.gas_consumed.unwrap_or_else(|| String::from("0")),
+            stack: tx.stack_result,
+            notifications: tx.notifications,
+        }],
+        Some(_) => Vec::new(),
+    };
+
+    Ok(ApplicationLog {
+        txid: tx.hash,
+        executions,
+    })
+}
+
+// a NEP-17 Transfer state: from and to, null for mints and burns, and the amount
+pub fn transfer_state(state: &StackItem) -> Option<(Option<String>, Option<String>, BigInt)> {
+    let [from, to, amount] = state.as_array()? else {
+        return None;
+    };
+
+    let account = |item: &StackItem| match item.is_null() {
+        true => Some(None),
+        false => item.as_address().map(Some),
+    };
+
+    Some((account(from)?, account(to)?, amount.as_integer()?))
+}
+
+pub struct TransferRow {
+    pub contract: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub amount: BigInt,
+    pub block_index: u64,
+    pub notification_index: u64,
+    pub txid: String,
+    pub time: u64,
+}
+
+// successful transfers the address took part in, most recent first
+fn transfers(
+    conn: &dyn Storage,
+    address: &str,
+    window: Option<(u64, u64)>,
+) -> Result<Vec<TransferRow>, RpcError> {
+    require_table(conn, "notifications", "Notifications")?;
+
+    let mut sql = String::from(
+        "SELECT n.contract, n.state, n.block_index, n.notification_index, n.txid, t.block_time
+        FROM notifications n JOIN transactions t ON t.hash = n.txid
+        WHERE n.event_name = 'Transfer' AND n.state LIKE $1 AND t.vm_state = 'HALT'",
+    );
+    let mut sql_params = vec![format!("%{}%", neo::address_to_base64(address)).into()];
+    if let Some((start, end)) = window {
+        sql.push_str(" AND t.block_time >= $2 AND t.block_time <= $3");
+        sql_params.extend([start.into(), end.into()]);
+    }
+    sql.push_str(" ORDER BY n.block_index DESC, n.id DESC");
+
+    let rows = conn.query(&sql, &sql_params).map_err(sql_error)?;
+
+    let mut transfers = Vec::new();
+    for row in rows {
+        let state: Value = row.get(1).map_err(sql_error)?;
+        let Some((from, to, amount)) = StackItem::from_json(&state)
+            .ok()
+            .as_ref()
+            .and_then(transfer_state)
+        else {
+            continue;
+        };
+
+        transfers.push(TransferRow {
+            contract: row.get(0).map_err(sql_error)?,
+            from,
+            to,
+            amount,
+            block_index: row.get(2).map_err(sql_error)?,
+            notification_index: row.get(3).map_err(sql_error)?,
+            txid: row.get(4).map_err(sql_error)?,
+            time: row.get(5).map_err(sql_error)?,
+        });
+    }
+
+    Ok(transfers)
+}
+
+// NeoGo's paging: `limit` transfers after skipping `page` times as many, sent and received together
+pub fn split_transfers(
+    address: &str,
+    transfers: Vec<TransferRow>,
+    limit: u64,
+    page: u64,
+) -> (Vec<Nep17Transfer>, Vec<Nep17Transfer>) {
+    let (mut sent, mut received) = (Vec::new(), Vec::new());
+    let mut skip = limit.saturating_mul(page);
+    let mut remaining = limit;
+
+    for transfer in transfers {
+        let sides = [
+            (
+                transfer.from.as_deref() == Some(address),
+                &transfer.to,
+                true,
+            ),
+            (
+                transfer.to.as_deref() == Some(address),
+                &transfer.from,
+                false,
+            ),
+        ];
+
+        for (involved, other, is_sent) in sides {
+            if !involved {
+                continue;
+            }
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            if remaining == 0 {
+                return (sent, received);
+            }
+            remaining -= 1;
+
+            let item = Nep17Transfer {
+                timestamp: transfer.time,
+                assethash: transfer.contract.clone(),
+                transferaddress: other.clone(),
+                amount: transfer.amount.to_string(),
+                blockindex: transfer.block_index,
+                transfernotifyindex: transfer.notification_index,
+                txhash: transfer.txid.clone(),
+            };
+            match is_sent {
+                true => sent.push(item),
+                false => received.push(item),
+            }
+        }
+    }
+
+    (sent, received)
+}
+
+fn get_nep17_transfers(conn: &dyn Storage, params: &[Value]) -> Result<Nep17Transfers, RpcError> {
+    let address = address_param(params, 0)?;
+    let end = optional_index_param(params, 2)?.unwrap_or_else(now);
+    let start = optional_index_param(params, 1)?.unwrap_or(end.saturating_sub(TRANSFER_WINDOW));
+    let limit = optional_index_param(params, 3)?.unwrap_or(MAX_PAGE_LIMIT);
+    let page = optional_index_param(params, 4)?.unwrap_or(0);
+
+    if start > end {
+        return Err(invalid_params("start is after end"));
+    }
+    if limit == 0 || limit > MAX_PAGE_LIMIT {
+        return Err(invalid_params(&format!(
+            "limit must be between 1 and {MAX_PAGE_LIMIT}"
+        )));
+    }
+
+    let transfers = transfers(conn, &address, Some((start, end)))?;
+    let (sent, received) = split_transfers(&address, transfers, limit, page);
+
+    Ok(Nep17Transfers {
+        sent,
+        received,
+        address,
+    })
+}
+
+// net amounts per token, with the block each last changed in
+#[derive(Default)]
+struct Ledger(BTreeMap<String, (BigInt, u64)>);
+
+impl Ledger {
+    fn add(&mut self, contract: &str, amount: BigInt, block_index: u64) {
+        let entry = self.0.entry(contract.to_string()).or_default();
+        entry.0 += amount;
+        entry.1 = entry.1.max(block_index);
+    }
+}
+
+// The balances follow from every transfer since genesis. GAS also goes out in fees and comes in
+// as the primary's reward, both of which are paid outside of transactions.
+pub fn get_nep17_balances(conn: &dyn Storage, params: &[Value]) -> Result<Nep17Balances, RpcError> {
+    let address = address_param(params, 0)?;
+    let mut ledger = Ledger::default();
+
+    for transfer in transfers(conn, &address, None)? {
+        if transfer.from.as_deref() == Some(address.as_str()) {
+            ledger.add(
+                &transfer.contract,
+                -transfer.amount.clone(),
+                transfer.block_index,
+            );
+        }
+        if transfer.to.as_deref() == Some(address.as_str()) {
+            ledger.add(&transfer.contract, transfer.amount, transfer.block_index);
+        }
+    }
+
+    let fees = conn
+        .query(
+            "SELECT sysfee, netfee, block_index FROM transactions WHERE sender = $1",
+            &[(&address).into()],
+        )
+        .map_err(sql_error)?;
+    for row in fees {
+        let (sysfee, netfee): (String, String) = (
+            row.get(0).map_err(sql_error)?,
+            row.get(1).map_err(sql_error)?,
+        );
+        let fee = sysfee.parse::<BigInt>().unwrap_or_default()
+            + netfee.parse::<BigInt>().unwrap_or_default();
+        ledger.add(GAS_TOKEN, -fee, row.get(2).map_err(sql_error)?);
+    }
+
+    let rewards = conn
+        .query(
+            "SELECT reward_amount, id FROM blocks WHERE reward_receiver = $1",
+            &[(&address).into()],
+        )
+        .map_err(sql_error)?;
+    for row in rewards {
+        let reward: BigInt = row.get(0).map_err(sql_error)?;
+        ledger.add(GAS_TOKEN, reward, row.get(1).map_err(sql_error)?);
+    }
+
+    let has_states = conn.table_exists("contract_states").map_err(sql_error)?;
+    let mut balance = Vec::new();
+    for (contract, (amount, lastupdatedblock)) in ledger.0 {
+        if amount == BigInt::default() {
+            continue;
+        }
+
+        let (name, symbol, decimals) = match contract.as_str() {
+            NEO_TOKEN => (Some("NeoToken"), Some("NEO"), Some("0")),
+            GAS_TOKEN => (Some("GasToken"), Some("GAS"), Some("8")),
+            _ => (None, None, None),
+        };
+        let name = match (name, has_states) {
+            (Some(name), _) => Some(name.to_string()),
+            (None, true) => manifest_name(conn, &contract)?,
+            (None, false) => None,
+        };
+
+        balance.push(Nep17Balance {
+            assethash: contract,
+            name,
+            symbol: symbol.map(str::to_string),
+            decimals: decimals.map(str::to_string),
+            amount: amount.to_string(),
+            lastupdatedblock,
+        });
+    }
+
+    Ok(Nep17Balances { balance, address })
+}
+
+fn latest_state_column(
+    conn: &dyn Storage,
+    hash: &str,
+    column: &str,
+) -> Result<Option<Value>, RpcError> {
+    conn.query_row(
+        &format!(
+            "SELECT {column} FROM contract_states WHERE hash = $1 AND {column} IS NOT NULL
+            ORDER BY block_index DESC, id DESC LIMIT 1"
+        ),
+        &[hash.into()],
+    )
+    .map_err(sql_error)?
+    .map(|row| row.get(0))
+    .transpose()
+    .map_err(sql_error)
+}
+
+fn manifest_name(conn: &dyn Storage, hash: &str) -> Result<Option<String>, RpcError> {
+    Ok(latest_state_column(conn, hash, "manifest")?
+        .and_then(|manifest| manifest["name"].as_str().map(str::to_string)))
+}
+
+// by hash or id. Native contracts are never deployed, so they aren't known here.
+fn get_contract_state(conn: &dyn Storage, params: &[Value]) -> Result<ContractState, RpcError> {
+    require_table(conn, "contract_states", "Contract states")?;
+    let contract =
+        param(params, 0).ok_or_else(|| invalid_params("contract hash or id expected"))?;
+    let unknown = || RpcError::new(UNKNOWN_CONTRACT, "");
+
+    let hash = match index_param(contract) {
+        Some(id) => conn
+            .query_row(
+                "SELECT hash FROM contract_states WHERE contract_id = $1 LIMIT 1",
+                &[id.into()],
+            )
+            .map_err(sql_error)?
+            .map(|row| row.get::<String>(0))
+            .transpose()
+            .map_err(sql_error)?
+            .ok_or_else(unknown)?,
+        None => hash_param(params, 0, 40)
+            .ok_or_else(|| invalid_params("contract hash or id expected"))?,
+    };
+
+    let latest = conn
+        .query_row(
+            "SELECT action, contract_id, update_counter FROM contract_states WHERE hash = $1
+            ORDER BY block_index DESC, id DESC LIMIT 1",
+            &[(&hash).into()],
+        )
+        .map_err(sql_error)?
+        .ok_or_else(unknown)?;
+
+    let action: String = latest.get(0).map_err(sql_error)?;
+    if action == "Destroy" {
+        return Err(unknown());
+    }
+
+    // an update can leave either one as it was
+    let (Some(nef), Some(manifest)) = (
+        latest_state_column(conn, &hash, "nef")?,
+        latest_state_column(conn, &hash, "manifest")?,
+    ) else {
+        return Err(RpcError::new(
+            INTERNAL_ERROR,
+            "the contract's NEF or manifest could not be read from its transactions",
+        ));
+    };
+
+    Ok(ContractState {
+        id: latest.get(1).map_err(sql_error)?,
+        updatecounter: latest.get(2).map_err(sql_error)?,
+        hash,
+        nef,
+        manifest,
+    })
+}
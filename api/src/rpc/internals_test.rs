@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use lib::integer::BigInt;
+    use lib::network::{GAS_TOKEN, NEO_TOKEN};
+    use lib::stack_item::StackItem;
+    use serde_json::{json, Value};
+
+    use crate::rpc::internals::{
+        address_param, hash_param, optional_index_param, split_transfers, transfer_state,
+        verbose_param, TransferRow,
+    };
+    use crate::rpc::models::INVALID_PARAMS;
+
+    fn transfer_row(from: Option<&str>, to: Option<&str>, block_index: u64) -> TransferRow {
+        TransferRow {
+            contract: GAS_TOKEN.to_string(),
+            from: from.map(str::to_string),
+            to: to.map(str::to_string),
+            amount: BigInt::from(100),
+            block_index,
+            notification_index: 0,
+            txid: format!("0x{block_index:064x}"),
+            time: 1_000 * block_index,
+        }
+    }
+
+    #[test]
+    fn test_params() {
+        let params = vec![
+            json!("0xEF4073A0F2B305A38EC4050E4D3D28BC40EA63F5"),
+            json!(1),
+            json!("12"),
+            Value::Null,
+        ];
+
+        assert_eq!(hash_param(&params, 0, 40).as_deref(), Some(NEO_TOKEN));
+        assert_eq!(hash_param(&params, 0, 64), None);
+        assert_eq!(verbose_param(&params, 1), Ok(true));
+        assert_eq!(verbose_param(&params, 3), Ok(false));
+        assert_eq!(verbose_param(&params, 4), Ok(false));
+        assert_eq!(optional_index_param(&params, 2), Ok(Some(12)));
+        assert!(optional_index_param(&params, 0).is_err());
+
+        assert_eq!(
+            address_param(&params, 0).unwrap(),
+            "NiHURyS83nX2mpxtA7xq84cGxVbHojj5Wc"
+        );
+        let address = vec![json!("NVg7LjGcUSrgxgjX3zEgqaksfMaiS8Z6e1")];
+        assert_eq!(
+            address_param(&address, 0).unwrap(),
+            "NVg7LjGcUSrgxgjX3zEgqaksfMaiS8Z6e1"
+        );
+        assert_eq!(
+            address_param(&[json!("neo.neo")], 0).unwrap_err().code,
+            INVALID_PARAMS
+        );
+    }
+
+    #[test]
+    fn test_transfer_state() {
+        let state = StackItem::from_json(&json!({ "type": "Array", "value": [
+            { "type": "Any" },
+            { "type": "ByteString", "value": "axI92L7HGGSIUrvHhZXjU2oFj58=" },
+            { "type": "Integer", "value": "40300000" }
+        ] }))
+        .unwrap();
+        assert_eq!(
+            transfer_state(&state),
+            Some((
+                None,
+                Some(String::from("NVg7LjGcUSrgxgjX3zEgqaksfMaiS8Z6e1")),
+                BigInt::from(40_300_000)
+            ))
+        );
+
+        // NEP-11 transfers carry the token id as well
+        let state = StackItem::from_json(&json!({ "type": "Array", "value": [
+            { "type": "Any" },
+            { "type": "ByteString", "value": "axI92L7HGGSIUrvHhZXjU2oFj58=" },
+            { "type": "Integer", "value": "1" },
+            { "type": "ByteString", "value": "AQ==" }
+        ] }))
+        .unwrap();
+        assert_eq!(transfer_state(&state), None);
+    }
+
+    #[test]
+    fn test_split_transfers() {
+        let (me, other) = ("Nme", "Nother");
+        let transfers = || {
+            vec![
+                transfer_row(Some(me), Some(other), 4),
+                transfer_row(Some(me), Some(me), 3),
+                transfer_row(None, Some(me), 2),
+                transfer_row(Some(other), Some(me), 1),
+            ]
+        };
+
+        let (sent, received) = split_transfers(me, transfers(), 10, 0);
+        assert_eq!(sent.len(), 2);
+        assert_eq!(received.len(), 3);
+        assert_eq!(sent[0].transferaddress.as_deref(), Some(other));
+        assert_eq!(received[1].transferaddress, None);
+        assert_eq!(received[1].amount, "100");
+
+        // the self transfer counts twice towards the limit
+        let (sent, received) = split_transfers(me, transfers(), 2, 0);
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[1].blockindex, 3);
+        assert!(received.is_empty());
+
+        let (sent, received) = split_transfers(me, transfers(), 2, 1);
+        assert!(sent.is_empty());
+        let blocks: Vec<u64> = received
+            .iter()
+            .map(|transfer| transfer.blockindex)
+            .collect();
+        assert_eq!(blocks, vec![3, 2]);
+
+        let (sent, received) = split_transfers(me, transfers(), 2, 2);
+        assert!(sent.is_empty());
+        assert_eq!(received.len(), 1);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod controller;
+mod internals;
+mod internals_test;
+pub mod models;
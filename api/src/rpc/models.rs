@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+// JSON-RPC 2.0 codes, then the ones Neo nodes use for missing data
+pub const PARSE_ERROR: i64 = -32700;
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+pub const INTERNAL_ERROR: i64 = -32603;
+pub const UNKNOWN_BLOCK: i64 = -101;
+pub const UNKNOWN_CONTRACT: i64 = -102;
+pub const UNKNOWN_TRANSACTION: i64 = -103;
+
+#[derive(Deserialize)]
+pub struct RpcRequest {
+    pub jsonrpc: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: Option<Value>,
+    #[serde(default)]
+    pub id: Value,
+}
+
+#[derive(Serialize)]
+pub struct RpcResponse {
+    pub jsonrpc: &'static str,
+    pub id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    pub fn new(id: Value, result: Result<Value, RpcError>) -> Self {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+
+        RpcResponse {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+}
+
+impl RpcError {
+    pub fn new(code: i64, data: &str) -> Self {
+        let message = match code {
+            PARSE_ERROR => "Parse error",
+            INVALID_REQUEST => "Invalid Request",
+            METHOD_NOT_FOUND => "Method not found",
+            INVALID_PARAMS => "Invalid params",
+            UNKNOWN_BLOCK => "Unknown block",
+            UNKNOWN_CONTRACT => "Unknown contract",
+            UNKNOWN_TRANSACTION => "Unknown transaction",
+            _ => "Internal error",
+        };
+
+        RpcError {
+            code,
+            message: message.to_string(),
+            data: (!data.is_empty()).then(|| data.to_string()),
+        }
+    }
+}
+
+// a transaction as NeoGo returns it, with the block fields only in verbose getrawtransaction
+#[derive(Serialize)]
+pub struct RpcTransaction {
+    pub hash: String,
+    pub size: u32,
+    pub version: u8,
+    pub nonce: u64,
+    pub sender: String,
+    pub sysfee: String,
+    pub netfee: String,
+    pub validuntilblock: u64,
+    pub signers: Value,
+    pub attributes: Value,
+    pub script: String, // base64
+    pub witnesses: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blockhash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocktime: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vmstate: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct RpcBlock {
+    pub hash: String,
+    pub size: u32,
+    pub version: u8,
+    pub previousblockhash: String,
+    pub merkleroot: String,
+    pub time: u64,
+    pub nonce: String,
+    pub index: u64,
+    pub primary: u8,
+    pub nextconsensus: String,
+    pub witnesses: Value,
+    pub tx: Vec<RpcTransaction>,
+    pub confirmations: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nextblockhash: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct Execution {
+    pub trigger: String,
+    pub vmstate: String,
+    pub exception: Option<String>,
+    pub gasconsumed: String,
+    pub stack: Value,
+    pub notifications: Value,
+}
+
+#[derive(Serialize)]
+pub struct ApplicationLog {
+    pub txid: String,
+    pub executions: Vec<Execution>,
+}
+
+// `transferaddress` is left out for mints and burns
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Nep17Transfer {
+    pub timestamp: u64,
+    pub assethash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transferaddress: Option<String>,
+    pub amount: String,
+    pub blockindex: u64,
+    pub transfernotifyindex: u64,
+    pub txhash: String,
+}
+
+#[derive(Serialize)]
+pub struct Nep17Transfers {
+    pub sent: Vec<Nep17Transfer>,
+    pub received: Vec<Nep17Transfer>,
+    pub address: String,
+}
+
+// token metadata is only known for native tokens, or when a node can be asked
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct Nep17Balance {
+    pub assethash: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decimals: Option<String>,
+    pub amount: String,
+    pub lastupdatedblock: u64,
+}
+
+#[derive(Serialize)]
+pub struct Nep17Balances {
+    pub balance: Vec<Nep17Balance>,
+    pub address: String,
+}
+
+#[derive(Serialize)]
+pub struct ContractState {
+    pub id: i64,
+    pub updatecounter: u64,
+    pub hash: String,
+    pub nef: Value,
+    pub manifest: Value,
+}
@@ -6,7 +6,6 @@ pub fn is_neo_address(string: &str) -> bool {
         && string.chars().all(|c| ALPHABET.contains(&(c as u8)))
 }
 
-#[allow(dead_code)]
 pub fn is_neo_script_hash(string: &str) -> bool {
     string.chars().count() == 42
         && string.starts_with("0x")
@@ -27,6 +26,25 @@ pub fn is_neo_txid_hash(string: &str) -> bool {
             .all(|c| c.is_ascii_hexdigit())
 }
 
+// e.g. neo.neo or docs.neo.com, lowercased when looked up
+pub fn is_nns_name(string: &str) -> bool {
+    let labels: Vec<&str> = string.split('.').collect();
+
+    string.len() <= 255
+        && labels.len() >= 2
+        && labels.iter().all(|label| {
+            (1..=63).contains(&label.len())
+                && label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-')
+                && !label.starts_with('-')
+                && !label.ends_with('-')
+        })
+}
+
+// an address, or an NNS name to resolve into one
+pub fn is_address_or_name(string: &str) -> bool {
+    is_neo_address(string) || is_nns_name(string)
+}
+
 #[test]
 fn test_is_neo_address() {
     assert!(is_neo_address("NSTSntFPK36QXsjEK6oAhnPzSyfgfVA2GQ"));
@@ -68,3 +86,13 @@ fn test_is_neo_txid_hash() {
         "0x6250481ec87ae2052f90ec7cb46d757b8db1c447"
     ));
 }
+
+#[test]
+fn test_is_nns_name() {
+    assert!(is_nns_name("neo.neo"));
+    assert!(is_nns_name("docs.Neo.com"));
+    assert!(!is_nns_name("neo"));
+    assert!(!is_nns_name("neo..neo"));
+    assert!(!is_nns_name("-neo.neo"));
+    assert!(!is_nns_name("NSTSntFPK36QXsjEK6oAhnPzSyfgfVA2GQ"));
+}
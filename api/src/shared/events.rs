@@ -1,72 +1,84 @@
-use crate::shared::models::{Transaction, Transfer, TxData};
+use lib::integer::{self, BigInt, GAS_DECIMALS, NEO_DECIMALS};
+use lib::network::{GAS_TOKEN, NEO_TOKEN};
 
-use lib::neo;
+use crate::shared::models::{Event, Transaction, Transfer, TxData};
 
-use super::models::{FUSDT_PRECISION, GAS_PRECISION};
+const FUSDT_HASH: &str = "0xcd48b160c1bbc9d74997b803b9a7ad50a4bef020";
+
+// Decimals are a contract method and aren't indexed, so only these tokens' are known
+pub fn token_decimals(contract: &str) -> Option<u32> {
+    match contract {
+        NEO_TOKEN => Some(NEO_DECIMALS),
+        GAS_TOKEN => Some(GAS_DECIMALS),
+        FUSDT_HASH => Some(6),
+        _ => None,
+    }
+}
+
+// in whole tokens when the decimals are known, the integer amount otherwise
+pub fn format_amount(amount: &BigInt, decimals: Option<u32>) -> String {
+    match decimals {
+        Some(decimals) => integer::format_fixed(amount, decimals),
+        None => amount.to_string(),
+    }
+}
+
+// fees are stored as integer strings in GAS fractions
+pub fn format_gas(fractions: &str) -> String {
+    let value: BigInt = fractions.parse().unwrap_or_default();
+    integer::format_fixed(&value, GAS_DECIMALS)
+}
 
 // now supports inbound and outbound (dictated by sender field and from/to, depending on requirements)
-// still needs work to support all contract decimals properly
 // also it may return tons of pointless transfer data for airdrops that include the address
 // not sure what to do about that right now, as we might not want to fully discount transfers
 // that do not have the specified address as from/to/sender (e.g. internal transfers on DEX swaps)
 pub fn get_transfer_events(tx: Transaction) -> TxData {
     let mut transfers = Vec::new();
-    let notifications = tx.notifications.as_array().unwrap();
+    // notifications that don't parse can't be transfers
+    let notifications: Vec<Event> =
+        serde_json::from_value(tx.notifications.clone()).unwrap_or_default();
 
     for notification in notifications {
-        let state = notification["state"].clone();
+        let Some([from, to, amount]) = notification.state.as_array() else {
+            continue;
+        };
 
-        if notification["eventname"] == "Transfer"
-            && state["type"] == "Array"
-            && state["value"][0]["type"] == "ByteString"
-            || state["value"][0]["type"] == "Any" && state["value"][1]["type"] == "ByteString"
-            || state["value"][1]["type"] == "Any" && state["value"][2]["type"] == "Integer"
+        // mints come from null and burns go to it
+        if notification.eventname != "Transfer"
+            || !(from.is_null() || from.as_address().is_some())
+            || !(to.is_null() || to.as_address().is_some())
+            || from.is_null() && to.is_null()
         {
-            let contract = notification["contract"].as_str().unwrap().to_string();
-
-            let from = if state["value"][0]["value"].is_string() {
-                neo::base64_to_address(state["value"][0]["value"].as_str().unwrap())
-            } else {
-                "null".to_string()
-            };
-
-            let to = if state["value"][1]["value"].is_string() {
-                neo::base64_to_address(state["value"][1]["value"].as_str().unwrap())
-            } else {
-                "null".to_string()
-            };
+            continue;
+        }
 
-            let qty = state["value"][2]["value"].as_str().unwrap();
+        let contract = notification.contract;
+        let from = from.as_address().unwrap_or_else(|| "null".to_string());
+        let to = to.as_address().unwrap_or_else(|| "null".to_string());
 
-            let amount = match qty.parse::<f64>() {
-                Ok(v) => {
-                    if contract == "0xef4073a0f2b305a38ec4050e4d3d28bc40ea63f5" {
-                        v
-                    } else if contract == "0xcd48b160c1bbc9d74997b803b9a7ad50a4bef020" {
-                        v / FUSDT_PRECISION
-                    } else {
-                        v / GAS_PRECISION
-                    }
-                }
-                Err(_) => continue,
-            };
+        let Some(amount) = amount.as_integer() else {
+            continue;
+        };
+        let decimals = token_decimals(&contract);
+        let amount = format_amount(&amount, decimals);
 
-            let transfer = Transfer {
-                contract,
-                from,
-                to,
-                amount, // this will break on non-8 decimal contracts, will need contract table
-            };
+        let transfer = Transfer {
+            contract,
+            from,
+            to,
+            amount,
+            decimals,
+        };
 
-            transfers.push(transfer);
-        }
+        transfers.push(transfer);
     }
 
     TxData {
         txid: tx.hash,
-        time: 0,
-        sysfee: tx.sysfee.parse::<f64>().unwrap() / GAS_PRECISION,
-        netfee: tx.netfee.parse::<f64>().unwrap() / GAS_PRECISION,
+        time: tx.block_time,
+        sysfee: format_gas(&tx.sysfee),
+        netfee: format_gas(&tx.netfee),
         nep17_transfers: transfers,
         nep11_transfers: Vec::new(),
     }
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use crate::shared::events::get_transfer_events;
+    use crate::shared::models::Transaction;
+
+    #[test]
+    fn test_get_transfer_events() {
+        let tx = Transaction {
+            index: 1,
+            hash: String::from("0x01"),
+            block_index: 1,
+            vm_state: String::from("HALT"),
+            size: 250,
+            version: 0,
+            nonce: 0,
+            sender: String::from("NVg7LjGcUSrgxgjX3zEgqaksfMaiS8Z6e1"),
+            sysfee: String::from("100000000"),
+            netfee: String::from("0"),
+            valid_until: 5760,
+            signers: serde_json::json!([]),
+            script: String::new(),
+            witnesses: serde_json::json!([]),
+            stack_result: serde_json::json!([]),
+            notifications: serde_json::json!([
+                {
+                    "contract": "0xd2a4cff31913016155e38e474a2c06d08be276cf",
+                    "eventname": "Transfer",
+                    "state": { "type": "Array", "value": [
+                        { "type": "Any" },
+                        { "type": "ByteString", "value": "axI92L7HGGSIUrvHhZXjU2oFj58=" },
+                        { "type": "Integer", "value": "40300000" }
+                    ] }
+                },
+                {
+                    "contract": "0x0000000000000000000000000000000000000001",
+                    "eventname": "Transfer",
+                    "state": { "type": "Array", "value": [
+                        { "type": "ByteString", "value": "axI92L7HGGSIUrvHhZXjU2oFj58=" },
+                        { "type": "Any" },
+                        { "type": "Integer", "value": "1000000000000000000" }
+                    ] }
+                },
+                {
+                    "contract": "0xd2a4cff31913016155e38e474a2c06d08be276cf",
+                    "eventname": "Approval",
+                    "state": { "type": "Array", "value": [
+                        { "type": "Any" },
+                        { "type": "ByteString", "value": "axI92L7HGGSIUrvHhZXjU2oFj58=" },
+                        { "type": "Integer", "value": "1" }
+                    ] }
+                }
+            ]),
+            block_time: 0,
+            exception: None,
+            gas_consumed: Some(String::from("9977780")),
+            attributes: Some(serde_json::json!([])),
+        };
+
+        let data = get_transfer_events(tx);
+
+        assert_eq!(data.nep17_transfers.len(), 2);
+        let transfer = &data.nep17_transfers[0];
+        assert_eq!(transfer.from, "null");
+        assert_eq!(transfer.to, "NVg7LjGcUSrgxgjX3zEgqaksfMaiS8Z6e1");
+        assert_eq!(transfer.amount, "0.403");
+        assert_eq!(transfer.decimals, Some(8));
+
+        // a token whose decimals aren't known keeps its integer amount
+        let transfer = &data.nep17_transfers[1];
+        assert_eq!(transfer.to, "null");
+        assert_eq!(transfer.amount, "1000000000000000000");
+        assert_eq!(transfer.decimals, None);
+        assert_eq!(data.sysfee, "1");
+        assert_eq!(data.netfee, "0");
+    }
+}
@@ -1,3 +1,7 @@
 pub mod checker;
 pub mod events;
+mod events_test;
 pub mod models;
+pub mod nns;
+pub mod node;
+pub mod time;
@@ -1,8 +1,10 @@
+use lib::db::{Row, StorageError};
+use lib::stack_item::StackItem;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-pub const GAS_PRECISION: f64 = 100000000.0;
-pub const FUSDT_PRECISION: f64 = 1000000.0;
+pub const DEFAULT_PAGE_LIMIT: u64 = 100;
+pub const MAX_PAGE_LIMIT: u64 = 1000;
 
 pub type Hash160 = String;
 pub type Address = String;
@@ -25,6 +27,38 @@ pub struct Transaction {
     pub witnesses: Value,
     pub stack_result: Value,
     pub notifications: Value,
+    pub block_time: u64,
+    pub exception: Option<String>,
+    pub gas_consumed: Option<String>, // unknown for transactions indexed before it was stored
+    pub attributes: Option<Value>,    // same, until the range is reindexed
+}
+
+impl Transaction {
+    // columns in table order, i.e. `SELECT * FROM transactions`
+    pub fn from_row(row: &Row) -> Result<Self, StorageError> {
+        Ok(Transaction {
+            index: row.get(0)?,
+            hash: row.get(1)?,
+            block_index: row.get(2)?,
+            vm_state: row.get(3)?,
+            size: row.get(4)?,
+            version: row.get(5)?,
+            nonce: row.get(6)?,
+            sender: row.get(7)?,
+            sysfee: row.get(8)?,
+            netfee: row.get(9)?,
+            valid_until: row.get(10)?,
+            signers: row.get(11)?,
+            script: row.get(12)?,
+            witnesses: row.get(13)?,
+            stack_result: row.get(14)?,
+            notifications: row.get(15)?,
+            block_time: row.get(16)?,
+            exception: row.get(17)?,
+            gas_consumed: row.get(18)?,
+            attributes: row.get(19)?,
+        })
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -37,15 +71,16 @@ pub struct Transfer {
     pub contract: Hash160,
     pub from: Address,
     pub to: Address,
-    pub amount: f64,
+    pub amount: String, // fixed-point decimal, e.g. "0.403", or the integer amount without decimals
+    pub decimals: Option<u32>, // null for tokens whose decimals aren't known
 }
 
 #[derive(Serialize, Deserialize, Clone)]
 pub struct TxData {
     pub txid: String,
-    pub time: u64, // unix timestamp, extra call to set it until I modify the db to store block time for transactions
-    pub sysfee: f64,
-    pub netfee: f64,
+    pub time: u64,      // unix timestamp in milliseconds
+    pub sysfee: String, // in GAS, fixed-point decimal
+    pub netfee: String,
     pub nep17_transfers: Vec<Transfer>,
     pub nep11_transfers: Vec<Transfer>,
 }
@@ -61,5 +96,16 @@ pub struct TxDataList {
 pub struct Event {
     pub contract: Hash160,
     pub eventname: String,
-    pub state: serde_json::Value,
+    pub state: StackItem,
+}
+
+// the page size asked for, within bounds
+#[derive(Deserialize)]
+pub struct PageQuery {
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+pub fn page_limit(limit: Option<u64>) -> u64 {
+    limit.unwrap_or(DEFAULT_PAGE_LIMIT).clamp(1, MAX_PAGE_LIMIT)
 }
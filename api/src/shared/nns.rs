@@ -0,0 +1,58 @@
+use lib::db::{Storage, StorageError};
+use lib::neo::nns_domain;
+
+use crate::error::Error;
+
+use super::checker;
+use super::time::now;
+
+pub const TXT_RECORD: u64 = 16;
+
+// The address a name points to: its TXT record when that holds an address, otherwise the owner
+// of the domain. Names of expired domains point nowhere.
+pub fn resolve_name(conn: &dyn Storage, name: &str) -> Result<Option<String>, StorageError> {
+    let name = name.to_lowercase();
+    let domain = nns_domain(&name);
+
+    let Some(owner) = conn
+        .query_row(
+            "SELECT owner FROM nns_domains WHERE name = $1 AND expiration > $2",
+            &[domain.as_str().into(), now().into()],
+        )?
+        .map(|row| row.get::<String>(0))
+        .transpose()?
+    else {
+        return Ok(None);
+    };
+
+    let txt = conn
+        .query_row(
+            "SELECT data FROM nns_records WHERE name = $1 AND record_type = $2",
+            &[name.as_str().into(), TXT_RECORD.into()],
+        )?
+        .map(|row| row.get::<String>(0))
+        .transpose()?;
+
+    Ok(match txt {
+        Some(address) if checker::is_neo_address(&address) => Some(address),
+        _ if name == domain => Some(owner),
+        _ => None,
+    })
+}
+
+// an address as it is, or the address an NNS name points to
+pub fn resolve_address(conn: &dyn Storage, address: String) -> Result<String, Error> {
+    if checker::is_neo_address(&address) {
+        return Ok(address);
+    }
+
+    if !conn.table_exists("nns_domains")? {
+        return Err(Error {
+            error: "NNS names are not indexed yet. Run the latest Indexer.".to_string(),
+        });
+    }
+
+    resolve_name(conn, &address)?.ok_or_else(|| Error {
+        error: "Name does not resolve to an address.".to_string(),
+    })
+}
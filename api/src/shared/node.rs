@@ -0,0 +1,50 @@
+use once_cell::sync::Lazy;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::error::Error;
+
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+
+#[derive(Deserialize)]
+struct RpcError {
+    message: String,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    result: Option<Value>,
+    error: Option<RpcError>,
+}
+
+// Sends a JSON-RPC request to the node and returns its result, node errors included as is
+pub async fn call(rpc_url: Option<&str>, method: &str, params: Value) -> Result<Value, Error> {
+    let rpc_url = rpc_url.ok_or_else(|| Error {
+        error: "No node is configured for this network.".to_string(),
+    })?;
+
+    let response: RpcResponse = CLIENT
+        .post(rpc_url)
+        .json(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|_| Error {
+            error: "Failed to reach the node.".to_string(),
+        })?
+        .json()
+        .await
+        .map_err(|_| Error {
+            error: "Invalid response from the node.".to_string(),
+        })?;
+
+    match (response.result, response.error) {
+        (_, Some(error)) => Err(Error {
+            error: format!("Node error: {}.", error.message.trim_end_matches('.')),
+        }),
+        (Some(result), None) => Ok(result),
+        (None, None) => Err(Error {
+            error: "Invalid response from the node.".to_string(),
+        }),
+    }
+}
@@ -1,19 +1,19 @@
 use actix_web::{get, web, HttpResponse, Responder};
 
-use super::internals::CURRENT_NETWORK_STATISTICS;
-use super::internals::CURRENT_STATS;
+use crate::ConnectionPool;
+
 use super::models::NetworkStatistics;
 use super::models::ShrikeStats;
 
 // Now this path is always fast and always up to date
 #[get("/v1/stat/stats")]
-async fn get_stats() -> impl Responder {
-    let lock = CURRENT_STATS.read().unwrap();
+async fn get_stats(pool: web::Data<ConnectionPool>) -> impl Responder {
+    let lock = pool.stats.read().unwrap();
 
     HttpResponse::Ok().json(ShrikeStats {
         total_blocks: lock.total_blocks,
         total_transactions: lock.total_transactions,
-        total_sysfee: lock.total_sysfee,
+        total_sysfee: lock.total_sysfee.clone(),
         total_transfers: lock.total_transfers,
         total_senders: lock.total_senders,
         total_contracts: lock.total_contracts,
@@ -21,9 +21,9 @@ async fn get_stats() -> impl Responder {
 }
 
 #[get("/v1/stat/network-statistics")]
-async fn get_network_statistics() -> impl Responder {
+async fn get_network_statistics(pool: web::Data<ConnectionPool>) -> impl Responder {
     let lock: std::sync::RwLockReadGuard<'_, NetworkStatistics> =
-        CURRENT_NETWORK_STATISTICS.read().unwrap();
+        pool.network_statistics.read().unwrap();
 
     HttpResponse::Ok().json(NetworkStatistics {
         total_transactions: lock.total_transactions,
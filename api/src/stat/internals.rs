@@ -1,90 +1,49 @@
 use actix_web::web;
-use once_cell::sync::Lazy;
-use r2d2::PooledConnection;
-use r2d2_sqlite::SqliteConnectionManager;
-use tokio::task;
+use lib::db::{FromValue, Storage, Value};
+use lib::integer::{self, BigInt, GAS_DECIMALS};
+use lib::network::NATIVE_CONTRACTS;
 
-use std::sync::RwLock;
-
-use crate::shared::models::GAS_PRECISION;
+use crate::shared::time::now;
 use crate::ConnectionPool;
 
-use super::models::{NetworkStatistics, ShrikeStats};
-
-pub static CURRENT_NETWORK_STATISTICS: Lazy<RwLock<NetworkStatistics>> = Lazy::new(|| {
-    let s = NetworkStatistics {
-        total_transactions: 0,
-        total_addresses: 0,
-        total_contracts: 0,
-        current_week_transactions: 0,
-        current_week_addresses: 0,
-        current_week_contracts: 0,
-    };
-    RwLock::new(s)
-});
-
-pub static CURRENT_STATS: Lazy<RwLock<ShrikeStats>> = Lazy::new(|| {
-    let s = ShrikeStats {
-        total_blocks: 0,
-        total_transactions: 0,
-        total_sysfee: 0.0,
-        total_transfers: 0,
-        total_senders: 0,
-        total_contracts: 0,
-    };
-    RwLock::new(s)
-});
-
-pub fn get_stat_internal<T: rusqlite::types::FromSql>(
-    conn: &PooledConnection<SqliteConnectionManager>,
-    sql: &str,
-) -> T {
-    let mut stmt = conn.prepare(sql).unwrap();
-    let total: Result<T, rusqlite::Error> = stmt.query_row([], |row| row.get(0));
-
-    total.unwrap()
+const WEEK_MS: u64 = 7 * 24 * 60 * 60 * 1000;
+
+pub fn get_stat_internal<T: FromValue>(conn: &dyn Storage, sql: &str, params: &[Value]) -> T {
+    let row = conn.query_row(sql, params).unwrap().unwrap();
+
+    row.get(0).unwrap()
+}
+
+// block times are in milliseconds
+fn week_ago() -> Value {
+    Value::from(now().saturating_sub(WEEK_MS))
 }
 
 pub async fn set_stats_internal(pool: web::Data<ConnectionPool>) {
-    let conn1 = pool.connection.clone().get().unwrap();
+    let db = &pool.connection;
 
-    let blocks = task::spawn_blocking(move || get_blocks_internal(&conn1))
-        .await
-        .unwrap();
+    let blocks = db.run(get_blocks_internal).await;
 
-    let current_block = CURRENT_STATS.read().unwrap().total_blocks;
+    let current_block = pool.stats.read().unwrap().total_blocks;
 
     if blocks > current_block {
-        let conn2 = pool.connection.clone().get().unwrap();
-        let conn3 = pool.connection.clone().get().unwrap();
-        let conn4 = pool.connection.clone().get().unwrap();
-        let conn5 = pool.connection.clone().get().unwrap();
-        let conn6 = pool.connection.clone().get().unwrap();
-        let conn7 = pool.connection.clone().get().unwrap();
-        let conn8 = pool.connection.clone().get().unwrap();
-        let conn9 = pool.connection.clone().get().unwrap();
-        let conn10 = pool.connection.clone().get().unwrap();
-
-        let transactions = task::spawn_blocking(move || get_transactions_internal(&conn2));
+        let transactions = db.run(get_transactions_internal);
 
-        let sysfees = task::spawn_blocking(move || get_sysfee_internal(&conn3));
+        let sysfees = db.run(get_sysfee_internal);
 
-        let transfers = task::spawn_blocking(move || get_transfers_internal(&conn4));
+        let transfers = db.run(get_transfers_internal);
 
-        let senders = task::spawn_blocking(move || get_senders_internal(&conn5));
+        let senders = db.run(get_senders_internal);
 
-        let contracts = task::spawn_blocking(move || get_contracts_internal(&conn6));
+        let contracts = db.run(get_contracts_internal);
 
-        let addresses = task::spawn_blocking(move || get_addresses_internal(&conn7));
+        let addresses = db.run(get_addresses_internal);
 
-        let current_week_contracts =
-            task::spawn_blocking(move || get_contracts_current_week_internal(&conn8));
+        let current_week_contracts = db.run(get_contracts_current_week_internal);
 
-        let current_week_transactions =
-            task::spawn_blocking(move || get_transactions_current_week_internal(&conn9));
+        let current_week_transactions = db.run(get_transactions_current_week_internal);
 
-        let current_week_addresses =
-            task::spawn_blocking(move || get_addresses_current_week_internal(&conn10));
+        let current_week_addresses = db.run(get_addresses_current_week_internal);
 
         let results = tokio::join!(
             transactions,
@@ -98,109 +57,107 @@ pub async fn set_stats_internal(pool: web::Data<ConnectionPool>) {
             current_week_addresses,
         );
 
-        let total_transactions = results.0.unwrap();
-        let total_contracts = results.4.unwrap();
+        let total_transactions = results.0;
+        let total_contracts = results.4;
 
         {
-            let mut w = CURRENT_STATS.write().unwrap();
+            let mut w = pool.stats.write().unwrap();
 
             w.total_blocks = blocks;
             w.total_transactions = total_transactions;
-            w.total_sysfee = results.1.unwrap();
-            w.total_transfers = results.2.unwrap();
-            w.total_senders = results.3.unwrap();
+            w.total_sysfee = results.1;
+            w.total_transfers = results.2;
+            w.total_senders = results.3;
             w.total_contracts = total_contracts;
         }
 
         {
-            let mut w = CURRENT_NETWORK_STATISTICS.write().unwrap();
+            let mut w = pool.network_statistics.write().unwrap();
 
             w.total_transactions = total_transactions;
-            w.total_addresses = results.5.unwrap();
+            w.total_addresses = results.5;
             w.total_contracts = total_contracts;
-            w.current_week_contracts = results.6.unwrap();
-            w.current_week_transactions = results.7.unwrap();
-            w.current_week_addresses = results.8.unwrap();
+            w.current_week_contracts = results.6;
+            w.current_week_transactions = results.7;
+            w.current_week_addresses = results.8;
         }
     } else {
         // println!("No cache updated needed.")
     }
-    println!("Stats refreshed. Current height is {}.", blocks);
+    println!(
+        "Stats refreshed. Current {} height is {}.",
+        pool.network, blocks
+    );
 }
 
-pub fn get_blocks_internal(conn: &PooledConnection<SqliteConnectionManager>) -> u64 {
-    let sql = "SELECT id FROM blocks WHERE id=(SELECT max(id) FROM blocks)";
-    get_stat_internal::<u64>(conn, sql)
+pub fn get_blocks_internal(conn: &dyn Storage) -> u64 {
+    let sql = "SELECT COALESCE(MAX(id), 0) FROM blocks";
+    get_stat_internal::<u64>(conn, sql, &[])
 }
 
-pub fn get_transactions_internal(conn: &PooledConnection<SqliteConnectionManager>) -> u64 {
-    let sql = "SELECT id FROM transactions WHERE id=(SELECT max(id) FROM transactions)";
-    get_stat_internal::<u64>(conn, sql)
+pub fn get_transactions_internal(conn: &dyn Storage) -> u64 {
+    let sql = "SELECT COALESCE(MAX(id), 0) FROM transactions";
+    get_stat_internal::<u64>(conn, sql, &[])
 }
 
-pub fn get_sysfee_internal(conn: &PooledConnection<SqliteConnectionManager>) -> f64 {
-    let sql = "SELECT sum(sysfee) FROM transactions";
-    get_stat_internal::<f64>(conn, sql) / GAS_PRECISION
+pub fn get_sysfee_internal(conn: &dyn Storage) -> String {
+    let sql = "SELECT COALESCE(SUM(CAST(sysfee AS BIGINT)), 0) FROM transactions";
+    let total = get_stat_internal::<BigInt>(conn, sql, &[]);
+
+    integer::format_fixed(&total, GAS_DECIMALS)
 }
 
-pub fn get_transfers_internal(conn: &PooledConnection<SqliteConnectionManager>) -> u64 {
-    let sql = "SELECT SUM(LENGTH(notifications) - LENGTH(REPLACE(notifications, 'Transfer', ''))) / 8 FROM transactions WHERE notifications LIKE '%Transfer%'";
-    get_stat_internal::<u64>(conn, sql)
+pub fn get_transfers_internal(conn: &dyn Storage) -> u64 {
+    let sql = "SELECT COALESCE(SUM(LENGTH(notifications) - LENGTH(REPLACE(notifications, 'Transfer', ''))) / 8, 0) FROM transactions WHERE notifications LIKE '%Transfer%'";
+    get_stat_internal::<u64>(conn, sql, &[])
 }
 
-pub fn get_senders_internal(conn: &PooledConnection<SqliteConnectionManager>) -> u64 {
+pub fn get_senders_internal(conn: &dyn Storage) -> u64 {
     let sql = "SELECT COUNT(DISTINCT sender) FROM transactions";
-    get_stat_internal::<u64>(conn, sql)
+    get_stat_internal::<u64>(conn, sql, &[])
 }
 
-pub fn get_contracts_internal(conn: &PooledConnection<SqliteConnectionManager>) -> u64 {
-    let sql = "SELECT COUNT() FROM contracts";
+pub fn get_contracts_internal(conn: &dyn Storage) -> u64 {
+    let sql = "SELECT COUNT(*) FROM contracts";
 
-    let native_contracts_count = 9; // fetch natives properly in future
-    get_stat_internal::<u64>(conn, &sql) + native_contracts_count
+    get_stat_internal::<u64>(conn, sql, &[]) + NATIVE_CONTRACTS.len() as u64
 }
 
-pub fn get_addresses_internal(conn: &PooledConnection<SqliteConnectionManager>) -> u64 {
+pub fn get_addresses_internal(conn: &dyn Storage) -> u64 {
     let sql = "SELECT COUNT(DISTINCT address) FROM addresses";
-    get_stat_internal::<u64>(conn, sql)
+    get_stat_internal::<u64>(conn, sql, &[])
 }
 
-pub fn get_contracts_current_week_internal(
-    conn: &PooledConnection<SqliteConnectionManager>,
-) -> u64 {
-    let sql = "SELECT COUNT(*) 
+pub fn get_contracts_current_week_internal(conn: &dyn Storage) -> u64 {
+    let sql = "SELECT COUNT(*)
         FROM contracts 
         INNER JOIN blocks ON blocks.id = block_index 
-        WHERE time >= strftime('%s', 'now', '-7 days') * 1000";
-    get_stat_internal::<u64>(conn, sql)
+        WHERE time >= $1";
+    get_stat_internal::<u64>(conn, sql, &[week_ago()])
 }
 
-pub fn get_addresses_current_week_internal(
-    conn: &PooledConnection<SqliteConnectionManager>,
-) -> u64 {
-    let sql = "SELECT COUNT(*) 
+pub fn get_addresses_current_week_internal(conn: &dyn Storage) -> u64 {
+    let sql = "SELECT COUNT(*)
         FROM addresses AS a
         WHERE a.block_index IN (
             SELECT b.id
             FROM blocks AS b
-            WHERE b.time >= strftime('%s', 'now', '-7 days') * 1000
+            WHERE b.time >= $1
         )
         AND NOT EXISTS (
             SELECT 1 
             FROM addresses AS a2
             INNER JOIN blocks AS b2 ON a2.block_index = b2.id
             WHERE a2.address = a.address
-            AND b2.time < strftime('%s', 'now', '-7 days') * 1000
+            AND b2.time < $1
         )";
-    get_stat_internal::<u64>(conn, sql)
+    get_stat_internal::<u64>(conn, sql, &[week_ago()])
 }
 
-pub fn get_transactions_current_week_internal(
-    conn: &PooledConnection<SqliteConnectionManager>,
-) -> u64 {
-    let sql = "SELECT COUNT(*) 
+pub fn get_transactions_current_week_internal(conn: &dyn Storage) -> u64 {
+    let sql = "SELECT COUNT(*)
         FROM transactions 
         INNER JOIN blocks ON blocks.id = block_index 
-        WHERE time >= strftime('%s', 'now', '-7 days') * 1000";
-    get_stat_internal::<u64>(conn, sql)
+        WHERE time >= $1";
+    get_stat_internal::<u64>(conn, sql, &[week_ago()])
 }
@@ -0,0 +1,119 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use serde_json::json;
+
+use crate::error::Error;
+use crate::shared::{checker, node};
+use crate::ConnectionPool;
+
+use super::internals;
+use super::models::{ProofQuery, StateProof, VerifiedProof, VerifyQuery};
+
+#[get("/v1/stateroot/{index}")]
+async fn get_state_root(
+    pool: web::Data<ConnectionPool>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let Ok(index) = path.trim().parse::<u64>() else {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid block index.".to_string(),
+        });
+    };
+
+    let state_root = pool
+        .connection
+        .run(move |conn| internals::get_state_root_internal(conn, index))
+        .await;
+
+    match state_root {
+        Ok(state_root) => HttpResponse::Ok().json(state_root),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
+// getproof against the root Shrike stored for the block
+#[get("/v1/stateroot/{index}/proof")]
+async fn get_proof(
+    pool: web::Data<ConnectionPool>,
+    path: web::Path<String>,
+    query: web::Query<ProofQuery>,
+) -> impl Responder {
+    let Ok(index) = path.trim().parse::<u64>() else {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid block index.".to_string(),
+        });
+    };
+    let ProofQuery { contract, key } = query.into_inner();
+
+    if !checker::is_neo_script_hash(&contract) {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid contract hash.".to_string(),
+        });
+    }
+
+    let proof = async {
+        let state_root = pool
+            .connection
+            .run(move |conn| internals::get_state_root_internal(conn, index))
+            .await?;
+
+        let params = json!([state_root.root_hash, contract, key]);
+        let proof = node::call(pool.rpc_url.as_deref(), "getproof", params).await?;
+
+        Ok::<_, Error>(StateProof {
+            index,
+            root_hash: state_root.root_hash,
+            contract,
+            key,
+            proof,
+        })
+    }
+    .await;
+
+    match proof {
+        Ok(proof) => HttpResponse::Ok().json(proof),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
+// verifyproof against the root Shrike stored for the block
+#[get("/v1/stateroot/{index}/verify")]
+async fn verify_proof(
+    pool: web::Data<ConnectionPool>,
+    path: web::Path<String>,
+    query: web::Query<VerifyQuery>,
+) -> impl Responder {
+    let Ok(index) = path.trim().parse::<u64>() else {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid block index.".to_string(),
+        });
+    };
+    let proof = query.into_inner().proof;
+
+    let verified = async {
+        let state_root = pool
+            .connection
+            .run(move |conn| internals::get_state_root_internal(conn, index))
+            .await?;
+
+        let params = json!([state_root.root_hash, proof]);
+        let value = node::call(pool.rpc_url.as_deref(), "verifyproof", params).await?;
+
+        Ok::<_, Error>(VerifiedProof {
+            index,
+            root_hash: state_root.root_hash,
+            value,
+        })
+    }
+    .await;
+
+    match verified {
+        Ok(verified) => HttpResponse::Ok().json(verified),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
+pub fn config(cfg: &mut web::ServiceConfig) {
+    cfg.service(get_state_root)
+        .service(get_proof)
+        .service(verify_proof);
+}
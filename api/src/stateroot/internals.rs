@@ -0,0 +1,23 @@
+use lib::db::Storage;
+
+use crate::error::Error;
+
+use super::models::StateRoot;
+
+pub fn get_state_root_internal(conn: &dyn Storage, index: u64) -> Result<StateRoot, Error> {
+    if !conn.table_exists("state_roots")? {
+        return Err(Error {
+            error: "State roots are not indexed yet. Run the latest Indexer.".to_string(),
+        });
+    }
+
+    conn.query_row(
+        "SELECT block_index, root_hash, validated, witnesses, mismatch FROM state_roots
+        WHERE block_index = $1",
+        &[index.into()],
+    )
+    .and_then(|row| row.as_ref().map(StateRoot::from_row).transpose())?
+    .ok_or_else(|| Error {
+        error: "State root does not exist.".to_string(),
+    })
+}
@@ -0,0 +1,51 @@
+use lib::db::{Row, StorageError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StateRoot {
+    pub index: u64,
+    pub root_hash: String,
+    pub validated: bool, // signed by the state validators
+    pub witnesses: Value,
+    pub mismatch: Option<String>, // the root the node reported later, if it changed
+}
+
+impl StateRoot {
+    pub fn from_row(row: &Row) -> Result<Self, StorageError> {
+        Ok(StateRoot {
+            index: row.get(0)?,
+            root_hash: row.get(1)?,
+            validated: row.get(2)?,
+            witnesses: row.get(3)?,
+            mismatch: row.get(4)?,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ProofQuery {
+    pub contract: String,
+    pub key: String, // base64 storage key
+}
+
+#[derive(Deserialize)]
+pub struct VerifyQuery {
+    pub proof: String,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct StateProof {
+    pub index: u64,
+    pub root_hash: String,
+    pub contract: String,
+    pub key: String,
+    pub proof: Value, // base64, as returned by the node's getproof
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct VerifiedProof {
+    pub index: u64,
+    pub root_hash: String,
+    pub value: Value, // base64 storage value the proof resolves to
+}
@@ -0,0 +1,88 @@
+use actix_web::web;
+use lib::db::sqlite::SqliteStorage;
+use lib::db::{is_postgres_url, Storage};
+use r2d2::Pool;
+use r2d2_sqlite::rusqlite::OpenFlags;
+use r2d2_sqlite::SqliteConnectionManager;
+
+use std::path::Path;
+
+#[cfg(feature = "postgres")]
+use lib::db::postgres::PostgresStorage;
+#[cfg(feature = "postgres")]
+use r2d2_postgres::{postgres::NoTls, PostgresConnectionManager};
+
+pub type Connection = Box<dyn Storage + Send>;
+
+// Connection pool for either backend. Connections are handed out behind the storage trait,
+// so the queries don't care where the data lives.
+#[derive(Clone)]
+pub enum StoragePool {
+    Sqlite(Pool<SqliteConnectionManager>),
+    #[cfg(feature = "postgres")]
+    Postgres(Pool<PostgresConnectionManager<NoTls>>),
+}
+
+impl StoragePool {
+    // the API only reads, so both backends are opened read-only
+    pub fn open(url: &str) -> Result<Self, String> {
+        if is_postgres_url(url) {
+            return Self::open_postgres(url);
+        }
+
+        if !Path::new(url).exists() {
+            return Err(format!("No database at {url}."));
+        }
+
+        let manager =
+            SqliteConnectionManager::file(url).with_flags(OpenFlags::SQLITE_OPEN_READ_ONLY);
+        let pool = Pool::new(manager).map_err(|e| e.to_string())?;
+
+        Ok(StoragePool::Sqlite(pool))
+    }
+
+    #[cfg(feature = "postgres")]
+    fn open_postgres(url: &str) -> Result<Self, String> {
+        let mut config: r2d2_postgres::postgres::Config = url
+            .parse()
+            .map_err(|e: r2d2_postgres::postgres::Error| e.to_string())?;
+        config.options("-c default_transaction_read_only=on");
+
+        let manager = PostgresConnectionManager::new(config, NoTls);
+        let pool = Pool::new(manager).map_err(|e| e.to_string())?;
+
+        Ok(StoragePool::Postgres(pool))
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    fn open_postgres(_: &str) -> Result<Self, String> {
+        Err(String::from(
+            "Postgres support is not compiled in, rebuild the API with the `postgres` feature.",
+        ))
+    }
+
+    // Queries block, and the Postgres driver panics when used from an async worker,
+    // so they run on the blocking thread pool
+    pub async fn run<T, F>(&self, query: F) -> T
+    where
+        F: FnOnce(&dyn Storage) -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let pool = self.clone();
+
+        web::block(move || {
+            let conn = pool.get().expect("Failed to get a database connection");
+            query(conn.as_ref())
+        })
+        .await
+        .expect("Database query panicked")
+    }
+
+    pub fn get(&self) -> Result<Connection, r2d2::Error> {
+        match self {
+            StoragePool::Sqlite(pool) => Ok(Box::new(SqliteStorage::new(pool.get()?))),
+            #[cfg(feature = "postgres")]
+            StoragePool::Postgres(pool) => Ok(Box::new(PostgresStorage::new(pool.get()?))),
+        }
+    }
+}
@@ -1,17 +1,17 @@
 use actix_web::{get, web, HttpResponse, Responder};
 
 use crate::error::Error;
-use crate::shared::checker;
+use crate::shared::{checker, models, nns};
 use crate::ConnectionPool;
 
 use super::internals;
+use super::models::{AttributeQuery, FaultedQuery, ATTRIBUTE_TYPES};
 
 #[get("/v1/transaction/{hash}")]
 async fn get_transaction(
     pool: web::Data<ConnectionPool>,
     path: web::Path<String>,
 ) -> impl Responder {
-    let conn = &pool.connection.get().unwrap();
     let hash = path.into_inner();
 
     if !checker::is_neo_txid_hash(&hash) {
@@ -20,7 +20,10 @@ async fn get_transaction(
         });
     }
 
-    let transaction = internals::get_transaction_internal(conn, hash);
+    let transaction = pool
+        .connection
+        .run(move |conn| internals::get_transaction_internal(conn, hash))
+        .await;
 
     match transaction {
         Ok(tx) => HttpResponse::Ok().json(tx),
@@ -33,16 +36,52 @@ async fn get_sender_transactions(
     pool: web::Data<ConnectionPool>,
     path: web::Path<String>,
 ) -> impl Responder {
-    let conn = &pool.connection.get().unwrap();
     let address = path.into_inner();
 
-    if !checker::is_neo_address(&address) {
+    if !checker::is_address_or_name(&address) {
         return HttpResponse::Ok().json(Error {
             error: "Invalid address.".to_string(),
         });
     }
 
-    let transactions = internals::get_sender_transactions_internal(conn, address);
+    let transactions = pool
+        .connection
+        .run(move |conn| {
+            let address = nns::resolve_address(conn, address)?;
+            internals::get_sender_transactions_internal(conn, address)
+        })
+        .await;
+
+    match transactions {
+        Ok(txs) => HttpResponse::Ok().json(txs),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
+#[get("/v1/transaction/signer/{address}")]
+async fn get_signer_transactions(
+    pool: web::Data<ConnectionPool>,
+    path: web::Path<String>,
+    query: web::Query<models::PageQuery>,
+) -> impl Responder {
+    let address = path.into_inner();
+
+    if !checker::is_address_or_name(&address) {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid address.".to_string(),
+        });
+    }
+
+    let limit = models::page_limit(query.limit);
+    let offset = query.offset.unwrap_or(0);
+
+    let transactions = pool
+        .connection
+        .run(move |conn| {
+            let address = nns::resolve_address(conn, address)?;
+            internals::get_signer_transactions_internal(conn, address, limit, offset)
+        })
+        .await;
 
     match transactions {
         Ok(txs) => HttpResponse::Ok().json(txs),
@@ -55,16 +94,21 @@ async fn get_address_transfers(
     pool: web::Data<ConnectionPool>,
     path: web::Path<String>,
 ) -> impl Responder {
-    let conn = &pool.connection.get().unwrap();
     let address = path.into_inner();
 
-    if !checker::is_neo_address(&address) {
+    if !checker::is_address_or_name(&address) {
         return HttpResponse::Ok().json(Error {
             error: "Invalid address.".to_string(),
         });
     }
 
-    let transfer_list = internals::get_address_transfers_internal(conn, address);
+    let transfer_list = pool
+        .connection
+        .run(move |conn| {
+            let address = nns::resolve_address(conn, address)?;
+            internals::get_address_transfers_internal(conn, address)
+        })
+        .await;
 
     match transfer_list {
         Ok(txs) => HttpResponse::Ok().json(txs),
@@ -72,8 +116,84 @@ async fn get_address_transfers(
     }
 }
 
+#[get("/v1/transactions/faulted")]
+async fn get_faulted_transactions(
+    pool: web::Data<ConnectionPool>,
+    query: web::Query<FaultedQuery>,
+) -> impl Responder {
+    let query = query.into_inner();
+
+    if let Some(sender) = &query.sender {
+        if !checker::is_address_or_name(sender) {
+            return HttpResponse::Ok().json(Error {
+                error: "Invalid address.".to_string(),
+            });
+        }
+    }
+
+    let limit = models::page_limit(query.limit);
+    let offset = query.offset.unwrap_or(0);
+
+    let transactions = pool
+        .connection
+        .run(move |conn| {
+            let mut query = query;
+            query.sender = query
+                .sender
+                .map(|sender| nns::resolve_address(conn, sender))
+                .transpose()?;
+            internals::get_faulted_transactions_internal(conn, query, limit, offset)
+        })
+        .await;
+
+    match transactions {
+        Ok(txs) => HttpResponse::Ok().json(txs),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
+#[get("/v1/transactions/attribute/{type}")]
+async fn get_attribute_transactions(
+    pool: web::Data<ConnectionPool>,
+    path: web::Path<String>,
+    query: web::Query<AttributeQuery>,
+) -> impl Responder {
+    let attribute_type = path.into_inner();
+    let query = query.into_inner();
+
+    if !ATTRIBUTE_TYPES.contains(&attribute_type.as_str()) {
+        return HttpResponse::Ok().json(Error {
+            error: "Invalid attribute type.".to_string(),
+        });
+    }
+
+    let limit = models::page_limit(query.limit);
+    let offset = query.offset.unwrap_or(0);
+
+    let transactions = pool
+        .connection
+        .run(move |conn| {
+            internals::get_attribute_transactions_internal(
+                conn,
+                attribute_type,
+                query.value,
+                limit,
+                offset,
+            )
+        })
+        .await;
+
+    match transactions {
+        Ok(txs) => HttpResponse::Ok().json(txs),
+        Err(err) => HttpResponse::Ok().json(err),
+    }
+}
+
 pub fn config(cfg: &mut web::ServiceConfig) {
     cfg.service(get_transaction)
+        .service(get_faulted_transactions)
+        .service(get_attribute_transactions)
         .service(get_sender_transactions)
+        .service(get_signer_transactions)
         .service(get_address_transfers);
 }
@@ -1,75 +1,45 @@
+use lib::db::Storage;
 use lib::neo;
-use r2d2::PooledConnection;
-use r2d2_sqlite::SqliteConnectionManager;
 
-use crate::block::internals;
 use crate::error::Error;
 use crate::shared::events;
 use crate::shared::models::{Transaction, TransactionList, TxDataList};
 
-pub fn get_transaction_internal(
-    conn: &PooledConnection<SqliteConnectionManager>,
-    hash: String,
-) -> Result<Transaction, Error> {
-    let sql = "SELECT * FROM transactions WHERE hash = ?";
-    let mut stmt = conn.prepare(sql).unwrap();
-
-    let transaction = stmt.query_row([hash], |row| {
-        Ok(Transaction {
-            index: row.get(0)?,
-            hash: row.get(1)?,
-            block_index: row.get(2)?,
-            vm_state: row.get(3)?,
-            size: row.get(4)?,
-            version: row.get(5)?,
-            nonce: row.get(6)?,
-            sender: row.get(7)?,
-            sysfee: row.get(8)?,
-            netfee: row.get(9)?,
-            valid_until: row.get(10)?,
-            signers: row.get(11)?,
-            script: row.get(12)?,
-            witnesses: row.get(13)?,
-            stack_result: row.get(14)?,
-            notifications: row.get(15)?,
+use super::models::{
+    AttributeTransactionList, FaultedQuery, FaultedTransaction, FaultedTransactionList,
+    SignedTransactionList,
+};
+
+fn query_transactions(
+    conn: &dyn Storage,
+    sql: &str,
+    param: String,
+) -> Result<Vec<Transaction>, Error> {
+    conn.query(sql, &[param.into()])
+        .and_then(|rows| rows.iter().map(Transaction::from_row).collect())
+        .map_err(|e| Error {
+            error: e.to_string(),
         })
-    });
+}
 
-    transaction.map_err(|_| Error {
+pub fn get_transaction_internal(conn: &dyn Storage, hash: String) -> Result<Transaction, Error> {
+    let transaction = conn
+        .query_row("SELECT * FROM transactions WHERE hash = $1", &[hash.into()])
+        .ok()
+        .flatten()
+        .and_then(|row| Transaction::from_row(&row).ok());
+
+    transaction.ok_or_else(|| Error {
         error: "Transaction does not exist.".to_string(),
     })
 }
 
 pub fn get_sender_transactions_internal(
-    conn: &PooledConnection<SqliteConnectionManager>,
+    conn: &dyn Storage,
     address: String,
 ) -> Result<TransactionList, Error> {
-    let sql = "SELECT * FROM transactions WHERE sender = ?";
-    let mut stmt = conn.prepare(sql).unwrap();
-
-    let mut rows = stmt.query([address]).unwrap();
-    let mut transactions = Vec::new();
-
-    while let Some(row) = rows.next().unwrap() {
-        transactions.push(Transaction {
-            index: row.get(0).unwrap(),
-            hash: row.get(1).unwrap(),
-            block_index: row.get(2).unwrap(),
-            vm_state: row.get(3).unwrap(),
-            size: row.get(4).unwrap(),
-            version: row.get(5).unwrap(),
-            nonce: row.get(6).unwrap(),
-            sender: row.get(7).unwrap(),
-            sysfee: row.get(8).unwrap(),
-            netfee: row.get(9).unwrap(),
-            valid_until: row.get(10).unwrap(),
-            signers: row.get(11).unwrap(),
-            script: row.get(12).unwrap(),
-            witnesses: row.get(13).unwrap(),
-            stack_result: row.get(14).unwrap(),
-            notifications: row.get(15).unwrap(),
-        })
-    }
+    let sql = "SELECT * FROM transactions WHERE sender = $1";
+    let transactions = query_transactions(conn, sql, address)?;
 
     match transactions.is_empty() {
         false => Ok(TransactionList { transactions }),
@@ -79,37 +49,88 @@ pub fn get_sender_transactions_internal(
     }
 }
 
+// transactions with the address among their signers, at any position, most recent first
+pub fn get_signer_transactions_internal(
+    conn: &dyn Storage,
+    address: String,
+    limit: u64,
+    offset: u64,
+) -> Result<SignedTransactionList, Error> {
+    if !conn.table_exists("signers")? {
+        return Err(Error {
+            error: "Signers are not indexed yet. Run the latest Indexer.".to_string(),
+        });
+    }
+
+    let transactions = conn
+        .query(
+            "SELECT t.* FROM transactions t
+            WHERE t.hash IN (SELECT txid FROM signers WHERE account = $1)
+            ORDER BY t.block_index DESC, t.id DESC LIMIT $2 OFFSET $3",
+            &[(&address).into(), limit.into(), offset.into()],
+        )
+        .and_then(|rows| rows.iter().map(Transaction::from_row).collect())?;
+
+    Ok(SignedTransactionList {
+        address,
+        transactions,
+        limit,
+        offset,
+    })
+}
+
+// transactions carrying an attribute of that type, optionally with that value, most recent first
+pub fn get_attribute_transactions_internal(
+    conn: &dyn Storage,
+    attribute_type: String,
+    value: Option<String>,
+    limit: u64,
+    offset: u64,
+) -> Result<AttributeTransactionList, Error> {
+    if !conn.table_exists("attributes")? {
+        return Err(Error {
+            error: "Attributes are not indexed yet. Run the latest Indexer.".to_string(),
+        });
+    }
+
+    let rows = match &value {
+        Some(value) => conn.query(
+            "SELECT t.* FROM transactions t
+            WHERE t.hash IN (SELECT txid FROM attributes WHERE attribute_type = $1 AND value = $2)
+            ORDER BY t.block_index DESC, t.id DESC LIMIT $3 OFFSET $4",
+            &[
+                (&attribute_type).into(),
+                value.into(),
+                limit.into(),
+                offset.into(),
+            ],
+        ),
+        None => conn.query(
+            "SELECT t.* FROM transactions t
+            WHERE t.hash IN (SELECT txid FROM attributes WHERE attribute_type = $1)
+            ORDER BY t.block_index DESC, t.id DESC LIMIT $2 OFFSET $3",
+            &[(&attribute_type).into(), limit.into(), offset.into()],
+        ),
+    };
+
+    let transactions = rows.and_then(|rows| rows.iter().map(Transaction::from_row).collect())?;
+
+    Ok(AttributeTransactionList {
+        attribute_type,
+        value,
+        transactions,
+        limit,
+        offset,
+    })
+}
+
 pub fn get_address_transfers_internal(
-    conn: &PooledConnection<SqliteConnectionManager>,
+    conn: &dyn Storage,
     address: String,
 ) -> Result<TxDataList, Error> {
     let base64 = neo::address_to_base64(&address);
-    let sql = "SELECT * FROM transactions WHERE notifications LIKE ?";
-    let mut stmt = conn.prepare(sql).unwrap();
-
-    let mut rows = stmt.query([format!("%{}%", base64)]).unwrap();
-    let mut transactions = Vec::new();
-
-    while let Some(row) = rows.next().unwrap() {
-        transactions.push(Transaction {
-            index: row.get(0).unwrap(),
-            hash: row.get(1).unwrap(),
-            block_index: row.get(2).unwrap(),
-            vm_state: row.get(3).unwrap(),
-            size: row.get(4).unwrap(),
-            version: row.get(5).unwrap(),
-            nonce: row.get(6).unwrap(),
-            sender: row.get(7).unwrap(),
-            sysfee: row.get(8).unwrap(),
-            netfee: row.get(9).unwrap(),
-            valid_until: row.get(10).unwrap(),
-            signers: row.get(11).unwrap(),
-            script: row.get(12).unwrap(),
-            witnesses: row.get(13).unwrap(),
-            stack_result: row.get(14).unwrap(),
-            notifications: row.get(15).unwrap(),
-        })
-    }
+    let sql = "SELECT * FROM transactions WHERE notifications LIKE $1";
+    let transactions = query_transactions(conn, sql, format!("%{}%", base64))?;
 
     let mut tx_list = TxDataList {
         address: address.clone(),
@@ -119,10 +140,7 @@ pub fn get_address_transfers_internal(
 
     for transaction in transactions {
         let sender = transaction.clone().sender;
-        let block_time =
-            internals::get_block_time(conn, transaction.block_index.to_string()).unwrap();
-        let mut tx_data = events::get_transfer_events(transaction);
-        tx_data.time = block_time;
+        let tx_data = events::get_transfer_events(transaction);
 
         if sender == address {
             tx_list.as_sender.push(tx_data);
@@ -139,3 +157,50 @@ pub fn get_address_transfers_internal(
         Ok(tx_list)
     }
 }
+
+// most recent first
+pub fn get_faulted_transactions_internal(
+    conn: &dyn Storage,
+    query: FaultedQuery,
+    limit: u64,
+    offset: u64,
+) -> Result<FaultedTransactionList, Error> {
+    let mut conditions = vec![String::from("vm_state = 'FAULT'")];
+    let mut params = Vec::new();
+
+    if let Some(sender) = query.sender {
+        params.push(sender.into());
+        conditions.push(format!("sender = ${}", params.len()));
+    }
+    if let Some(from_block) = query.from_block {
+        params.push(from_block.into());
+        conditions.push(format!("block_index >= ${}", params.len()));
+    }
+    if let Some(to_block) = query.to_block {
+        params.push(to_block.into());
+        conditions.push(format!("block_index <= ${}", params.len()));
+    }
+    params.push(limit.into());
+    params.push(offset.into());
+
+    let sql = format!(
+        "SELECT {} FROM transactions WHERE {} ORDER BY block_index DESC, id DESC LIMIT ${} OFFSET ${}",
+        FaultedTransaction::COLUMNS,
+        conditions.join(" AND "),
+        params.len() - 1,
+        params.len()
+    );
+
+    let transactions = conn
+        .query(&sql, &params)
+        .and_then(|rows| rows.iter().map(FaultedTransaction::from_row).collect())
+        .map_err(|e| Error {
+            error: e.to_string(),
+        })?;
+
+    Ok(FaultedTransactionList {
+        transactions,
+        limit,
+        offset,
+    })
+}
@@ -1,2 +1,3 @@
 pub mod controller;
-mod internals;
+pub mod internals;
+pub mod models;
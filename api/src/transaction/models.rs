@@ -0,0 +1,82 @@
+use lib::db::{Row, StorageError};
+use serde::{Deserialize, Serialize};
+
+use crate::shared::models::Transaction;
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FaultedTransaction {
+    pub hash: String,
+    pub block_index: u64,
+    pub block_time: u64,
+    pub sender: String,
+    pub exception: Option<String>,
+    pub gas_consumed: Option<String>,
+    pub sysfee: String,
+    pub netfee: String,
+}
+
+impl FaultedTransaction {
+    pub const COLUMNS: &'static str =
+        "hash, block_index, block_time, sender, exception, gas_consumed, sysfee, netfee";
+
+    pub fn from_row(row: &Row) -> Result<Self, StorageError> {
+        Ok(FaultedTransaction {
+            hash: row.get(0)?,
+            block_index: row.get(1)?,
+            block_time: row.get(2)?,
+            sender: row.get(3)?,
+            exception: row.get(4)?,
+            gas_consumed: row.get(5)?,
+            sysfee: row.get(6)?,
+            netfee: row.get(7)?,
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct FaultedTransactionList {
+    pub transactions: Vec<FaultedTransaction>,
+    pub limit: u64,
+    pub offset: u64,
+}
+
+#[derive(Deserialize)]
+pub struct FaultedQuery {
+    pub sender: Option<String>,
+    pub from_block: Option<u64>,
+    pub to_block: Option<u64>, // inclusive
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+pub const ATTRIBUTE_TYPES: &[&str] = &[
+    "HighPriority",
+    "OracleResponse",
+    "NotValidBefore",
+    "Conflicts",
+    "NotaryAssisted",
+];
+
+#[derive(Deserialize)]
+pub struct AttributeQuery {
+    pub value: Option<String>, // oracle request id, conflicting hash or NotValidBefore height
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct AttributeTransactionList {
+    pub attribute_type: String,
+    pub value: Option<String>,
+    pub transactions: Vec<Transaction>,
+    pub limit: u64,
+    pub offset: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SignedTransactionList {
+    pub address: String,
+    pub transactions: Vec<Transaction>,
+    pub limit: u64,
+    pub offset: u64,
+}
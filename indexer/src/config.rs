@@ -1,33 +1,250 @@
-use lib::db::DB_PATH;
+use clap::{Parser, Subcommand};
+use lib::db::{db_path, is_postgres_url};
+use lib::network::{is_valid_network_name, NetworkProfile};
+
+#[derive(Parser, Debug)]
+#[command(
+    version,
+    about = "Indexes Neo N3 chain data into a relational database"
+)]
+pub struct Args {
+    /// Install or upgrade NeoGo without asking for confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+
+    /// Install NeoGo from a local file, a directory of release assets or a mirror URL
+    #[arg(long)]
+    pub node_source: Option<String>,
+
+    /// Expected sha256 checksum of the NeoGo asset, overriding the pinned manifest
+    #[arg(long)]
+    pub node_checksum: Option<String>,
+
+    /// Network to index: mainnet, testnet, or the name of a private network
+    #[arg(long, default_value = "mainnet")]
+    pub network: String,
+
+    /// NeoGo protocol configuration file, required for private networks
+    #[arg(long)]
+    pub node_config: Option<String>,
+
+    /// RPC endpoint of the local node, overriding the network default
+    #[arg(long)]
+    pub rpc_url: Option<String>,
+
+    /// SQLite file or postgres:// URL to index into, instead of the per-network SQLite file
+    #[arg(long, env = "SHRIKE_DATABASE_URL")]
+    pub database_url: Option<String>,
+
+    /// Blocks fetched, converted and written together
+    #[arg(long)]
+    pub batch_size: Option<u64>,
+
+    /// Batches fetched from the node at the same time
+    #[arg(long)]
+    pub fetch_workers: Option<usize>,
+
+    /// Batches converted at the same time
+    #[arg(long)]
+    pub convert_workers: Option<usize>,
+
+    /// Serve Prometheus metrics at http://<address>/metrics, e.g. 127.0.0.1:9184
+    #[arg(long)]
+    pub metrics_address: Option<String>,
+
+    /// Also record pending transactions from the node's mempool
+    #[arg(long)]
+    pub mempool: bool,
+
+    /// Check every block's hash and merkle root against its binary form
+    #[arg(long)]
+    pub verify_hashes: bool,
+
+    /// Runs the Indexer when omitted
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Export or import a compressed copy of the database
+    #[command(subcommand)]
+    Snapshot(SnapshotCommand),
+    /// Check the database for gaps and compare it with the node
+    Verify {
+        /// Compare this many evenly spread blocks with the node instead of every block
+        #[arg(long)]
+        sample: Option<u64>,
+
+        /// Re-index the broken ranges that were found
+        #[arg(long)]
+        repair: bool,
+    },
+    /// Delete and regenerate the rows stored for a range of blocks
+    Reindex {
+        /// First block to re-index
+        #[arg(long)]
+        from: u64,
+
+        /// Last block to re-index, inclusive
+        #[arg(long)]
+        to: u64,
+
+        /// Tables to regenerate, all by default. Processor tables such as contracts and addresses
+        /// are rebuilt from stored transactions, blocks and transactions are fetched from the node
+        /// along with everything else
+        #[arg(long, value_delimiter = ',')]
+        tables: Vec<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotCommand {
+    /// Write a consistent snapshot of the database, safe to run while indexing
+    Export {
+        /// Directory to write the snapshot and its manifest to
+        #[arg(long, default_value = ".")]
+        output: String,
+    },
+    /// Restore the database from a snapshot manifest
+    Import {
+        /// Path to the snapshot manifest (.json)
+        manifest: String,
+
+        /// Replace an existing database
+        #[arg(long)]
+        force: bool,
+    },
+}
 
 #[derive(Debug)]
 pub struct AppConfig {
+    pub network: NetworkProfile,
     pub test_db: bool,
     pub db_path: String,
+    pub database_url: Option<String>,
     pub node_path: String,
     pub node_version: String,
     pub log_level: String,
     pub batch_size: u64,
+    pub fetch_workers: usize,
+    pub convert_workers: usize,
     pub keep_alive: bool,
     pub keep_alive_interval: u64,
     pub height_limit: u64,
+    pub node_log_path: String,
+    pub node_log_level: String,
+    pub node_restart_backoff_min: u64,
+    pub node_restart_backoff_max: u64,
+    pub node_report_interval: u64,
+    pub auto_install: bool,
+    pub node_source: Option<String>,
+    pub node_checksum: Option<String>,
+    pub metrics_address: Option<String>,
+    pub mempool: bool,
+    pub mempool_interval: u64,
+    pub verify_hashes: bool,
 }
 
 impl AppConfig {
     pub fn new() -> Self {
+        Self::for_network(NetworkProfile::mainnet())
+    }
+
+    pub fn for_network(network: NetworkProfile) -> Self {
         Self {
             test_db: false,
-            db_path: DB_PATH
+            db_path: db_path(&network.name)
                 .to_str()
                 .expect("Failed to convert path")
                 .to_string(),
-            node_path: String::from("http://localhost:10332"),
+            database_url: None,
+            node_path: network.rpc_url.clone(),
+            network,
             node_version: String::from("v0.107.2"),
             log_level: String::from("info"),
             batch_size: 25,
+            fetch_workers: 4,
+            convert_workers: 2,
             keep_alive: true,
             keep_alive_interval: 5,
             height_limit: 0,
+            node_log_path: String::from("./log/neogo.log"),
+            node_log_level: String::from("warn"),
+            node_restart_backoff_min: 1,
+            node_restart_backoff_max: 60,
+            node_report_interval: 60,
+            auto_install: false,
+            node_source: None,
+            node_checksum: None,
+            metrics_address: None,
+            mempool: false,
+            mempool_interval: 1,
+            verify_hashes: false,
+        }
+    }
+
+    // None when indexing into Postgres
+    pub fn sqlite_path(&self) -> Option<&str> {
+        match &self.database_url {
+            Some(url) if is_postgres_url(url) => None,
+            Some(path) => Some(path),
+            None => Some(&self.db_path),
+        }
+    }
+
+    pub fn from_args(args: &Args) -> Result<Self, anyhow::Error> {
+        if !is_valid_network_name(&args.network) {
+            return Err(anyhow::anyhow!("Invalid network name: {}.", args.network));
+        }
+
+        let network = match (NetworkProfile::from_name(&args.network), &args.node_config) {
+            (Some(profile), None) => profile,
+            (_, Some(node_config)) => {
+                let rpc_url = NetworkProfile::mainnet().rpc_url;
+                NetworkProfile::private(&args.network, node_config, &rpc_url)
+            }
+            (None, None) => {
+                return Err(anyhow::anyhow!(
+                    "Unknown network {}. Pass --node-config to index a private network.",
+                    args.network
+                ))
+            }
+        };
+
+        let mut config = Self::for_network(network);
+        if let Some(rpc_url) = &args.rpc_url {
+            config.network.rpc_url = rpc_url.clone();
+            config.node_path = rpc_url.clone();
         }
+
+        if args.database_url.is_some() {
+            config.database_url = args.database_url.clone();
+        }
+
+        if let Some(batch_size) = args.batch_size {
+            config.batch_size = batch_size;
+        }
+        if let Some(fetch_workers) = args.fetch_workers {
+            config.fetch_workers = fetch_workers;
+        }
+        if let Some(convert_workers) = args.convert_workers {
+            config.convert_workers = convert_workers;
+        }
+
+        config.auto_install = config.auto_install || args.yes;
+        if args.node_source.is_some() {
+            config.node_source = args.node_source.clone();
+        }
+        if args.node_checksum.is_some() {
+            config.node_checksum = args.node_checksum.clone();
+        }
+        if args.metrics_address.is_some() {
+            config.metrics_address = args.metrics_address.clone();
+        }
+        config.mempool = config.mempool || args.mempool;
+        config.verify_hashes = config.verify_hashes || args.verify_hashes;
+
+        Ok(config)
     }
 }
@@ -1,285 +1,553 @@
+use lib::db::{in_transaction, is_postgres_url, Dialect, Storage, StorageError};
+use lib::integer::{self, GAS_DECIMALS};
 use log::info;
-use rusqlite::{params, Connection, Result};
+use tokio::runtime::{Handle, RuntimeFlavor};
+use tokio::task;
+
+use std::time::Instant;
 
 use crate::config::AppConfig;
+use crate::processor::{self, Processor};
+use crate::utils::metrics::METRICS;
+
+use super::migrations;
+use super::model::{Block, BlockBatch, NetworkMetadata, StateRoot, Transaction};
 
-use super::model::{Address, Block, Contract, Transaction};
+type Result<T> = std::result::Result<T, StorageError>;
 
 pub struct Database {
-    conn: Connection,
+    // only taken on drop
+    storage: Option<Box<dyn Storage>>,
+    processors: Vec<Box<dyn Processor>>,
+}
+
+// Queries block, and the Postgres driver panics when driven from an async worker.
+// block_in_place hands the worker's other tasks off while the query runs.
+fn blocking<T>(f: impl FnOnce() -> T) -> T {
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            task::block_in_place(f)
+        }
+        _ => f(),
+    }
 }
 
 impl Database {
     pub fn new(config: &AppConfig) -> Result<Self> {
         if config.test_db {
             info!("Using test database.");
-            let conn = Connection::open("shrike_test.db3")?;
+            let storage = blocking(|| lib::db::open("shrike_test.db3"))?;
 
-            Ok(Database { conn })
+            Ok(Database::from_storage(storage))
         } else {
-            info!("Using database at {}.", config.db_path);
-            let conn = Connection::open(&config.db_path)?;
-
-            Ok(Database { conn })
+            match &config.database_url {
+                // the url can hold credentials, keep it out of the logs
+                Some(url) if is_postgres_url(url) => info!("Using Postgres database."),
+                Some(url) => info!("Using database at {}.", url),
+                None => info!("Using database at {}.", config.db_path),
+            }
+            let url = config.database_url.as_ref().unwrap_or(&config.db_path);
+            let storage = blocking(|| lib::db::open(url))?;
+
+            Ok(Database::from_storage(storage))
         }
     }
 
-    pub fn set_to_wal(&self) -> Result<()> {
-        let wal_active: String = self
-            .conn
-            .query_row("PRAGMA journal_mode", [], |row| row.get(0))?;
-        if wal_active == "wal" {
-            info!("WAL mode already active.");
-        } else {
-            let _: String = self
-                .conn
-                .query_row("PRAGMA journal_mode=WAL", [], |row| row.get(0))?;
-            info!("Set db to WAL mode.");
+    pub fn from_storage(storage: Box<dyn Storage>) -> Self {
+        Database {
+            storage: Some(storage),
+            processors: processor::builtin(),
         }
+    }
 
-        Ok(())
+    // must happen before `migrate`, so the processor's tables are created
+    #[allow(dead_code)]
+    pub fn register(&mut self, processor: Box<dyn Processor>) {
+        self.processors.push(processor);
     }
 
-    pub fn create_index(&self, name: &str, table: &str, column: &str) -> Result<usize> {
-        let sql = format!("CREATE INDEX IF NOT EXISTS {name} ON {table} ({column})");
-        let result = self.conn.execute(&sql, [])?;
+    pub fn processor_names(&self) -> Vec<&'static str> {
+        self.processors
+            .iter()
+            .map(|processor| processor.name())
+            .collect()
+    }
 
-        Ok(result)
+    fn processors(&self) -> Vec<&dyn Processor> {
+        self.processors.iter().map(Box::as_ref).collect()
     }
 
-    pub fn create_block_table(&self) -> Result<usize> {
-        let result = self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS blocks (
-            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
-            hash                TEXT NOT NULL UNIQUE,
-            size                INTEGER NOT NULL,
-            version             INTEGER NOT NULL,
-            merkle_root         TEXT NOT NULL,
-            time                INTEGER NOT NULL,
-            nonce               TEXT NOT NULL,
-            speaker             INTEGER NOT NULL,
-            next_consensus      TEXT NOT NULL,
-            reward              FLOAT NOT NULL,
-            reward_receiver     TEXT NOT NULL,
-            witnesses           TEXT NOT NULL
-        )",
-            [],
-        )?;
+    pub(crate) fn blocking<T>(&self, query: impl FnOnce(&dyn Storage) -> T) -> T {
+        let storage = self.storage.as_deref().expect("Database used after drop");
 
-        Ok(result)
+        blocking(|| query(storage))
     }
 
-    pub fn create_transaction_table(&self) -> Result<usize> {
-        let result = self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS transactions (
-            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
-            hash                TEXT NOT NULL UNIQUE,
-            block_index         INTEGER NOT NULL,
-            vm_state            TEXT NOT NULL,
-            size                INTEGER NOT NULL,
-            version             INTEGER NOT NULL,
-            nonce               INTEGER NOT NULL,
-            sender              TEXT NOT NULL,
-            sysfee              TEXT NOT NULL,
-            netfee              TEXT NOT NULL,
-            valid_until         INTEGER NOT NULL,
-            signers             TEXT NOT NULL,
-            script              TEXT NOT NULL,
-            witnesses           TEXT NOT NULL,
-            stack_result        TEXT,
-            notifications       TEXT,
-            FOREIGN KEY (block_index) REFERENCES blocks (id)
-        )",
-            [],
-        )?;
+    pub fn set_to_wal(&self) -> Result<()> {
+        self.blocking(|storage| {
+            if storage.dialect() != Dialect::Sqlite {
+                return Ok(());
+            }
+
+            let wal_active: Option<String> = storage
+                .query_row("PRAGMA journal_mode", &[])?
+                .map(|row| row.get(0))
+                .transpose()?;
+            if wal_active.as_deref() == Some("wal") {
+                info!("WAL mode already active.");
+            } else {
+                storage.query("PRAGMA journal_mode=WAL", &[])?;
+                info!("Set db to WAL mode.");
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn migrate(&self) -> std::result::Result<Vec<u32>, anyhow::Error> {
+        let processors = self.processors();
+
+        self.blocking(|storage| {
+            let applied = migrations::migrate(storage)?;
+            migrations::migrate_processors(storage, &processors)?;
 
-        Ok(result)
+            Ok(applied)
+        })
     }
 
-    pub fn create_address_table(&self) -> Result<usize> {
-        let result = self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS addresses (
-            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
-            block_index         INTEGER NOT NULL,
-            address             TEXT NOT NULL,
-            balances            TEXT NOT NULL,
-            FOREIGN KEY (block_index) REFERENCES blocks (id)
-        )",
-            [],
-        )?;
+    pub fn get_network_metadata(&self) -> Result<Option<NetworkMetadata>> {
+        self.blocking(|storage| {
+            let row = storage.query_row(
+                "SELECT name, magic, address_version, genesis_hash FROM network WHERE id = 0",
+                &[],
+            )?;
+
+            match row {
+                Some(row) => Ok(Some(NetworkMetadata {
+                    name: row.get(0)?,
+                    magic: row.get(1)?,
+                    address_version: row.get(2)?,
+                    genesis_hash: row.get(3)?,
+                })),
+                None => Ok(None),
+            }
+        })
+    }
 
-        Ok(result)
+    pub fn insert_network_metadata(&self, metadata: &NetworkMetadata) -> Result<u64> {
+        self.blocking(|storage| {
+            storage.execute(
+                "INSERT INTO network (id, name, magic, address_version, genesis_hash)
+                VALUES (0, $1, $2, $3, $4)",
+                &[
+                    metadata.name.as_str().into(),
+                    metadata.magic.into(),
+                    metadata.address_version.into(),
+                    metadata.genesis_hash.as_str().into(),
+                ],
+            )
+        })
     }
 
-    pub fn create_contract_table(&self) -> Result<usize> {
-        let result = self.conn.execute(
-            "CREATE TABLE IF NOT EXISTS contracts (
-            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
-            block_index         INTEGER NOT NULL,
-            hash                TEXT NOT NULL UNIQUE,
-            contract_type       TEXT NOT NULL,
-            FOREIGN KEY (block_index) REFERENCES blocks (id)
-        )",
-            [],
-        )?;
+    pub fn get_block_hash(&self, index: u64) -> Result<Option<String>> {
+        self.blocking(|storage| {
+            storage
+                .query_row("SELECT hash FROM blocks WHERE id = $1", &[index.into()])?
+                .map(|row| row.get(0))
+                .transpose()
+        })
+    }
 
-        Ok(result)
+    // Writes a batch and everything the processors derive from it in one transaction,
+    // so a failed batch leaves nothing behind
+    pub fn insert_batch(&self, batch: &BlockBatch) -> anyhow::Result<()> {
+        let processors = self.processors();
+
+        self.blocking(|storage| {
+            in_transaction(storage, |storage| {
+                let started = Instant::now();
+                insert_blocks(storage, &batch.blocks)?;
+                insert_transactions(storage, &batch.transactions)?;
+                insert_state_roots(storage, &batch.state_roots)?;
+                METRICS.observe_write("blocks_transactions", started);
+
+                processor::process(&processors, storage, &batch.blocks, &batch.transactions)
+            })
+        })
     }
 
-    pub fn insert_into_block_table(&self, block: &Block) -> Result<usize> {
-        let sql = "INSERT INTO blocks (
-            id, hash, size, version, merkle_root, time,
-            nonce, speaker, next_consensus, reward, reward_receiver, witnesses
-        ) VALUES (0, ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)";
-
-        let result = self.conn.execute(
-            sql,
-            params![
-                block.hash,
-                block.size,
-                block.version,
-                block.merkle_root,
-                block.time,
-                block.nonce,
-                block.speaker,
-                block.next_consensus,
-                block.reward,
-                block.reward_receiver,
-                block.witnesses
-            ],
-        )?;
+    // Swaps everything stored for the blocks in [start, end) for the batch in one transaction,
+    // so repairs can be interrupted and rerun safely
+    pub fn replace_range(&self, start: u64, end: u64, batch: &BlockBatch) -> anyhow::Result<()> {
+        let processors = self.processors();
+
+        self.blocking(|storage| {
+            in_transaction(storage, |storage| {
+                for processor in &processors {
+                    processor.delete_range(storage, start, end)?;
+                }
+                delete_range(storage, start, end)?;
+                insert_blocks(storage, &batch.blocks)?;
+                insert_transactions(storage, &batch.transactions)?;
+                insert_state_roots(storage, &batch.state_roots)?;
+
+                processor::process(&processors, storage, &batch.blocks, &batch.transactions)
+            })
+        })
+    }
 
-        Ok(result)
+    // Runs the named processors again over the stored blocks in [start, end), leaving the
+    // blocks, transactions and other processors' tables as they are
+    pub fn reprocess(&self, start: u64, end: u64, names: &[String]) -> anyhow::Result<()> {
+        let processors: Vec<&dyn Processor> = self
+            .processors()
+            .into_iter()
+            .filter(|processor| names.iter().any(|name| name == processor.name()))
+            .collect();
+
+        self.blocking(|storage| {
+            in_transaction(storage, |storage| {
+                for processor in &processors {
+                    processor.delete_range(storage, start, end)?;
+                }
+                let blocks = select_blocks(storage, start, end)?;
+                let transactions = select_transactions(storage, start, end)?;
+
+                processor::process(&processors, storage, &blocks, &transactions)
+            })
+        })
     }
 
-    pub fn insert_contracts(&self, contracts: impl Iterator<Item = Contract>) -> Result<()> {
-        let tx = self.conn.unchecked_transaction()?;
+    // [start, end) ranges of missing block ids below the stored height
+    pub fn find_block_gaps(&self) -> Result<Vec<(u64, u64)>> {
+        self.blocking(|storage| {
+            let mut gaps = Vec::new();
+
+            let first: Option<u64> = match storage.query_row("SELECT MIN(id) FROM blocks", &[])? {
+                Some(row) => row.get(0)?,
+                None => None,
+            };
+            if let Some(first) = first.filter(|first| *first > 0) {
+                gaps.push((0, first));
+            }
+
+            let rows = storage.query(
+                "SELECT b.id + 1, (SELECT MIN(n.id) FROM blocks n WHERE n.id > b.id)
+                FROM blocks b
+                WHERE NOT EXISTS (SELECT 1 FROM blocks n WHERE n.id = b.id + 1)
+                AND b.id < (SELECT MAX(id) FROM blocks)
+                ORDER BY b.id",
+                &[],
+            )?;
+            for row in rows {
+                gaps.push((row.get(0)?, row.get(1)?));
+            }
+
+            Ok(gaps)
+        })
+    }
 
-        let mut stmt = self.conn.prepare_cached(
-            "INSERT INTO contracts (
-            block_index, hash, contract_type
-        ) VALUES (?1, ?2, ?3)",
-        )?;
+    // block indexes that transactions point at but that aren't stored
+    pub fn find_orphaned_transactions(&self) -> Result<Vec<u64>> {
+        self.blocking(|storage| {
+            storage
+                .query(
+                    "SELECT DISTINCT block_index FROM transactions t
+                    WHERE NOT EXISTS (SELECT 1 FROM blocks b WHERE b.id = t.block_index)
+                    ORDER BY block_index",
+                    &[],
+                )?
+                .iter()
+                .map(|row| row.get(0))
+                .collect()
+        })
+    }
 
-        for contract in contracts {
-            stmt.execute(params![
-                contract.block_index,
-                contract.hash,
-                contract.contract_type
-            ])?;
-        }
+    pub fn count_block_transactions(&self, index: u64) -> Result<u64> {
+        self.blocking(|storage| {
+            storage
+                .query_row(
+                    "SELECT COUNT(*) FROM transactions WHERE block_index = $1",
+                    &[index.into()],
+                )?
+                .map_or(Ok(0), |row| row.get(0))
+        })
+    }
 
-        let result = tx.commit();
-        if let Err(e) = result {
-            println!("Error committing transaction: {e:?}");
-        }
+    // stored roots of blocks up to `height` that weren't validated yet, oldest first,
+    // leaving out those already found not to match
+    pub fn get_unvalidated_state_roots(&self, height: u64, limit: u64) -> Result<Vec<StateRoot>> {
+        self.blocking(|storage| {
+            storage
+                .query(
+                    "SELECT block_index, root_hash, validated, witnesses FROM state_roots
+                    WHERE validated = 0 AND mismatch IS NULL AND block_index <= $1
+                    ORDER BY block_index LIMIT $2",
+                    &[height.into(), limit.into()],
+                )?
+                .iter()
+                .map(|row| {
+                    Ok(StateRoot {
+                        index: row.get(0)?,
+                        root_hash: row.get(1)?,
+                        validated: row.get(2)?,
+                        witnesses: row.get(3)?,
+                    })
+                })
+                .collect()
+        })
+    }
 
-        Ok(())
+    pub fn set_state_root_validated(&self, index: u64, witnesses: &str) -> Result<u64> {
+        self.blocking(|storage| {
+            storage.execute(
+                "UPDATE state_roots SET validated = 1, witnesses = $2 WHERE block_index = $1",
+                &[index.into(), witnesses.into()],
+            )
+        })
     }
 
-    pub fn insert_addresses(&self, addresses: impl Iterator<Item = Address>) -> Result<()> {
-        let tx = self.conn.unchecked_transaction()?;
+    // keeps the stored root, the one the node now reports is kept next to it
+    pub fn set_state_root_mismatch(&self, index: u64, root_hash: &str) -> Result<u64> {
+        self.blocking(|storage| {
+            storage.execute(
+                "UPDATE state_roots SET mismatch = $2 WHERE block_index = $1",
+                &[index.into(), root_hash.into()],
+            )
+        })
+    }
 
-        let mut stmt = self.conn.prepare_cached(
-            "INSERT INTO addresses (
-            block_index, address, balances
-        ) VALUES (?1, ?2, ?3)",
-        )?;
+    // the first unfinished block of an interrupted re-index of exactly this range and tables
+    pub fn get_reindex_progress(&self, from: u64, to: u64, tables: &str) -> Result<Option<u64>> {
+        self.blocking(|storage| {
+            storage
+                .query_row(
+                    "SELECT next_block FROM reindex_progress
+                    WHERE id = 0 AND from_block = $1 AND to_block = $2 AND tables = $3",
+                    &[from.into(), to.into(), tables.into()],
+                )?
+                .map(|row| row.get(0))
+                .transpose()
+        })
+    }
 
-        for address in addresses {
-            stmt.execute(params![
-                address.block_index,
-                address.address,
-                address.balances
-            ])?;
-        }
+    // replaces the progress of any other re-index, only the latest one can be resumed
+    pub fn set_reindex_progress(&self, from: u64, to: u64, tables: &str, next: u64) -> Result<u64> {
+        self.blocking(|storage| {
+            storage.execute(
+                "INSERT INTO reindex_progress (id, from_block, to_block, tables, next_block)
+                VALUES (0, $1, $2, $3, $4)
+                ON CONFLICT (id) DO UPDATE SET from_block = excluded.from_block,
+                to_block = excluded.to_block, tables = excluded.tables, next_block = excluded.next_block",
+                &[from.into(), to.into(), tables.into(), next.into()],
+            )
+        })
+    }
 
-        let result = tx.commit();
-        if let Err(e) = result {
-            println!("Error committing transaction: {e:?}");
-        }
+    pub fn clear_reindex_progress(&self) -> Result<u64> {
+        self.blocking(|storage| storage.execute("DELETE FROM reindex_progress", &[]))
+    }
+
+    // None when the table is empty
+    pub fn get_last_index(&self, table: &str) -> Result<Option<u64>> {
+        self.blocking(|storage| {
+            let sql = format!("SELECT MAX(id) FROM {table}");
+
+            match storage.query_row(&sql, &[])? {
+                Some(row) => row.get(0),
+                None => Ok(None),
+            }
+        })
+    }
+
+    // None when nothing has been indexed yet
+    pub fn get_stored_height(&self) -> Result<Option<u64>> {
+        self.get_last_index("blocks")
+    }
 
-        Ok(())
+    #[allow(dead_code)]
+    pub fn drop_table(&self, table: &str) -> Result<u64> {
+        self.blocking(|storage| storage.execute(&format!("DROP TABLE {table}"), &[]))
     }
+}
 
-    // synced rollback for both tables
-    pub fn insert_blocks_transactions(
-        &self,
-        blocks: impl Iterator<Item = Block>,
-        transactions: impl Iterator<Item = Transaction>,
-    ) -> Result<()> {
-        let tx = self.conn.unchecked_transaction()?;
+// closing a Postgres connection blocks too
+impl Drop for Database {
+    fn drop(&mut self) {
+        if let Some(storage) = self.storage.take() {
+            blocking(|| drop(storage));
+        }
+    }
+}
 
-        let mut block_stmt = self.conn.prepare_cached(
+fn delete_range(storage: &dyn Storage, start: u64, end: u64) -> Result<()> {
+    storage.execute(
+        "DELETE FROM transactions WHERE block_index >= $1 AND block_index < $2",
+        &[start.into(), end.into()],
+    )?;
+    storage.execute(
+        "DELETE FROM state_roots WHERE block_index >= $1 AND block_index < $2",
+        &[start.into(), end.into()],
+    )?;
+    storage.execute(
+        "DELETE FROM blocks WHERE id >= $1 AND id < $2",
+        &[start.into(), end.into()],
+    )?;
+
+    Ok(())
+}
+
+// `reward` is the older FLOAT column, kept filled for queries written against it
+fn insert_blocks(storage: &dyn Storage, blocks: &[Block]) -> Result<()> {
+    for block in blocks {
+        let legacy_reward = integer::format_fixed(&block.reward, GAS_DECIMALS)
+            .parse::<f64>()
+            .unwrap_or_default();
+
+        storage.execute(
             "INSERT INTO blocks (
-            hash, size, version, merkle_root, time,
-            nonce, speaker, next_consensus, reward, reward_receiver, witnesses
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            id, hash, size, version, merkle_root, time,
+            nonce, speaker, next_consensus, reward, reward_receiver, witnesses, reward_amount
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)",
+            &[
+                block.index.into(),
+                (&block.hash).into(),
+                block.size.into(),
+                block.version.into(),
+                (&block.merkle_root).into(),
+                block.time.into(),
+                (&block.nonce).into(),
+                block.speaker.into(),
+                (&block.next_consensus).into(),
+                legacy_reward.into(),
+                (&block.reward_receiver).into(),
+                (&block.witnesses).into(),
+                (&block.reward).into(),
+            ],
         )?;
+    }
 
-        for block in blocks {
-            block_stmt.execute(params![
-                block.hash,
-                block.size,
-                block.version,
-                block.merkle_root,
-                block.time,
-                block.nonce,
-                block.speaker,
-                block.next_consensus,
-                block.reward,
-                block.reward_receiver,
-                block.witnesses
-            ])?;
-        }
+    Ok(())
+}
 
-        let mut tx_stmt = self.conn.prepare_cached(
+// a failed statement aborts the whole transaction on Postgres,
+// so duplicates are skipped by the database instead of ignored here
+fn insert_transactions(storage: &dyn Storage, transactions: &[Transaction]) -> Result<()> {
+    for transaction in transactions {
+        storage.execute(
             "INSERT INTO transactions (
             hash, block_index, vm_state, size, version, nonce, sender, sysfee, netfee,
-            valid_until, signers, script, witnesses, stack_result, notifications
-        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            valid_until, signers, script, witnesses, stack_result, notifications, block_time,
+            exception, gas_consumed, attributes
+        ) VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19
+        )
+        ON CONFLICT DO NOTHING",
+            &[
+                (&transaction.hash).into(),
+                transaction.block_index.into(),
+                (&transaction.vm_state).into(),
+                transaction.size.into(),
+                transaction.version.into(),
+                transaction.nonce.into(),
+                (&transaction.sender).into(),
+                (&transaction.sysfee).into(),
+                (&transaction.netfee).into(),
+                transaction.valid_until.into(),
+                (&transaction.signers).into(),
+                (&transaction.script).into(),
+                (&transaction.witnesses).into(),
+                (&transaction.stack_result).into(),
+                (&transaction.notifications).into(),
+                transaction.block_time.into(),
+                transaction.exception.as_deref().into(),
+                (&transaction.gas_consumed).into(),
+                (&transaction.attributes).into(),
+            ],
         )?;
-
-        for transaction in transactions {
-            let _ = tx_stmt.execute(params![
-                transaction.hash,
-                transaction.block_index,
-                transaction.vm_state,
-                transaction.size,
-                transaction.version,
-                transaction.nonce,
-                transaction.sender,
-                transaction.sysfee,
-                transaction.netfee,
-                transaction.valid_until,
-                transaction.signers,
-                transaction.script,
-                transaction.witnesses,
-                transaction.stack_result,
-                transaction.notifications
-            ]);
-        }
-
-        let result = tx.commit();
-        if let Err(e) = result {
-            println!("Error committing transaction: {e:?}");
-        }
-
-        Ok(())
     }
 
-    pub fn get_last_index(&self, table: &str) -> Result<u64> {
-        let sql = &format!("SELECT id FROM {table} WHERE id=(SELECT max(id) FROM {table})");
-        let mut stmt = self.conn.prepare(sql)?;
-        let index: u64 = stmt.query_row([], |row| row.get(0))?;
+    Ok(())
+}
 
-        Ok(index)
+fn insert_state_roots(storage: &dyn Storage, state_roots: &[StateRoot]) -> Result<()> {
+    for state_root in state_roots {
+        storage.execute(
+            "INSERT INTO state_roots (block_index, root_hash, validated, witnesses)
+            VALUES ($1, $2, $3, $4)",
+            &[
+                state_root.index.into(),
+                (&state_root.root_hash).into(),
+                state_root.validated.into(),
+                (&state_root.witnesses).into(),
+            ],
+        )?;
     }
 
-    #[allow(dead_code)]
-    pub fn drop_table(&self, table: &str) -> Result<usize> {
-        let result = self.conn.execute(&format!("DROP TABLE {table}"), [])?;
+    Ok(())
+}
 
-        Ok(result)
-    }
+fn select_blocks(storage: &dyn Storage, start: u64, end: u64) -> Result<Vec<Block>> {
+    let rows = storage.query(
+        "SELECT id, hash, size, version, merkle_root, time, nonce, speaker, next_consensus,
+        reward_amount, reward_receiver, witnesses
+        FROM blocks WHERE id >= $1 AND id < $2 ORDER BY id",
+        &[start.into(), end.into()],
+    )?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(Block {
+                index: row.get(0)?,
+                hash: row.get(1)?,
+                size: row.get(2)?,
+                version: row.get(3)?,
+                merkle_root: row.get(4)?,
+                time: row.get(5)?,
+                nonce: row.get(6)?,
+                speaker: row.get(7)?,
+                next_consensus: row.get(8)?,
+                reward: row.get(9)?,
+                reward_receiver: row.get(10)?,
+                witnesses: row.get(11)?,
+            })
+        })
+        .collect()
+}
+
+fn select_transactions(storage: &dyn Storage, start: u64, end: u64) -> Result<Vec<Transaction>> {
+    let rows = storage.query(
+        "SELECT hash, block_index, vm_state, size, version, nonce, sender, sysfee, netfee,
+        valid_until, signers, script, witnesses, COALESCE(stack_result, '[]'),
+        COALESCE(notifications, '[]'), block_time, exception, COALESCE(gas_consumed, '0'),
+        COALESCE(attributes, '[]')
+        FROM transactions WHERE block_index >= $1 AND block_index < $2 ORDER BY id",
+        &[start.into(), end.into()],
+    )?;
+
+    rows.iter()
+        .map(|row| {
+            Ok(Transaction {
+                hash: row.get(0)?,
+                block_index: row.get(1)?,
+                vm_state: row.get(2)?,
+                size: row.get(3)?,
+                version: row.get(4)?,
+                nonce: row.get(5)?,
+                sender: row.get(6)?,
+                sysfee: row.get(7)?,
+                netfee: row.get(8)?,
+                valid_until: row.get(9)?,
+                signers: row.get(10)?,
+                script: row.get(11)?,
+                witnesses: row.get(12)?,
+                stack_result: row.get(13)?,
+                notifications: row.get(14)?,
+                block_time: row.get(15)?,
+                exception: row.get(16)?,
+                gas_consumed: row.get(17)?,
+                attributes: row.get(18)?,
+            })
+        })
+        .collect()
 }
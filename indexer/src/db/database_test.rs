@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::database::Database;
+    use crate::db::model::{Block, BlockBatch, NetworkMetadata, Transaction};
+    use lib::db::sqlite::SqliteStorage;
+    use lib::integer::BigInt;
+
+    fn block(index: u64) -> Block {
+        Block {
+            index,
+            hash: format!("0xb{index}"),
+            size: 114,
+            version: 0,
+            merkle_root: String::from("0x00"),
+            time: 1468595301000 + index * 15000,
+            nonce: String::from("00"),
+            speaker: 0,
+            next_consensus: String::from("N"),
+            reward: BigInt::from(50_000_000),
+            reward_receiver: String::from("N"),
+            witnesses: String::from("[]"),
+        }
+    }
+
+    fn transaction(hash: &str, block: &Block) -> Transaction {
+        Transaction {
+            hash: hash.to_string(),
+            block_index: block.index,
+            vm_state: String::from("HALT"),
+            size: 250,
+            version: 0,
+            nonce: 4294967295,
+            sender: String::from("N"),
+            sysfee: String::from("997775"),
+            netfee: String::from("1231520"),
+            valid_until: 5760,
+            signers: String::from("[]"),
+            script: String::new(),
+            witnesses: String::from("[]"),
+            stack_result: String::from("[]"),
+            notifications: String::from("[]"),
+            block_time: block.time,
+            exception: None,
+            gas_consumed: String::from("997775"),
+            attributes: String::from("[]"),
+        }
+    }
+
+    // exercises the indexer writes against any backend
+    fn round_trip(db: &Database) {
+        db.migrate().unwrap();
+        assert_eq!(db.get_stored_height().unwrap(), None);
+
+        let blocks: Vec<Block> = (0..3).map(block).collect();
+        let transactions = vec![
+            transaction("0xt1", &blocks[1]),
+            // duplicates are skipped rather than failing the batch
+            transaction("0xt1", &blocks[1]),
+            transaction("0xt2", &blocks[2]),
+        ];
+        db.insert_batch(&BlockBatch {
+            blocks,
+            transactions,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(db.get_stored_height().unwrap(), Some(2));
+        assert!(db.get_last_index("transactions").unwrap().is_some());
+        assert_eq!(db.get_block_hash(1).unwrap().as_deref(), Some("0xb1"));
+        assert_eq!(db.get_block_hash(3).unwrap(), None);
+
+        // a failing batch leaves nothing behind
+        assert!(db
+            .insert_batch(&BlockBatch {
+                blocks: vec![block(3), block(3)],
+                ..Default::default()
+            })
+            .is_err());
+        assert_eq!(db.get_stored_height().unwrap(), Some(2));
+
+        let metadata = NetworkMetadata {
+            name: String::from("mainnet"),
+            magic: 860833102,
+            address_version: 53,
+            genesis_hash: String::from("0xb0"),
+        };
+        assert_eq!(db.get_network_metadata().unwrap(), None);
+        db.insert_network_metadata(&metadata).unwrap();
+        assert_eq!(db.get_network_metadata().unwrap(), Some(metadata));
+
+        assert!(db.find_block_gaps().unwrap().is_empty());
+        assert!(db.find_orphaned_transactions().unwrap().is_empty());
+        assert_eq!(db.count_block_transactions(1).unwrap(), 1);
+
+        db.insert_batch(&BlockBatch {
+            blocks: vec![block(5)],
+            ..Default::default()
+        })
+        .unwrap();
+        assert_eq!(db.find_block_gaps().unwrap(), vec![(3, 5)]);
+
+        // replacing a range swaps out everything stored for it, and can be repeated
+        for _ in 0..2 {
+            let batch = BlockBatch {
+                blocks: (1..8).map(block).collect(),
+                transactions: vec![transaction("0xt7", &block(7))],
+                ..Default::default()
+            };
+            db.replace_range(1, 8, &batch).unwrap();
+        }
+        assert!(db.find_block_gaps().unwrap().is_empty());
+        assert!(db.find_orphaned_transactions().unwrap().is_empty());
+        assert_eq!(db.count_block_transactions(1).unwrap(), 0);
+        assert_eq!(db.count_block_transactions(7).unwrap(), 1);
+        assert_eq!(db.get_stored_height().unwrap(), Some(7));
+    }
+
+    #[test]
+    fn test_sqlite_round_trip() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+
+        round_trip(&Database::from_storage(Box::new(storage)));
+    }
+
+    // Needs a scratch database, e.g. SHRIKE_TEST_POSTGRES_URL=postgres://postgres@localhost/shrike_test
+    // runs on a runtime worker like the indexer does, where the driver can't block directly
+    #[cfg(feature = "postgres")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_postgres_round_trip() {
+        use lib::db::postgres::PostgresStorage;
+
+        let Ok(url) = std::env::var("SHRIKE_TEST_POSTGRES_URL") else {
+            eprintln!("SHRIKE_TEST_POSTGRES_URL not set, skipping.");
+            return;
+        };
+        let storage = tokio::task::block_in_place(|| PostgresStorage::connect(&url)).unwrap();
+        let db = Database::from_storage(Box::new(storage));
+        db.blocking(|storage| {
+            storage.execute_batch(
+                "DROP SCHEMA IF EXISTS shrike_test_database CASCADE;
+                CREATE SCHEMA shrike_test_database;
+                SET search_path TO shrike_test_database;",
+            )
+        })
+        .unwrap();
+
+        round_trip(&db);
+    }
+}
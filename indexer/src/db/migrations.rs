@@ -0,0 +1,297 @@
+use log::info;
+
+use lib::db::{in_transaction, Dialect, Storage, StorageError, SCHEMA_VERSION};
+
+use crate::processor::Processor;
+
+type Result<T> = std::result::Result<T, StorageError>;
+
+// Each migration runs in its own transaction together with the schema version bump,
+// so a failed migration leaves the database at the previous version.
+pub struct Migration {
+    pub version: u32,
+    pub description: &'static str,
+    pub up: fn(&dyn Storage) -> Result<()>,
+}
+
+// Append only. Never edit a migration that has shipped, add a new one instead.
+pub static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create base tables",
+        up: create_base_tables,
+    },
+    Migration {
+        version: 2,
+        description: "store block time on transactions",
+        up: add_transaction_block_time,
+    },
+    Migration {
+        version: 3,
+        description: "track processor migrations",
+        up: create_processor_versions,
+    },
+    Migration {
+        version: 4,
+        description: "store transaction exceptions and GAS consumed",
+        up: add_transaction_execution,
+    },
+    Migration {
+        version: 5,
+        description: "store transaction attributes",
+        up: add_transaction_attributes,
+    },
+    Migration {
+        version: 6,
+        description: "store state roots",
+        up: create_state_roots,
+    },
+    Migration {
+        version: 7,
+        description: "store block rewards as integers",
+        up: add_block_reward_amount,
+    },
+    Migration {
+        version: 8,
+        description: "track re-index progress",
+        up: create_reindex_progress,
+    },
+];
+
+// brings the database up to the latest version and returns the versions applied
+pub fn migrate(storage: &dyn Storage) -> std::result::Result<Vec<u32>, anyhow::Error> {
+    let current = storage.schema_version()?;
+
+    if current > SCHEMA_VERSION {
+        return Err(anyhow::anyhow!(
+            "Database schema version {} is newer than this Indexer supports ({}). Upgrade Shrike.",
+            current,
+            SCHEMA_VERSION
+        ));
+    }
+
+    let mut applied = Vec::new();
+    for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+        info!(
+            "Applying migration {}: {}.",
+            migration.version, migration.description
+        );
+
+        in_transaction(storage, |storage| {
+            (migration.up)(storage)?;
+            storage.set_schema_version(migration.version)
+        })?;
+
+        applied.push(migration.version);
+    }
+
+    Ok(applied)
+}
+
+// Processors version their own tables independently of the schema, in `processor_versions`
+pub fn migrate_processors(
+    storage: &dyn Storage,
+    processors: &[&dyn Processor],
+) -> std::result::Result<(), anyhow::Error> {
+    for processor in processors {
+        let name = processor.name();
+        let current: u32 = storage
+            .query_row(
+                "SELECT version FROM processor_versions WHERE name = $1",
+                &[name.into()],
+            )?
+            .map_or(Ok(0), |row| row.get(0))?;
+
+        for migration in processor
+            .migrations()
+            .iter()
+            .filter(|m| m.version > current)
+        {
+            info!(
+                "Applying {} migration {}: {}.",
+                name, migration.version, migration.description
+            );
+
+            in_transaction(storage, |storage| {
+                (migration.up)(storage)?;
+                storage.execute(
+                    "INSERT INTO processor_versions (name, version) VALUES ($1, $2)
+                    ON CONFLICT (name) DO UPDATE SET version = excluded.version",
+                    &[name.into(), migration.version.into()],
+                )
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+// DDL is written for SQLite and widened for Postgres, where every integer is a BIGINT
+// so that values bind the same way on both backends
+pub fn execute_ddl(storage: &dyn Storage, sql: &str) -> Result<()> {
+    match storage.dialect() {
+        Dialect::Sqlite => storage.execute_batch(sql),
+        Dialect::Postgres => storage.execute_batch(
+            &sql.replace("INTEGER PRIMARY KEY AUTOINCREMENT", "BIGSERIAL PRIMARY KEY")
+                .replace("INTEGER", "BIGINT")
+                .replace("FLOAT", "DOUBLE PRECISION"),
+        ),
+    }
+}
+
+// Tables are created with IF NOT EXISTS so databases from before versioning are adopted as is
+fn create_base_tables(storage: &dyn Storage) -> Result<()> {
+    execute_ddl(
+        storage,
+        "CREATE TABLE IF NOT EXISTS network (
+            id                  INTEGER PRIMARY KEY CHECK (id = 0),
+            name                TEXT NOT NULL,
+            magic               INTEGER NOT NULL,
+            address_version     INTEGER NOT NULL,
+            genesis_hash        TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS blocks (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            hash                TEXT NOT NULL UNIQUE,
+            size                INTEGER NOT NULL,
+            version             INTEGER NOT NULL,
+            merkle_root         TEXT NOT NULL,
+            time                INTEGER NOT NULL,
+            nonce               TEXT NOT NULL,
+            speaker             INTEGER NOT NULL,
+            next_consensus      TEXT NOT NULL,
+            reward              FLOAT NOT NULL,
+            reward_receiver     TEXT NOT NULL,
+            witnesses           TEXT NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS transactions (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            hash                TEXT NOT NULL UNIQUE,
+            block_index         INTEGER NOT NULL,
+            vm_state            TEXT NOT NULL,
+            size                INTEGER NOT NULL,
+            version             INTEGER NOT NULL,
+            nonce               INTEGER NOT NULL,
+            sender              TEXT NOT NULL,
+            sysfee              TEXT NOT NULL,
+            netfee              TEXT NOT NULL,
+            valid_until         INTEGER NOT NULL,
+            signers             TEXT NOT NULL,
+            script              TEXT NOT NULL,
+            witnesses           TEXT NOT NULL,
+            stack_result        TEXT,
+            notifications       TEXT,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE TABLE IF NOT EXISTS addresses (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            block_index         INTEGER NOT NULL,
+            address             TEXT NOT NULL,
+            balances            TEXT NOT NULL,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE TABLE IF NOT EXISTS contracts (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            block_index         INTEGER NOT NULL,
+            hash                TEXT NOT NULL UNIQUE,
+            contract_type       TEXT NOT NULL,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_blocks_hash ON blocks (hash);
+        CREATE INDEX IF NOT EXISTS idx_tx_hash ON transactions (hash);
+        CREATE INDEX IF NOT EXISTS idx_tx_senders ON transactions (sender);
+        CREATE INDEX IF NOT EXISTS idx_transaction_block_index ON transactions (block_index);
+        CREATE INDEX IF NOT EXISTS idx_address_address ON addresses (address);
+        CREATE INDEX IF NOT EXISTS idx_contract_hash ON contracts (hash);",
+    )
+}
+
+// saves the API a block lookup per transaction
+fn add_transaction_block_time(storage: &dyn Storage) -> Result<()> {
+    execute_ddl(
+        storage,
+        "ALTER TABLE transactions ADD COLUMN block_time INTEGER NOT NULL DEFAULT 0;
+
+        UPDATE transactions
+        SET block_time = (SELECT time FROM blocks WHERE blocks.id = transactions.block_index)
+        WHERE EXISTS (SELECT 1 FROM blocks WHERE blocks.id = transactions.block_index);",
+    )
+}
+
+fn create_processor_versions(storage: &dyn Storage) -> Result<()> {
+    execute_ddl(
+        storage,
+        "CREATE TABLE IF NOT EXISTS processor_versions (
+            name                TEXT PRIMARY KEY,
+            version             INTEGER NOT NULL
+        );",
+    )
+}
+
+// Transactions indexed before this keep NULL, their execution details weren't stored
+fn add_transaction_execution(storage: &dyn Storage) -> Result<()> {
+    execute_ddl(
+        storage,
+        "ALTER TABLE transactions ADD COLUMN exception TEXT;
+        ALTER TABLE transactions ADD COLUMN gas_consumed TEXT;
+
+        CREATE INDEX IF NOT EXISTS idx_tx_faulted ON transactions (block_index) WHERE vm_state = 'FAULT';",
+    )
+}
+
+// NULL for transactions indexed before this, `reindex` fills them in from the node
+fn add_transaction_attributes(storage: &dyn Storage) -> Result<()> {
+    execute_ddl(
+        storage,
+        "ALTER TABLE transactions ADD COLUMN attributes TEXT;",
+    )
+}
+
+// One row per block whose state root the node returned. `mismatch` holds the root the node
+// reported later when it no longer matches the stored one.
+fn create_state_roots(storage: &dyn Storage) -> Result<()> {
+    execute_ddl(
+        storage,
+        "CREATE TABLE IF NOT EXISTS state_roots (
+            block_index         INTEGER PRIMARY KEY,
+            root_hash           TEXT NOT NULL,
+            validated           INTEGER NOT NULL,
+            witnesses           TEXT NOT NULL,
+            mismatch            TEXT,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_state_roots_unvalidated ON state_roots (block_index) WHERE validated = 0;",
+    )
+}
+
+// The reward in GAS fractions, as text like sysfee. Rewards so far are far below 2^53 fractions,
+// so the old FLOAT column converts back exactly. `reward` is still written for existing queries.
+fn add_block_reward_amount(storage: &dyn Storage) -> Result<()> {
+    execute_ddl(
+        storage,
+        "ALTER TABLE blocks ADD COLUMN reward_amount TEXT;
+
+        UPDATE blocks SET reward_amount = CAST(CAST(ROUND(reward * 100000000) AS INTEGER) AS TEXT);",
+    )
+}
+
+// The running `reindex` command and the first block it hasn't finished, so running the same
+// command again after an interruption picks up where it stopped. Removed once a run completes.
+fn create_reindex_progress(storage: &dyn Storage) -> Result<()> {
+    execute_ddl(
+        storage,
+        "CREATE TABLE IF NOT EXISTS reindex_progress (
+            id                  INTEGER PRIMARY KEY CHECK (id = 0),
+            from_block          INTEGER NOT NULL,
+            to_block            INTEGER NOT NULL,
+            tables              TEXT NOT NULL,
+            next_block          INTEGER NOT NULL
+        );",
+    )
+}
@@ -0,0 +1,124 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::migrations::{migrate, MIGRATIONS};
+    use lib::db::sqlite::SqliteStorage;
+    use lib::db::{Storage, SCHEMA_VERSION};
+
+    const TABLES: [&str; 6] = [
+        "network",
+        "blocks",
+        "transactions",
+        "addresses",
+        "contracts",
+        "processor_versions",
+    ];
+
+    #[test]
+    fn test_migrations_are_ordered() {
+        let versions: Vec<u32> = MIGRATIONS.iter().map(|m| m.version).collect();
+        let expected: Vec<u32> = (1..=MIGRATIONS.len() as u32).collect();
+
+        assert_eq!(versions, expected);
+        assert_eq!(MIGRATIONS.last().unwrap().version, SCHEMA_VERSION);
+    }
+
+    #[test]
+    fn test_migrate_fresh_database() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+
+        let applied = migrate(&storage).unwrap();
+
+        assert_eq!(applied.len(), MIGRATIONS.len());
+        assert_eq!(storage.schema_version().unwrap(), SCHEMA_VERSION);
+        for table in TABLES {
+            assert!(
+                storage.table_exists(table).unwrap(),
+                "missing table {table}"
+            );
+        }
+
+        // running again is a no-op
+        assert!(migrate(&storage).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_migrate_unversioned_database_backfills_block_time() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+
+        // a database created before versioning, at user_version 0
+        (MIGRATIONS[0].up)(&storage).unwrap();
+        storage
+            .execute_batch(
+            "INSERT INTO blocks VALUES (0, '0x01', 114, 0, '0x00', 1468595301000, '00', 0, 'N', 0.5, 'N', '[]');
+            INSERT INTO transactions (
+                hash, block_index, vm_state, size, version, nonce, sender, sysfee, netfee,
+                valid_until, signers, script, witnesses, stack_result, notifications
+            ) VALUES ('0x02', 0, 'HALT', 1, 0, 0, 'N', '0', '0', 1, '[]', '', '[]', '[]', '[]');",
+        )
+        .unwrap();
+
+        let applied = migrate(&storage).unwrap();
+
+        // the base tables are adopted as they are
+        assert_eq!(applied, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        let block_time: u64 = storage
+            .query_row(
+                "SELECT block_time FROM transactions WHERE hash = '0x02'",
+                &[],
+            )
+            .unwrap()
+            .unwrap()
+            .get(0)
+            .unwrap();
+        assert_eq!(block_time, 1468595301000);
+
+        let reward: String = storage
+            .query_row("SELECT reward_amount FROM blocks WHERE id = 0", &[])
+            .unwrap()
+            .unwrap()
+            .get(0)
+            .unwrap();
+        assert_eq!(reward, "50000000");
+    }
+
+    #[test]
+    fn test_migrate_rejects_newer_database() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        storage.set_schema_version(SCHEMA_VERSION + 1).unwrap();
+
+        assert!(migrate(&storage).is_err());
+        assert_eq!(storage.schema_version().unwrap(), SCHEMA_VERSION + 1);
+    }
+
+    // Needs a scratch database, e.g. SHRIKE_TEST_POSTGRES_URL=postgres://postgres@localhost/shrike_test
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_migrate_postgres() {
+        use lib::db::postgres::PostgresStorage;
+
+        let Ok(url) = std::env::var("SHRIKE_TEST_POSTGRES_URL") else {
+            eprintln!("SHRIKE_TEST_POSTGRES_URL not set, skipping.");
+            return;
+        };
+        let storage = PostgresStorage::connect(&url).unwrap();
+        storage
+            .execute_batch(
+                "DROP SCHEMA IF EXISTS shrike_test_migrations CASCADE;
+                CREATE SCHEMA shrike_test_migrations;
+                SET search_path TO shrike_test_migrations;",
+            )
+            .unwrap();
+
+        let applied = migrate(&storage).unwrap();
+
+        assert_eq!(applied.len(), MIGRATIONS.len());
+        assert_eq!(storage.schema_version().unwrap(), SCHEMA_VERSION);
+        for table in TABLES {
+            assert!(
+                storage.table_exists(table).unwrap(),
+                "missing table {table}"
+            );
+        }
+        assert!(migrate(&storage).unwrap().is_empty());
+    }
+}
@@ -1,2 +1,7 @@
 pub mod database;
+mod database_test;
+pub mod migrations;
+mod migrations_test;
 pub mod model;
+pub mod snapshot;
+mod snapshot_test;
@@ -0,0 +1,242 @@
+use anyhow::{anyhow, Context, Result};
+use lib::db::sqlite::SqliteStorage;
+use lib::db::{Storage, SCHEMA_VERSION};
+use log::info;
+use rusqlite::backup::{Backup, StepResult};
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter};
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::config::AppConfig;
+
+const COMPRESSION_LEVEL: i32 = 9;
+
+// Written next to the archive once it is complete, so a manifest always points at a whole snapshot
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotManifest {
+    pub network: String,
+    pub height: u64,
+    pub tip_hash: String,
+    pub schema_version: u32,
+    // archive file name, relative to the manifest
+    pub file: String,
+    // sha256 of the compressed archive
+    pub sha256: String,
+    pub created_at: u64,
+}
+
+// what a database file says about itself, compared against the manifest on import
+#[derive(Debug, PartialEq, Eq)]
+struct SnapshotInfo {
+    network: Option<String>,
+    height: u64,
+    tip_hash: String,
+    schema_version: u32,
+}
+
+fn read_info(path: &Path) -> Result<SnapshotInfo> {
+    let storage = SqliteStorage::open(path)?;
+
+    let network = if storage.table_exists("network")? {
+        storage
+            .query_row("SELECT name FROM network WHERE id = 0", &[])?
+            .map(|row| row.get(0))
+            .transpose()?
+    } else {
+        None
+    };
+
+    let tip = storage
+        .query_row(
+            "SELECT id, hash FROM blocks WHERE id = (SELECT MAX(id) FROM blocks)",
+            &[],
+        )?
+        .ok_or_else(|| anyhow!("Database has no blocks."))?;
+
+    Ok(SnapshotInfo {
+        network,
+        height: tip.get(0)?,
+        tip_hash: tip.get(1)?,
+        schema_version: storage.schema_version()?,
+    })
+}
+
+fn sha256_file(path: &Path) -> Result<String> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut BufReader::new(File::open(path)?), &mut hasher)?;
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+fn sqlite_path(config: &AppConfig) -> Result<&str> {
+    config.sqlite_path().ok_or_else(|| {
+        anyhow!("Snapshots are only supported for SQLite databases. Use pg_dump for Postgres.")
+    })
+}
+
+pub fn export(config: &AppConfig, output: &Path) -> Result<SnapshotManifest> {
+    let source_path = sqlite_path(config)?;
+    if !Path::new(source_path).exists() {
+        return Err(anyhow!("No database at {}.", source_path));
+    }
+
+    fs::create_dir_all(output)?;
+    let copy_path = output.join(format!("shrike-{}.db3.tmp", config.network.name));
+
+    info!("Copying database at {}..", source_path);
+    {
+        let source = Connection::open_with_flags(source_path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+        let mut copy = Connection::open(&copy_path)?;
+
+        // copying every page in a single step holds one read transaction for the whole copy,
+        // so the snapshot is consistent even while the Indexer keeps writing
+        let backup = Backup::new(&source, &mut copy)?;
+        while backup.step(-1)? != StepResult::Done {
+            // the Indexer is holding a write lock, try again shortly
+            thread::sleep(Duration::from_millis(100));
+        }
+    }
+
+    let result = compress(config, &copy_path, output);
+    fs::remove_file(&copy_path)?;
+
+    result
+}
+
+fn compress(config: &AppConfig, copy_path: &Path, output: &Path) -> Result<SnapshotManifest> {
+    let info = read_info(copy_path).context("Failed to read database copy")?;
+    let network = info.network.unwrap_or_else(|| config.network.name.clone());
+    if network != config.network.name {
+        return Err(anyhow!(
+            "Database belongs to {}, not {}.",
+            network,
+            config.network.name
+        ));
+    }
+
+    let name = format!("shrike-{}-{}", network, info.height);
+    let file = format!("{name}.db3.zst");
+    let archive_path = output.join(&file);
+
+    info!("Compressing snapshot at height {}..", info.height);
+    let mut archive = BufWriter::new(File::create(&archive_path)?);
+    zstd::stream::copy_encode(
+        BufReader::new(File::open(copy_path)?),
+        &mut archive,
+        COMPRESSION_LEVEL,
+    )?;
+    archive.into_inner()?.sync_all()?;
+
+    let manifest = SnapshotManifest {
+        network,
+        height: info.height,
+        tip_hash: info.tip_hash,
+        schema_version: info.schema_version,
+        sha256: sha256_file(&archive_path)?,
+        file,
+        created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+    };
+    let manifest_path = output.join(format!("{name}.json"));
+    fs::write(&manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+    info!("Wrote snapshot manifest to {}.", manifest_path.display());
+
+    Ok(manifest)
+}
+
+pub fn import(config: &AppConfig, manifest_path: &Path, force: bool) -> Result<SnapshotManifest> {
+    let target_path = sqlite_path(config)?;
+
+    let manifest: SnapshotManifest = serde_json::from_slice(&fs::read(manifest_path)?)
+        .context("Failed to parse snapshot manifest")?;
+    if manifest.network != config.network.name {
+        return Err(anyhow!(
+            "Snapshot is for {}, not {}.",
+            manifest.network,
+            config.network.name
+        ));
+    }
+    if manifest.schema_version > SCHEMA_VERSION {
+        return Err(anyhow!(
+            "Snapshot schema version {} is newer than this Indexer supports ({}).",
+            manifest.schema_version,
+            SCHEMA_VERSION
+        ));
+    }
+    if Path::new(target_path).exists() && !force {
+        return Err(anyhow!(
+            "Database at {} already exists. Pass --force to replace it.",
+            target_path
+        ));
+    }
+
+    // the archive has to sit next to its manifest
+    if Path::new(&manifest.file).file_name() != Some(manifest.file.as_ref()) {
+        return Err(anyhow!("Invalid snapshot file name {}.", manifest.file));
+    }
+    let archive_path = manifest_path
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(&manifest.file);
+
+    info!("Verifying {}..", archive_path.display());
+    let checksum = sha256_file(&archive_path)?;
+    if !checksum.eq_ignore_ascii_case(&manifest.sha256) {
+        return Err(anyhow!(
+            "Checksum mismatch for {}: expected {}, got {}.",
+            manifest.file,
+            manifest.sha256,
+            checksum
+        ));
+    }
+
+    if let Some(parent) = Path::new(target_path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let import_path = format!("{target_path}.import");
+    let result = restore(&manifest, &archive_path, Path::new(&import_path));
+    if result.is_err() {
+        let _ = fs::remove_file(&import_path);
+    }
+    result?;
+
+    // the journal files belong to the database being replaced
+    for suffix in ["-wal", "-shm"] {
+        let _ = fs::remove_file(format!("{target_path}{suffix}"));
+    }
+    fs::rename(&import_path, target_path)?;
+    info!(
+        "Restored {} at height {} to {}.",
+        manifest.network, manifest.height, target_path
+    );
+
+    Ok(manifest)
+}
+
+fn restore(manifest: &SnapshotManifest, archive_path: &Path, import_path: &Path) -> Result<()> {
+    info!("Decompressing snapshot..");
+    let mut database = BufWriter::new(File::create(import_path)?);
+    zstd::stream::copy_decode(BufReader::new(File::open(archive_path)?), &mut database)?;
+    database.into_inner()?.sync_all()?;
+
+    let info = read_info(import_path).context("Failed to read restored database")?;
+    let expected = SnapshotInfo {
+        network: info.network.clone().map(|_| manifest.network.clone()),
+        height: manifest.height,
+        tip_hash: manifest.tip_hash.clone(),
+        schema_version: manifest.schema_version,
+    };
+    if info != expected {
+        return Err(anyhow!(
+            "Restored database does not match the manifest: {:?}.",
+            info
+        ));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::AppConfig;
+    use crate::db::snapshot::{export, import};
+    use lib::db::sqlite::SqliteStorage;
+    use lib::db::Storage;
+
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("shrike-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn config_for(path: &Path) -> AppConfig {
+        let mut config = AppConfig::new();
+        config.database_url = Some(path.to_str().unwrap().to_string());
+        config
+    }
+
+    fn seed(path: &Path, height: u64) {
+        let storage = SqliteStorage::open(path).unwrap();
+        crate::db::migrations::migrate(&storage).unwrap();
+        for index in 0..=height {
+            storage
+                .execute(
+                    "INSERT INTO blocks (id, hash, size, version, merkle_root, time, nonce, speaker,
+                    next_consensus, reward, reward_receiver, witnesses, reward_amount)
+                    VALUES ($1, $2, 0, 0, '', 0, '', 0, '', 0, '', '[]', '0')",
+                    &[index.into(), format!("0xb{index}").into()],
+                )
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_export_import() {
+        let dir = scratch_dir("snapshot-round-trip");
+        let source = dir.join("source.db3");
+        seed(&source, 4);
+
+        let manifest = export(&config_for(&source), &dir.join("out")).unwrap();
+        assert_eq!(manifest.network, "mainnet");
+        assert_eq!(manifest.height, 4);
+        assert_eq!(manifest.tip_hash, "0xb4");
+        assert_eq!(manifest.file, "shrike-mainnet-4.db3.zst");
+        let manifest_path = dir.join("out").join("shrike-mainnet-4.json");
+        assert!(manifest_path.exists());
+
+        let target = dir.join("target.db3");
+        let target_config = config_for(&target);
+        assert_eq!(
+            import(&target_config, &manifest_path, false).unwrap(),
+            manifest
+        );
+
+        let storage = SqliteStorage::open(&target).unwrap();
+        let tip: Option<String> = storage
+            .query_row("SELECT hash FROM blocks WHERE id = 4", &[])
+            .unwrap()
+            .map(|row| row.get(0).unwrap());
+        assert_eq!(tip.as_deref(), Some("0xb4"));
+        assert_eq!(storage.schema_version().unwrap(), manifest.schema_version);
+        drop(storage);
+
+        // existing databases are only replaced on request
+        assert!(import(&target_config, &manifest_path, false).is_err());
+        assert!(import(&target_config, &manifest_path, true).is_ok());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_import_rejects_tampering() {
+        let dir = scratch_dir("snapshot-tampered");
+        let source = dir.join("source.db3");
+        seed(&source, 1);
+
+        let manifest = export(&config_for(&source), &dir).unwrap();
+        let manifest_path = dir.join("shrike-mainnet-1.json");
+        let target = dir.join("target.db3");
+
+        let mut archive = fs::read(dir.join(&manifest.file)).unwrap();
+        archive.push(0);
+        fs::write(dir.join(&manifest.file), archive).unwrap();
+        let error = import(&config_for(&target), &manifest_path, false).unwrap_err();
+        assert!(error.to_string().contains("Checksum mismatch"));
+        assert!(!target.exists());
+
+        // snapshots only restore into the network they were taken from
+        let mut testnet = config_for(&target);
+        testnet.network.name = String::from("testnet");
+        assert!(import(&testnet, &manifest_path, false).is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,31 +1,46 @@
 use anyhow::{Context, Result};
+use clap::Parser;
 use log::{error, info};
 use tokio::time::{sleep, Duration};
 
+use std::path::Path;
 use std::time::SystemTime;
 
 mod config;
 mod db;
+mod processor;
 mod rpc;
 mod spawn;
 mod utils;
 
-use config::AppConfig;
+use config::{AppConfig, Args, Command, SnapshotCommand};
 use db::database::Database as LocalDatabase;
-use db::model::Block;
+use db::snapshot;
 use rpc::client::Client as RpcClient;
 use spawn::indexer::Indexer;
-use utils::logger;
+use spawn::mempool::MempoolObserver;
+use spawn::supervisor::NodeSupervisor;
+use utils::{logger, metrics};
 
-use spawn::sync::run_node;
+use utils::network::check_network;
 use utils::node::check_neogo;
+use utils::reindex::reindex;
+use utils::verify::verify;
 
 #[tokio::main]
 async fn main() {
+    let args = Args::parse();
     logger::init();
 
-    if let Err(e) = run().await {
-        // if let Err(e) = dev_run().await {
+    let result = match &args.command {
+        Some(Command::Snapshot(command)) => run_snapshot(&args, command),
+        Some(Command::Verify { sample, repair }) => run_verify(&args, *sample, *repair).await,
+        Some(Command::Reindex { from, to, tables }) => run_reindex(&args, *from, *to, tables).await,
+        None => run(&args).await,
+        // None => dev_run(&args).await,
+    };
+
+    if let Err(e) = result {
         error!("Application error: {:?}", e);
         std::process::exit(1);
     }
@@ -33,8 +48,8 @@ async fn main() {
 
 // shortcut for development runs
 #[allow(dead_code)]
-async fn dev_run() -> Result<()> {
-    let config = AppConfig::new();
+async fn dev_run(args: &Args) -> Result<()> {
+    let config = AppConfig::from_args(args)?;
 
     check_neogo(&config)
         .await
@@ -44,13 +59,60 @@ async fn dev_run() -> Result<()> {
     Ok(())
 }
 
-async fn run() -> Result<()> {
-    let config = AppConfig::new();
+fn run_snapshot(args: &Args, command: &SnapshotCommand) -> Result<()> {
+    let config = AppConfig::from_args(args)?;
+
+    match command {
+        SnapshotCommand::Export { output } => {
+            let manifest = snapshot::export(&config, Path::new(output))
+                .context("Failed to export snapshot")?;
+            info!("Exported {} at height {}.", manifest.file, manifest.height);
+        }
+        SnapshotCommand::Import { manifest, force } => {
+            snapshot::import(&config, Path::new(manifest), *force)
+                .context("Failed to import snapshot")?;
+        }
+    }
+
+    Ok(())
+}
+
+// needs a node that has synced at least as far as the database, e.g. a running Indexer's
+async fn run_verify(args: &Args, sample: Option<u64>, repair: bool) -> Result<()> {
+    let config = AppConfig::from_args(args)?;
+
+    let client = RpcClient::new(&config);
+    let db = LocalDatabase::new(&config).context("Failed to initialize database")?;
+    if repair {
+        db.migrate().context("Failed to migrate database")?;
+    }
+
+    verify(&client, &db, &config, sample, repair).await
+}
+
+async fn run_reindex(args: &Args, from: u64, to: u64, tables: &[String]) -> Result<()> {
+    let config = AppConfig::from_args(args)?;
+
+    let client = RpcClient::new(&config);
+    let db = LocalDatabase::new(&config).context("Failed to initialize database")?;
+    db.migrate().context("Failed to migrate database")?;
+
+    reindex(&client, &db, &config, from, to, tables).await
+}
+
+async fn run(args: &Args) -> Result<()> {
+    let config = AppConfig::from_args(args)?;
 
     let client = RpcClient::new(&config);
     let db = LocalDatabase::new(&config).context("Failed to initialize database")?;
 
+    let metrics_server = match &config.metrics_address {
+        Some(address) => Some(metrics::serve(address)?),
+        None => None,
+    };
+
     info!("Welcome to Shrike!");
+    info!("Indexing {}.", config.network.name);
     info!("Checking for NeoGo..");
 
     check_neogo(&config)
@@ -60,49 +122,25 @@ async fn run() -> Result<()> {
     // make sure WAL journal mode is enabled
     db.set_to_wal().context("Failed to set to WAL")?;
 
-    // fails if it already exists
-    db.create_block_table()
-        .context("Failed to create block table")?;
-    db.create_transaction_table()
-        .context("Failed to create transaction table")?;
-    db.create_address_table()
-        .context("Failed to create address table")?;
-    db.create_contract_table()
-        .context("Failed to create contract table")?;
-
-    // create indexes if they don't exist
-    db.create_index("idx_blocks_hash", "blocks", "hash")
-        .context("Failed to create block index")?;
-    db.create_index("idx_tx_hash", "transactions", "hash")
-        .context("Failed to create txid index")?;
-    db.create_index("idx_tx_senders", "transactions", "sender")
-        .context("Failed to create txsender index")?;
-    db.create_index("idx_transaction_block_index", "transactions", "block_index")
-        .context("Failed to create transaction block index")?;
-    db.create_index("idx_address_address", "addresses", "address")
-        .context("Failed to create address index")?;
-    db.create_index("idx_contract_hash", "contracts", "hash")
-        .context("Failed to create contract index")?;
-
-    // some setup
-    let index_result = db
-        .get_last_index("blocks")
-        .context("Failed to get last stored block index");
-
-    if let Ok(value) = index_result {
-        info!("Last stored block index: {}", value);
-        value
-    } else {
-        info!("No rows in table yet. Adding first entry...");
-        db.insert_into_block_table(&Block::genesis_block())
-            .context("Failed to insert genesis block")?;
-        0
-    };
+    // bring the schema up to date
+    let applied = db.migrate().context("Failed to migrate database")?;
+    if applied.is_empty() {
+        info!("Database schema is up to date.");
+    }
+
+    match db
+        .get_stored_height()
+        .context("Failed to get last stored block index")?
+    {
+        Some(value) => info!("Last stored block index: {}", value),
+        None => info!("No rows in table yet. Indexing will start from genesis."),
+    }
 
     // spawn the node and wait for the sync to complete
     info!("Starting node sync..");
     let start = SystemTime::now();
-    let (_stderr_out, handle, shutdown_tx) = run_node(config.height_limit)
+    let mut node = NodeSupervisor::start(&config);
+    node.wait_for_sync(config.height_limit)
         .await
         .context("Failed to sync node")?;
 
@@ -111,13 +149,41 @@ async fn run() -> Result<()> {
     info!("Sync completed in {} ms.", sync_duration.as_millis());
     sleep(Duration::from_secs(2)).await;
 
-    // Launch indexer
-    let indexer = Indexer::new(client, db, config);
-    indexer.run().await?;
+    check_network(&client, &db, &config)
+        .await
+        .context("Failed to check network")?;
+
+    // the observer writes through its own connection
+    let mempool_observer = match config.mempool {
+        true => {
+            let db = LocalDatabase::new(&config).context("Failed to initialize database")?;
+            Some(MempoolObserver::new(
+                client.clone(),
+                db,
+                config.mempool_interval,
+            ))
+        }
+        false => None,
+    };
+
+    // Launch indexer, storage isn't Send so the observer is polled alongside it
+    // rather than spawned, and is dropped once the indexer returns
+    let indexer = Indexer::new(client, db, config, node.subscribe());
+    let result = match mempool_observer {
+        Some(observer) => {
+            tokio::select! {
+                result = indexer.run() => result,
+                _ = observer.run() => unreachable!("the mempool observer never returns"),
+            }
+        }
+        None => indexer.run().await,
+    };
 
     // send the shutdown signal to the node and wait for it to exit
-    let _ = shutdown_tx.send(());
-    handle.await.context("Failed to kill node")?;
+    node.shutdown().await.context("Failed to kill node")?;
+    if let Some(server) = metrics_server {
+        server.abort();
+    }
 
-    Ok(())
+    result
 }
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use lib::db::Storage;
+
+use crate::utils::conversion;
+
+use super::{Notification, Processor};
+
+// senders and recipients of token transfers
+pub struct AddressProcessor;
+
+impl Processor for AddressProcessor {
+    fn name(&self) -> &'static str {
+        "addresses"
+    }
+
+    fn process_notification(
+        &self,
+        storage: &dyn Storage,
+        notification: &Notification,
+    ) -> Result<()> {
+        let block_index = notification.transaction.block_index;
+
+        for address in conversion::convert_transfer_notification(notification.event, block_index) {
+            storage.execute(
+                "INSERT INTO addresses (
+                block_index, address, balances
+            ) VALUES ($1, $2, $3)",
+                &[
+                    address.block_index.into(),
+                    address.address.into(),
+                    address.balances.into(),
+                ],
+            )?;
+        }
+
+        Ok(())
+    }
+
+    fn delete_range(&self, storage: &dyn Storage, start: u64, end: u64) -> Result<()> {
+        storage.execute(
+            "DELETE FROM addresses WHERE block_index >= $1 AND block_index < $2",
+            &[start.into(), end.into()],
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+use anyhow::{Context, Result};
+use lib::db::{Storage, StorageError};
+
+use crate::db::migrations::{execute_ddl, Migration};
+use crate::db::model::Transaction;
+use crate::rpc::models::TransactionAttribute;
+
+use super::Processor;
+
+static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create attributes",
+        up: create_table,
+    },
+    Migration {
+        version: 2,
+        description: "fill attributes of stored transactions",
+        up: backfill,
+    },
+];
+
+// transactions read per query while backfilling
+const BACKFILL_PAGE: u64 = 10_000;
+
+fn create_table(storage: &dyn Storage) -> std::result::Result<(), StorageError> {
+    execute_ddl(
+        storage,
+        "CREATE TABLE IF NOT EXISTS attributes (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            txid                TEXT NOT NULL,
+            block_index         INTEGER NOT NULL,
+            position            INTEGER NOT NULL,
+            attribute_type      TEXT NOT NULL,
+            value               TEXT,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_attribute_type_value ON attributes (attribute_type, value);
+        CREATE INDEX IF NOT EXISTS idx_attribute_txid ON attributes (txid);
+        CREATE INDEX IF NOT EXISTS idx_attribute_block_index ON attributes (block_index);",
+    )
+}
+
+// Transactions stored before the table existed. Those stored before `transactions.attributes`
+// have nothing to fill it from, and ones without attributes are read but add no rows.
+fn backfill(storage: &dyn Storage) -> std::result::Result<(), StorageError> {
+    let mut start: u64 = 0;
+    loop {
+        let rows = storage.query(
+            "SELECT t.id, t.hash, t.block_index, t.attributes FROM transactions t
+            WHERE t.id >= $1 AND t.attributes IS NOT NULL
+            AND NOT EXISTS (SELECT 1 FROM attributes a WHERE a.txid = t.hash)
+            ORDER BY t.id LIMIT $2",
+            &[start.into(), BACKFILL_PAGE.into()],
+        )?;
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        for row in &rows {
+            let id: u64 = row.get(0)?;
+            let hash: String = row.get(1)?;
+            let attributes: String = row.get(3)?;
+            let attributes: Vec<TransactionAttribute> =
+                serde_json::from_str(&attributes).map_err(|e| {
+                    StorageError::Decode(format!("invalid attributes in transaction {hash}: {e}"))
+                })?;
+
+            insert_attributes(storage, &hash, row.get(2)?, &attributes)?;
+            start = id + 1;
+        }
+    }
+}
+
+fn insert_attributes(
+    storage: &dyn Storage,
+    txid: &str,
+    block_index: u64,
+    attributes: &[TransactionAttribute],
+) -> std::result::Result<(), StorageError> {
+    for (position, attribute) in attributes.iter().enumerate() {
+        storage.execute(
+            "INSERT INTO attributes (
+            txid, block_index, position, attribute_type, value
+        ) VALUES ($1, $2, $3, $4, $5)",
+            &[
+                txid.into(),
+                block_index.into(),
+                position.into(),
+                attribute.type_name().into(),
+                attribute.key().into(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+// One row per transaction attribute, with the value it is looked up by: the request id of an
+// oracle response, the hash a transaction conflicts with, or the NotValidBefore height.
+// The full attributes stay in `transactions.attributes`.
+pub struct AttributeProcessor;
+
+impl Processor for AttributeProcessor {
+    fn name(&self) -> &'static str {
+        "attributes"
+    }
+
+    fn migrations(&self) -> &'static [Migration] {
+        MIGRATIONS
+    }
+
+    fn process_transaction(&self, storage: &dyn Storage, transaction: &Transaction) -> Result<()> {
+        let attributes: Vec<TransactionAttribute> = serde_json::from_str(&transaction.attributes)
+            .with_context(|| {
+            format!("Invalid attributes in transaction {}", transaction.hash)
+        })?;
+
+        insert_attributes(
+            storage,
+            &transaction.hash,
+            transaction.block_index,
+            &attributes,
+        )?;
+
+        Ok(())
+    }
+
+    fn delete_range(&self, storage: &dyn Storage, start: u64, end: u64) -> Result<()> {
+        storage.execute(
+            "DELETE FROM attributes WHERE block_index >= $1 AND block_index < $2",
+            &[start.into(), end.into()],
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::database::Database;
+    use crate::db::model::{Block, BlockBatch, Transaction};
+    use crate::rpc::models::TransactionAttribute;
+    use lib::db::sqlite::SqliteStorage;
+    use lib::integer::BigInt;
+    use serde_json::{json, Value};
+
+    const CONFLICT: &str = "0x7f8cbb5ca3dc5fd6eabfd6cc1a1a7ba7b4ec8dbb0dd56e6dafe05b4a38f5b0b9";
+
+    fn attributes() -> Value {
+        json!([
+            { "type": "HighPriority" },
+            { "type": "OracleResponse", "id": 7, "code": "Success", "result": "bmVv" },
+            { "type": "NotValidBefore", "height": 1200 },
+            { "type": "Conflicts", "hash": CONFLICT },
+            { "type": "NotaryAssisted", "nkeys": 3 }
+        ])
+    }
+
+    #[test]
+    fn test_attributes_round_trip() {
+        let parsed: Vec<TransactionAttribute> = serde_json::from_value(attributes()).unwrap();
+
+        assert_eq!(parsed[0], TransactionAttribute::HighPriority);
+        assert_eq!(
+            parsed[1],
+            TransactionAttribute::OracleResponse {
+                id: 7,
+                code: String::from("Success"),
+                result: String::from("bmVv"),
+            }
+        );
+        assert_eq!(parsed[1].key().as_deref(), Some("7"));
+        assert_eq!(parsed[3].key().as_deref(), Some(CONFLICT));
+        assert_eq!(parsed[4].type_name(), "NotaryAssisted");
+        assert_eq!(parsed[4].key(), None);
+
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), attributes());
+    }
+
+    fn block(index: u64) -> Block {
+        Block {
+            index,
+            hash: format!("0xb{index}"),
+            size: 114,
+            version: 0,
+            merkle_root: String::from("0x00"),
+            time: 1468595301000,
+            nonce: String::from("00"),
+            speaker: 0,
+            next_consensus: String::from("N"),
+            reward: BigInt::from(50_000_000),
+            reward_receiver: String::from("N"),
+            witnesses: String::from("[]"),
+        }
+    }
+
+    fn transaction(hash: &str, block_index: u64, attributes: Value) -> Transaction {
+        Transaction {
+            hash: hash.to_string(),
+            block_index,
+            vm_state: String::from("HALT"),
+            size: 250,
+            version: 0,
+            nonce: 0,
+            sender: String::from("N"),
+            sysfee: String::from("0"),
+            netfee: String::from("0"),
+            valid_until: 5760,
+            signers: String::from("[]"),
+            script: String::new(),
+            witnesses: String::from("[]"),
+            stack_result: String::from("[]"),
+            notifications: String::from("[]"),
+            block_time: 1468595301000,
+            exception: None,
+            gas_consumed: String::from("0"),
+            attributes: attributes.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_attributes_are_stored_with_their_key() {
+        let db = Database::from_storage(Box::new(SqliteStorage::open_in_memory().unwrap()));
+        db.migrate().unwrap();
+
+        let batch = BlockBatch {
+            blocks: vec![block(0), block(1)],
+            transactions: vec![
+                transaction("0xt1", 1, attributes()),
+                transaction("0xt2", 1, json!([])),
+            ],
+            ..Default::default()
+        };
+        db.insert_batch(&batch).unwrap();
+
+        let rows: Vec<(String, u64, String, Option<String>)> = db.blocking(|storage| {
+            storage
+                .query(
+                    "SELECT txid, position, attribute_type, value FROM attributes ORDER BY id",
+                    &[],
+                )
+                .unwrap()
+                .iter()
+                .map(|row| {
+                    (
+                        row.get(0).unwrap(),
+                        row.get(1).unwrap(),
+                        row.get(2).unwrap(),
+                        row.get(3).unwrap(),
+                    )
+                })
+                .collect()
+        });
+
+        assert_eq!(rows.len(), 5);
+        assert_eq!(
+            rows[0],
+            (String::from("0xt1"), 0, String::from("HighPriority"), None)
+        );
+        assert_eq!(rows[1].3.as_deref(), Some("7"));
+        assert_eq!(rows[2].3.as_deref(), Some("1200"));
+        assert_eq!(
+            (rows[3].1, rows[3].2.as_str(), rows[3].3.as_deref()),
+            (3, "Conflicts", Some(CONFLICT))
+        );
+
+        // the attributes are kept as they came from the node
+        let stored: String = db.blocking(|storage| {
+            storage
+                .query_row(
+                    "SELECT attributes FROM transactions WHERE hash = '0xt1'",
+                    &[],
+                )
+                .unwrap()
+                .unwrap()
+                .get(0)
+                .unwrap()
+        });
+        assert_eq!(
+            serde_json::from_str::<Value>(&stored).unwrap(),
+            attributes()
+        );
+    }
+
+    #[test]
+    fn test_attributes_backfill() {
+        let db = Database::from_storage(Box::new(SqliteStorage::open_in_memory().unwrap()));
+        db.migrate().unwrap();
+
+        let batch = BlockBatch {
+            blocks: vec![block(0), block(1)],
+            transactions: vec![
+                transaction("0xt1", 1, attributes()),
+                transaction(
+                    "0xt2",
+                    1,
+                    json!([{ "type": "NotValidBefore", "height": 5 }]),
+                ),
+                transaction("0xt3", 1, json!([{ "type": "HighPriority" }])),
+            ],
+            ..Default::default()
+        };
+        db.insert_batch(&batch).unwrap();
+
+        // as if 0xt2 had been stored before the table existed, and 0xt3 before the column did
+        db.blocking(|storage| {
+            storage
+                .execute("DELETE FROM attributes WHERE txid IN ('0xt2', '0xt3')", &[])
+                .unwrap();
+            storage
+                .execute(
+                    "UPDATE transactions SET attributes = NULL WHERE hash = '0xt3'",
+                    &[],
+                )
+                .unwrap();
+            storage
+                .execute(
+                    "UPDATE processor_versions SET version = 1 WHERE name = 'attributes'",
+                    &[],
+                )
+                .unwrap();
+        });
+        db.migrate().unwrap();
+
+        let rows: Vec<(String, u64)> = db.blocking(|storage| {
+            storage
+                .query(
+                    "SELECT txid, COUNT(*) FROM attributes GROUP BY txid ORDER BY txid",
+                    &[],
+                )
+                .unwrap()
+                .iter()
+                .map(|row| (row.get(0).unwrap(), row.get(1).unwrap()))
+                .collect()
+        });
+        assert_eq!(
+            rows,
+            vec![(String::from("0xt1"), 5), (String::from("0xt2"), 1)]
+        );
+    }
+}
@@ -0,0 +1,171 @@
+use anyhow::Result;
+use lib::db::{Storage, StorageError};
+use lib::network::CONTRACT_MANAGEMENT;
+use lib::stack_item::StackItem;
+
+use crate::db::migrations::{execute_ddl, Migration};
+use crate::utils::conversion;
+
+use super::{Notification, Processor};
+
+static MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "create contract states",
+    up: create_contract_states,
+}];
+
+// A row per Deploy, Update and Destroy, with the NEF and manifest from the script when it
+// pushed them. `contract_id` and `update_counter` are ContractManagement's, as of that change.
+fn create_contract_states(storage: &dyn Storage) -> std::result::Result<(), StorageError> {
+    execute_ddl(
+        storage,
+        "CREATE TABLE IF NOT EXISTS contract_states (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            hash                TEXT NOT NULL,
+            txid                TEXT NOT NULL,
+            block_index         INTEGER NOT NULL,
+            action              TEXT NOT NULL,
+            contract_id         INTEGER NOT NULL,
+            update_counter      INTEGER NOT NULL,
+            nef                 TEXT,
+            manifest            TEXT,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_contract_state_hash ON contract_states (hash, block_index);
+        CREATE INDEX IF NOT EXISTS idx_contract_state_block_index ON contract_states (block_index);",
+    )
+}
+
+// contracts deployed through ContractManagement, with their supported standards,
+// and every change to their state
+pub struct ContractProcessor;
+
+impl Processor for ContractProcessor {
+    fn name(&self) -> &'static str {
+        "contracts"
+    }
+
+    fn migrations(&self) -> &'static [Migration] {
+        MIGRATIONS
+    }
+
+    fn process_notification(
+        &self,
+        storage: &dyn Storage,
+        notification: &Notification,
+    ) -> Result<()> {
+        if notification.contract() == CONTRACT_MANAGEMENT
+            && notification.transaction.vm_state == "HALT"
+            && matches!(notification.event_name(), "Deploy" | "Update" | "Destroy")
+        {
+            store_state(storage, notification)?;
+        }
+
+        let transaction = notification.transaction;
+        let Some(contract) = conversion::convert_deploy_notification(
+            &transaction.script,
+            notification.event,
+            transaction.block_index,
+        ) else {
+            return Ok(());
+        };
+
+        storage.execute(
+            "INSERT INTO contracts (
+            block_index, hash, contract_type
+        ) VALUES ($1, $2, $3)",
+            &[
+                contract.block_index.into(),
+                contract.hash.into(),
+                contract.contract_type.into(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn delete_range(&self, storage: &dyn Storage, start: u64, end: u64) -> Result<()> {
+        for table in ["contracts", "contract_states"] {
+            storage.execute(
+                &format!("DELETE FROM {table} WHERE block_index >= $1 AND block_index < $2"),
+                &[start.into(), end.into()],
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn store_state(storage: &dyn Storage, notification: &Notification) -> Result<()> {
+    let Some(hash) = notification
+        .state()
+        .as_array()
+        .and_then(|items| items.first())
+        .and_then(StackItem::as_script_hash)
+    else {
+        return Ok(());
+    };
+    let transaction = notification.transaction;
+
+    let (contract_id, update_counter) = match notification.event_name() {
+        // ids are handed out from 1 in deployment order. Rows of later blocks are left out
+        // here and below, so a reindexed range gets the same ids and counters.
+        "Deploy" => {
+            let deployed: u64 = storage
+                .query_row(
+                    "SELECT COUNT(*) FROM contract_states
+                    WHERE action = 'Deploy' AND block_index <= $1",
+                    &[transaction.block_index.into()],
+                )?
+                .map(|row| row.get(0))
+                .transpose()?
+                .unwrap_or_default();
+
+            (deployed + 1, 0)
+        }
+        action => {
+            let previous = storage.query_row(
+                "SELECT contract_id, update_counter FROM contract_states
+                WHERE hash = $1 AND block_index <= $2 ORDER BY block_index DESC, id DESC LIMIT 1",
+                &[hash.as_str().into(), transaction.block_index.into()],
+            )?;
+            // deployed before the indexed range
+            let Some(previous) = previous else {
+                return Ok(());
+            };
+            let (contract_id, update_counter): (u64, u64) = (previous.get(0)?, previous.get(1)?);
+
+            match action {
+                "Update" => (contract_id, update_counter + 1),
+                _ => (contract_id, update_counter),
+            }
+        }
+    };
+
+    let (nef, manifest) = match notification.event_name() {
+        "Destroy" => (None, None),
+        _ => (
+            conversion::deploy_nef(&transaction.script).map(|nef| nef.to_json().to_string()),
+            conversion::deploy_manifest(&transaction.script).map(|manifest| manifest.to_string()),
+        ),
+    };
+
+    storage.execute(
+        "INSERT INTO contract_states (
+            hash, txid, block_index, action, contract_id, update_counter, nef, manifest
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        &[
+            hash.into(),
+            transaction.hash.as_str().into(),
+            transaction.block_index.into(),
+            notification.event_name().into(),
+            contract_id.into(),
+            update_counter.into(),
+            nef.into(),
+            manifest.into(),
+        ],
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,214 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::database::Database;
+    use crate::db::model::{Block, BlockBatch, Transaction};
+    use lib::binary::Serializable;
+    use lib::db::sqlite::SqliteStorage;
+    use lib::integer::BigInt;
+    use lib::nef::NefFile;
+    use lib::payloads::hash160_from_hex;
+    use lib::script::{CallFlags, ScriptBuilder};
+    use lib::stack_item::StackItem;
+    use serde_json::{json, Value};
+
+    const CONTRACT_MANAGEMENT: &str = "0xfffdc93764dbaddd97c48f252a53ea4643faa3fd";
+    const FIRST: &str = "0x0102030405060708090a0b0c0d0e0f1011121314";
+    const SECOND: &str = "0x1112131415161718191a1b1c1d1e1f2021222324";
+    const FAULTED: &str = "0x2122232425262728292a2b2c2d2e2f3031323334";
+
+    fn nef(script: &[u8]) -> NefFile {
+        let mut nef = NefFile {
+            compiler: String::from("neo-core-v3.0"),
+            source: String::new(),
+            tokens: Vec::new(),
+            script: script.to_vec(),
+            checksum: 0,
+        };
+        nef.checksum = nef.compute_checksum();
+        nef
+    }
+
+    // ContractManagement.method(nef, manifest) as a wallet builds it
+    fn management_script(method: &str, nef: &NefFile, name: &str) -> String {
+        let manifest =
+            json!({ "name": name, "supportedstandards": ["NEP-17"], "abi": { "events": [] } });
+
+        let mut builder = ScriptBuilder::new();
+        builder
+            .contract_call(
+                &hash160_from_hex(CONTRACT_MANAGEMENT).unwrap(),
+                method,
+                CallFlags::All,
+                &[
+                    StackItem::ByteString(nef.to_bytes()),
+                    StackItem::ByteString(manifest.to_string().into_bytes()),
+                ],
+            )
+            .unwrap();
+
+        hex::encode(builder.into_bytes())
+    }
+
+    fn event(name: &str, contract: &str) -> Value {
+        let mut bytes = hex::decode(contract.trim_start_matches("0x")).unwrap();
+        bytes.reverse();
+
+        json!([{
+            "contract": CONTRACT_MANAGEMENT,
+            "eventname": name,
+            "state": { "type": "Array", "value": [StackItem::ByteString(bytes).to_json()] }
+        }])
+    }
+
+    fn block(index: u64) -> Block {
+        Block {
+            index,
+            hash: format!("0xb{index}"),
+            size: 114,
+            version: 0,
+            merkle_root: String::from("0x00"),
+            time: 1468595301000 + index * 15000,
+            nonce: String::from("00"),
+            speaker: 0,
+            next_consensus: String::from("N"),
+            reward: BigInt::from(50_000_000),
+            reward_receiver: String::from("N"),
+            witnesses: String::from("[]"),
+        }
+    }
+
+    fn transaction(
+        hash: &str,
+        block_index: u64,
+        script: String,
+        notifications: Value,
+    ) -> Transaction {
+        Transaction {
+            hash: hash.to_string(),
+            block_index,
+            vm_state: String::from("HALT"),
+            size: 250,
+            version: 0,
+            nonce: 0,
+            sender: String::from("N"),
+            sysfee: String::from("0"),
+            netfee: String::from("0"),
+            valid_until: 5760,
+            signers: String::from("[]"),
+            script,
+            witnesses: String::from("[]"),
+            stack_result: String::from("[]"),
+            notifications: notifications.to_string(),
+            block_time: 1468595301000 + block_index * 15000,
+            exception: None,
+            gas_consumed: String::from("0"),
+            attributes: String::from("[]"),
+        }
+    }
+
+    // action, id, update counter and whether the NEF and manifest were found, oldest first
+    fn states(db: &Database, hash: &str) -> Vec<(String, u64, u64, bool, bool)> {
+        let hash = hash.to_string();
+        db.blocking(move |storage| {
+            storage
+                .query(
+                    "SELECT action, contract_id, update_counter, nef, manifest FROM contract_states
+                    WHERE hash = $1 ORDER BY block_index, id",
+                    &[hash.into()],
+                )
+                .unwrap()
+                .iter()
+                .map(|row| {
+                    (
+                        row.get(0).unwrap(),
+                        row.get(1).unwrap(),
+                        row.get(2).unwrap(),
+                        row.get::<Option<String>>(3).unwrap().is_some(),
+                        row.get::<Option<String>>(4).unwrap().is_some(),
+                    )
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_contract_states() {
+        let db = Database::from_storage(Box::new(SqliteStorage::open_in_memory().unwrap()));
+        db.migrate().unwrap();
+
+        let first_nef = nef(&[0x11, 0x40]);
+        let mut faulted = transaction(
+            "0xt0",
+            1,
+            management_script("deploy", &nef(&[0x40]), "Faulted"),
+            event("Deploy", FAULTED),
+        );
+        faulted.vm_state = String::from("FAULT");
+
+        let transactions = vec![
+            faulted,
+            transaction(
+                "0xt1",
+                1,
+                management_script("deploy", &first_nef, "First"),
+                event("Deploy", FIRST),
+            ),
+            transaction(
+                "0xt2",
+                2,
+                management_script("deploy", &nef(&[0x12, 0x40]), "Second"),
+                event("Deploy", SECOND),
+            ),
+            transaction(
+                "0xt3",
+                3,
+                management_script("update", &nef(&[0x13, 0x40]), "First"),
+                event("Update", FIRST),
+            ),
+            transaction("0xt4", 4, String::new(), event("Destroy", FIRST)),
+        ];
+        db.insert_batch(&BlockBatch {
+            blocks: (1..5).map(block).collect(),
+            transactions,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            states(&db, FIRST),
+            vec![
+                (String::from("Deploy"), 1, 0, true, true),
+                (String::from("Update"), 1, 1, true, true),
+                (String::from("Destroy"), 1, 1, false, false),
+            ]
+        );
+        // the faulted deploy took no id
+        assert!(states(&db, FAULTED).is_empty());
+        assert_eq!(
+            states(&db, SECOND),
+            vec![(String::from("Deploy"), 2, 0, true, true)]
+        );
+
+        let stored_nef: String = db.blocking(|storage| {
+            storage
+                .query_row("SELECT nef FROM contract_states WHERE txid = '0xt1'", &[])
+                .unwrap()
+                .unwrap()
+                .get(0)
+                .unwrap()
+        });
+        assert_eq!(
+            serde_json::from_str::<Value>(&stored_nef).unwrap(),
+            first_nef.to_json()
+        );
+
+        // reindexing the middle of the range keeps the ids
+        db.reprocess(2, 4, &[String::from("contracts")]).unwrap();
+        assert_eq!(
+            states(&db, SECOND),
+            vec![(String::from("Deploy"), 2, 0, true, true)]
+        );
+        assert_eq!(states(&db, FIRST).len(), 3);
+        assert_eq!(states(&db, FIRST)[1].2, 1);
+    }
+}
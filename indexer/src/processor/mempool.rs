@@ -0,0 +1,141 @@
+use anyhow::Result;
+use lib::db::{Storage, StorageError};
+
+use crate::db::migrations::{execute_ddl, Migration};
+use crate::db::model::Transaction;
+use crate::rpc::models::TransactionResult;
+
+use super::Processor;
+
+static MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "create mempool",
+    up: create_table,
+}];
+
+fn create_table(storage: &dyn Storage) -> std::result::Result<(), StorageError> {
+    execute_ddl(
+        storage,
+        "CREATE TABLE IF NOT EXISTS mempool (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            hash                TEXT NOT NULL UNIQUE,
+            sender              TEXT NOT NULL,
+            sysfee              TEXT NOT NULL,
+            netfee              TEXT NOT NULL,
+            valid_until         INTEGER NOT NULL,
+            first_seen          INTEGER NOT NULL,
+            status              TEXT NOT NULL,
+            block_index         INTEGER,
+            latency             INTEGER,
+            expired_at          INTEGER
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_mempool_sender ON mempool (sender, first_seen);
+        CREATE INDEX IF NOT EXISTS idx_mempool_pending ON mempool (valid_until) WHERE status = 'pending';
+        CREATE INDEX IF NOT EXISTS idx_mempool_block_index ON mempool (block_index);",
+    )
+}
+
+// a transaction seen in the node's mempool, `first_seen` being a unix time in milliseconds
+#[derive(Debug, Clone)]
+pub struct PendingTransaction {
+    pub hash: String,
+    pub sender: String,
+    pub sysfee: String,
+    pub netfee: String,
+    pub valid_until: u64,
+    pub first_seen: u64,
+}
+
+impl PendingTransaction {
+    pub fn new(transaction: TransactionResult, first_seen: u64) -> Self {
+        PendingTransaction {
+            hash: transaction.hash,
+            sender: transaction.sender,
+            sysfee: transaction.sysfee,
+            netfee: transaction.netfee,
+            valid_until: transaction.validuntilblock,
+            first_seen,
+        }
+    }
+}
+
+// Rows are added by the mempool observer while it runs (`--mempool`). The processor marks them
+// confirmed when their transaction is indexed, with the time from first seen to the block.
+pub struct MempoolProcessor;
+
+impl Processor for MempoolProcessor {
+    fn name(&self) -> &'static str {
+        "mempool"
+    }
+
+    fn migrations(&self) -> &'static [Migration] {
+        MIGRATIONS
+    }
+
+    fn process_transaction(&self, storage: &dyn Storage, transaction: &Transaction) -> Result<()> {
+        // seen late, e.g. after a restart, counts as no wait at all
+        storage.execute(
+            "UPDATE mempool SET status = 'confirmed', block_index = $2, expired_at = NULL,
+            latency = CASE WHEN $3 > first_seen THEN $3 - first_seen ELSE 0 END
+            WHERE hash = $1",
+            &[
+                transaction.hash.as_str().into(),
+                transaction.block_index.into(),
+                transaction.block_time.into(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    // back to pending, the observer expires them again if they are not confirmed anew
+    fn delete_range(&self, storage: &dyn Storage, start: u64, end: u64) -> Result<()> {
+        storage.execute(
+            "UPDATE mempool SET status = 'pending', block_index = NULL, latency = NULL
+            WHERE block_index >= $1 AND block_index < $2",
+            &[start.into(), end.into()],
+        )?;
+
+        Ok(())
+    }
+}
+
+// keeps the first sighting of transactions that were already recorded
+pub fn insert_pending(
+    storage: &dyn Storage,
+    transactions: &[PendingTransaction],
+) -> std::result::Result<(), StorageError> {
+    for transaction in transactions {
+        storage.execute(
+            "INSERT INTO mempool (
+            hash, sender, sysfee, netfee, valid_until, first_seen, status
+        ) VALUES ($1, $2, $3, $4, $5, $6, 'pending')
+        ON CONFLICT (hash) DO NOTHING",
+            &[
+                transaction.hash.as_str().into(),
+                transaction.sender.as_str().into(),
+                transaction.sysfee.as_str().into(),
+                transaction.netfee.as_str().into(),
+                transaction.valid_until.into(),
+                transaction.first_seen.into(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+// A transaction can't be included once the chain is at its `valid_until` height. `height` is
+// the last indexed block, so confirmations the Indexer hasn't written yet aren't mistaken for expiry.
+pub fn expire_pending(
+    storage: &dyn Storage,
+    height: u64,
+    now: u64,
+) -> std::result::Result<u64, StorageError> {
+    storage.execute(
+        "UPDATE mempool SET status = 'expired', expired_at = $2
+        WHERE status = 'pending' AND valid_until <= $1",
+        &[height.into(), now.into()],
+    )
+}
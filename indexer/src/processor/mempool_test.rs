@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::database::Database;
+    use crate::db::model::{Block, BlockBatch, Transaction};
+    use crate::processor::mempool::{self, PendingTransaction};
+    use lib::db::sqlite::SqliteStorage;
+    use lib::integer::BigInt;
+
+    const BLOCK_TIME: u64 = 1468595301000;
+
+    fn block(index: u64) -> Block {
+        Block {
+            index,
+            hash: format!("0xb{index}"),
+            size: 114,
+            version: 0,
+            merkle_root: String::from("0x00"),
+            time: BLOCK_TIME,
+            nonce: String::from("00"),
+            speaker: 0,
+            next_consensus: String::from("N"),
+            reward: BigInt::from(50_000_000),
+            reward_receiver: String::from("N"),
+            witnesses: String::from("[]"),
+        }
+    }
+
+    fn transaction(hash: &str, block_index: u64) -> Transaction {
+        Transaction {
+            hash: hash.to_string(),
+            block_index,
+            vm_state: String::from("HALT"),
+            size: 250,
+            version: 0,
+            nonce: 0,
+            sender: String::from("N"),
+            sysfee: String::from("0"),
+            netfee: String::from("0"),
+            valid_until: 5760,
+            signers: String::from("[]"),
+            script: String::new(),
+            witnesses: String::from("[]"),
+            stack_result: String::from("[]"),
+            notifications: String::from("[]"),
+            block_time: BLOCK_TIME,
+            exception: None,
+            gas_consumed: String::from("0"),
+            attributes: String::from("[]"),
+        }
+    }
+
+    fn pending(hash: &str, valid_until: u64, first_seen: u64) -> PendingTransaction {
+        PendingTransaction {
+            hash: hash.to_string(),
+            sender: String::from("N"),
+            sysfee: String::from("0"),
+            netfee: String::from("0"),
+            valid_until,
+            first_seen,
+        }
+    }
+
+    type Row = (String, Option<u64>, Option<u64>, Option<u64>);
+
+    fn rows(db: &Database) -> Vec<Row> {
+        db.blocking(|storage| {
+            storage
+                .query(
+                    "SELECT status, block_index, latency, expired_at FROM mempool ORDER BY id",
+                    &[],
+                )
+                .unwrap()
+                .iter()
+                .map(|row| {
+                    (
+                        row.get(0).unwrap(),
+                        row.get(1).unwrap(),
+                        row.get(2).unwrap(),
+                        row.get(3).unwrap(),
+                    )
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_pending_transactions_are_confirmed_or_expired() {
+        let db = Database::from_storage(Box::new(SqliteStorage::open_in_memory().unwrap()));
+        db.migrate().unwrap();
+
+        db.blocking(|storage| {
+            mempool::insert_pending(
+                storage,
+                &[
+                    pending("0xt1", 5760, BLOCK_TIME - 15000),
+                    pending("0xt2", 1, BLOCK_TIME),
+                ],
+            )
+        })
+        .unwrap();
+
+        // seen again on a later poll, the first sighting stays
+        db.blocking(|storage| {
+            mempool::insert_pending(storage, &[pending("0xt1", 5760, BLOCK_TIME)])
+        })
+        .unwrap();
+
+        let batch = BlockBatch {
+            blocks: vec![block(0), block(1)],
+            transactions: vec![transaction("0xt1", 1)],
+            ..Default::default()
+        };
+        db.insert_batch(&batch).unwrap();
+
+        let expired = db
+            .blocking(|storage| mempool::expire_pending(storage, 1, BLOCK_TIME + 1000))
+            .unwrap();
+        assert_eq!(expired, 1);
+
+        assert_eq!(
+            rows(&db),
+            vec![
+                (String::from("confirmed"), Some(1), Some(15000), None),
+                (String::from("expired"), None, None, Some(BLOCK_TIME + 1000)),
+            ]
+        );
+
+        // reprocessing the block confirms it again
+        db.reprocess(1, 2, &[String::from("mempool")]).unwrap();
+        assert_eq!(
+            rows(&db)[0],
+            (String::from("confirmed"), Some(1), Some(15000), None)
+        );
+    }
+}
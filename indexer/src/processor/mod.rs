@@ -0,0 +1,150 @@
+use anyhow::{Context, Result};
+use lib::db::Storage;
+use lib::stack_item::StackItem;
+
+use std::time::Instant;
+
+use crate::db::migrations::Migration;
+use crate::db::model::{Block, Transaction};
+use crate::rpc::models;
+use crate::utils::metrics::METRICS;
+
+mod addresses;
+mod attributes;
+mod attributes_test;
+mod contracts;
+mod contracts_test;
+pub mod mempool;
+mod mempool_test;
+mod nns;
+mod nns_test;
+mod notifications;
+mod notifications_test;
+mod oracles;
+mod oracles_test;
+mod processor_test;
+mod signers;
+mod signers_test;
+
+pub use addresses::AddressProcessor;
+pub use attributes::AttributeProcessor;
+pub use contracts::ContractProcessor;
+pub use mempool::MempoolProcessor;
+pub use nns::NnsProcessor;
+pub use notifications::NotificationProcessor;
+pub use oracles::OracleProcessor;
+pub use signers::SignerProcessor;
+
+// A notification emitted by a transaction, `index` being its position in the application log
+pub struct Notification<'a> {
+    pub transaction: &'a Transaction,
+    pub index: usize,
+    pub event: &'a models::Notification,
+}
+
+impl Notification<'_> {
+    pub fn contract(&self) -> &str {
+        &self.event.contract
+    }
+
+    pub fn event_name(&self) -> &str {
+        &self.event.eventname
+    }
+
+    pub fn state(&self) -> &StackItem {
+        &self.event.state
+    }
+}
+
+// Processors see every block, transaction and notification written, inside the database
+// transaction of the batch, so their rows are committed or rolled back together with it.
+// Register them with `Database::register` before migrating.
+pub trait Processor: Send + Sync {
+    // unique, used to track the processor's migrations and to pick it in `reindex --tables`
+    fn name(&self) -> &'static str;
+
+    // applied in order after the Indexer's own migrations
+    fn migrations(&self) -> &'static [Migration] {
+        &[]
+    }
+
+    fn process_block(&self, _storage: &dyn Storage, _block: &Block) -> Result<()> {
+        Ok(())
+    }
+
+    fn process_transaction(
+        &self,
+        _storage: &dyn Storage,
+        _transaction: &Transaction,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    fn process_notification(
+        &self,
+        _storage: &dyn Storage,
+        _notification: &Notification,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    // removes everything written for blocks [start, end), so the range can be processed again
+    fn delete_range(&self, storage: &dyn Storage, start: u64, end: u64) -> Result<()>;
+}
+
+pub fn builtin() -> Vec<Box<dyn Processor>> {
+    vec![
+        Box::new(ContractProcessor),
+        Box::new(AddressProcessor),
+        Box::new(NotificationProcessor),
+        Box::new(SignerProcessor),
+        Box::new(AttributeProcessor),
+        Box::new(OracleProcessor),
+        Box::new(NnsProcessor),
+        Box::new(MempoolProcessor),
+    ]
+}
+
+// runs each processor over blocks and transactions that were just written
+pub fn process(
+    processors: &[&dyn Processor],
+    storage: &dyn Storage,
+    blocks: &[Block],
+    transactions: &[Transaction],
+) -> Result<()> {
+    // parsed once for all processors
+    let notifications = transactions
+        .iter()
+        .map(|transaction| {
+            serde_json::from_str::<Vec<models::Notification>>(&transaction.notifications)
+                .with_context(|| {
+                    format!("Invalid notifications in transaction {}", transaction.hash)
+                })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    for processor in processors {
+        let started = Instant::now();
+
+        for block in blocks {
+            processor.process_block(storage, block)?;
+        }
+
+        for (transaction, notifications) in transactions.iter().zip(&notifications) {
+            processor.process_transaction(storage, transaction)?;
+
+            for (index, event) in notifications.iter().enumerate() {
+                let notification = Notification {
+                    transaction,
+                    index,
+                    event,
+                };
+                processor.process_notification(storage, &notification)?;
+            }
+        }
+
+        METRICS.observe_write(processor.name(), started);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,424 @@
+use anyhow::{Context, Result};
+use lib::db::{Storage, StorageError};
+use lib::neo::{self, nns_domain};
+use lib::stack_item::StackItem;
+
+use crate::db::migrations::{execute_ddl, Migration};
+use crate::db::model::Transaction;
+use crate::rpc::models;
+use crate::utils::conversion;
+
+use super::Processor;
+
+// NameService, the same hash on MainNet and TestNet
+const NNS_CONTRACT: &str = "0x50ac1c37690cc2cfc594472833cf57505d5f46de";
+const ONE_YEAR: u64 = 365 * 24 * 3600 * 1000;
+
+static MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "create NNS domains and records",
+    up: create_tables,
+}];
+
+fn create_tables(storage: &dyn Storage) -> std::result::Result<(), StorageError> {
+    execute_ddl(
+        storage,
+        "CREATE TABLE IF NOT EXISTS nns_events (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            txid                TEXT NOT NULL,
+            block_index         INTEGER NOT NULL,
+            domain              TEXT NOT NULL,
+            name                TEXT NOT NULL,
+            action              TEXT NOT NULL,
+            owner               TEXT,
+            expiration          INTEGER,
+            record_type         INTEGER,
+            data                TEXT,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE TABLE IF NOT EXISTS nns_domains (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            name                TEXT NOT NULL UNIQUE,
+            owner               TEXT NOT NULL,
+            expiration          INTEGER NOT NULL,
+            block_index         INTEGER NOT NULL
+        );
+
+        CREATE TABLE IF NOT EXISTS nns_records (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            domain              TEXT NOT NULL,
+            name                TEXT NOT NULL,
+            record_type         INTEGER NOT NULL,
+            data                TEXT NOT NULL,
+            block_index         INTEGER NOT NULL
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_nns_event_domain ON nns_events (domain, block_index);
+        CREATE INDEX IF NOT EXISTS idx_nns_event_block_index ON nns_events (block_index);
+        CREATE INDEX IF NOT EXISTS idx_nns_domain_owner ON nns_domains (owner);
+        CREATE UNIQUE INDEX IF NOT EXISTS idx_nns_record_name_type ON nns_records (name, record_type);
+        CREATE INDEX IF NOT EXISTS idx_nns_record_domain ON nns_records (domain);
+        CREATE INDEX IF NOT EXISTS idx_nns_record_data ON nns_records (record_type, data);",
+    )
+}
+
+// A change to a domain. Every change is logged in `nns_events`, and `nns_domains` and
+// `nns_records` hold the result of applying them in order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NnsEvent {
+    pub name: String,
+    pub action: Action,
+    pub owner: Option<String>,
+    pub expiration: Option<u64>, // the new expiration, or the time added for Extend
+    pub record_type: Option<u64>,
+    pub data: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Register,
+    Transfer,
+    Burn,
+    Renew,
+    Extend,
+    SetRecord,
+    DeleteRecord,
+}
+
+impl Action {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Action::Register => "register",
+            Action::Transfer => "transfer",
+            Action::Burn => "burn",
+            Action::Renew => "renew",
+            Action::Extend => "extend",
+            Action::SetRecord => "set_record",
+            Action::DeleteRecord => "delete_record",
+        }
+    }
+
+    fn parse(action: &str) -> Option<Action> {
+        [
+            Action::Register,
+            Action::Transfer,
+            Action::Burn,
+            Action::Renew,
+            Action::Extend,
+            Action::SetRecord,
+            Action::DeleteRecord,
+        ]
+        .into_iter()
+        .find(|candidate| candidate.as_str() == action)
+    }
+}
+
+impl NnsEvent {
+    fn new(name: String, action: Action) -> Self {
+        NnsEvent {
+            name: name.to_lowercase(),
+            action,
+            owner: None,
+            expiration: None,
+            record_type: None,
+            data: None,
+        }
+    }
+}
+
+// NameService's ownership changes come from its NEP-11 Transfer events, and renewals from its
+// Renew events when it emits them. Records, and renewals by older versions, emit nothing, so
+// they are read from the setRecord, deleteRecord and renew calls made by transaction scripts.
+pub struct NnsProcessor;
+
+impl Processor for NnsProcessor {
+    fn name(&self) -> &'static str {
+        "nns"
+    }
+
+    fn migrations(&self) -> &'static [Migration] {
+        MIGRATIONS
+    }
+
+    fn process_transaction(&self, storage: &dyn Storage, transaction: &Transaction) -> Result<()> {
+        if transaction.vm_state != "HALT" {
+            return Ok(());
+        }
+
+        for event in nns_events(transaction)? {
+            let domain = nns_domain(&event.name);
+
+            storage.execute(
+                "INSERT INTO nns_events (
+                txid, block_index, domain, name, action, owner, expiration, record_type, data
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)",
+                &[
+                    transaction.hash.as_str().into(),
+                    transaction.block_index.into(),
+                    domain.as_str().into(),
+                    event.name.as_str().into(),
+                    event.action.as_str().into(),
+                    event.owner.as_deref().into(),
+                    event.expiration.into(),
+                    event.record_type.into(),
+                    event.data.as_deref().into(),
+                ],
+            )?;
+
+            // blocks are being processed again after later ones, so the domain is replayed
+            let later = storage.query_row(
+                "SELECT 1 FROM nns_events WHERE domain = $1 AND block_index > $2 LIMIT 1",
+                &[domain.as_str().into(), transaction.block_index.into()],
+            )?;
+            match later {
+                Some(_) => rebuild(storage, &domain)?,
+                None => apply(storage, &event, transaction.block_index)?,
+            }
+        }
+
+        Ok(())
+    }
+
+    fn delete_range(&self, storage: &dyn Storage, start: u64, end: u64) -> Result<()> {
+        let domains = storage
+            .query(
+                "SELECT DISTINCT domain FROM nns_events WHERE block_index >= $1 AND block_index < $2",
+                &[start.into(), end.into()],
+            )?
+            .iter()
+            .map(|row| row.get::<String>(0))
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+
+        storage.execute(
+            "DELETE FROM nns_events WHERE block_index >= $1 AND block_index < $2",
+            &[start.into(), end.into()],
+        )?;
+
+        for domain in domains {
+            rebuild(storage, &domain)?;
+        }
+
+        Ok(())
+    }
+}
+
+// the changes a transaction made, in the order they were made
+pub fn nns_events(transaction: &Transaction) -> Result<Vec<NnsEvent>> {
+    let mut events = Vec::new();
+
+    if transaction.notifications.contains(NNS_CONTRACT) {
+        let notifications: Vec<models::Notification> =
+            serde_json::from_str(&transaction.notifications).with_context(|| {
+                format!("Invalid notifications in transaction {}", transaction.hash)
+            })?;
+
+        for notification in notifications
+            .iter()
+            .filter(|notification| notification.contract == NNS_CONTRACT)
+        {
+            let state = notification.state.as_array().unwrap_or_default();
+            let event = match notification.eventname.as_str() {
+                // [from, to, amount, tokenId]
+                "Transfer" => transfer_event(state, transaction.block_time),
+                // [name, oldExpiration, newExpiration]
+                "Renew" => state.first().and_then(StackItem::as_utf8).map(|name| {
+                    let mut event = NnsEvent::new(name, Action::Renew);
+                    event.expiration = state
+                        .get(2)
+                        .and_then(StackItem::as_integer)
+                        .and_then(|expiration| u64::try_from(expiration).ok());
+                    event
+                }),
+                _ => None,
+            };
+            events.extend(event);
+        }
+    }
+
+    // the contract's script hash as it appears in a script
+    let script_hash = neo::reverse_hex(NNS_CONTRACT.trim_start_matches("0x"));
+    if !transaction.script.contains(&script_hash) {
+        return Ok(events);
+    }
+
+    let renewed = events.iter().any(|event| event.action == Action::Renew);
+    for call in conversion::contract_calls(&transaction.script)
+        .into_iter()
+        .filter(|call| call.contract == NNS_CONTRACT)
+    {
+        let Some(name) = call.args.first().and_then(StackItem::as_utf8) else {
+            continue;
+        };
+        let integer = |index: usize| {
+            call.args
+                .get(index)
+                .and_then(StackItem::as_integer)
+                .and_then(|value| u64::try_from(value).ok())
+        };
+
+        let event = match call.method.as_str() {
+            "setRecord" => {
+                let mut event = NnsEvent::new(name, Action::SetRecord);
+                event.record_type = integer(1);
+                event.data = call.args.get(2).and_then(StackItem::as_utf8);
+                event
+            }
+            "deleteRecord" => {
+                let mut event = NnsEvent::new(name, Action::DeleteRecord);
+                event.record_type = integer(1);
+                event
+            }
+            "renew" if !renewed => {
+                let mut event = NnsEvent::new(name, Action::Extend);
+                event.expiration = Some(integer(1).unwrap_or(1) * ONE_YEAR);
+                event
+            }
+            _ => continue,
+        };
+        events.push(event);
+    }
+
+    Ok(events)
+}
+
+// a mint registers the domain for a year, a burn releases it
+fn transfer_event(state: &[StackItem], block_time: u64) -> Option<NnsEvent> {
+    let name = state.get(3)?.as_utf8()?;
+    let from = state.first()?;
+    let to = state.get(1)?;
+
+    let event = match (from.is_null(), to.is_null()) {
+        (true, false) => {
+            let mut event = NnsEvent::new(name, Action::Register);
+            event.owner = to.as_address();
+            event.expiration = Some(block_time + ONE_YEAR);
+            event
+        }
+        (false, true) => NnsEvent::new(name, Action::Burn),
+        (false, false) => {
+            let mut event = NnsEvent::new(name, Action::Transfer);
+            event.owner = to.as_address();
+            event
+        }
+        (true, true) => return None,
+    };
+
+    Some(event)
+}
+
+fn apply(storage: &dyn Storage, event: &NnsEvent, block_index: u64) -> Result<()> {
+    let name = event.name.as_str();
+
+    match event.action {
+        Action::Register => {
+            storage.execute(
+                "INSERT INTO nns_domains (name, owner, expiration, block_index)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (name) DO UPDATE SET
+                owner = excluded.owner, expiration = excluded.expiration,
+                block_index = excluded.block_index",
+                &[
+                    name.into(),
+                    event.owner.as_deref().unwrap_or_default().into(),
+                    event.expiration.unwrap_or_default().into(),
+                    block_index.into(),
+                ],
+            )?;
+        }
+        Action::Transfer => {
+            storage.execute(
+                "UPDATE nns_domains SET owner = $2, block_index = $3 WHERE name = $1",
+                &[
+                    name.into(),
+                    event.owner.as_deref().unwrap_or_default().into(),
+                    block_index.into(),
+                ],
+            )?;
+        }
+        Action::Burn => {
+            storage.execute("DELETE FROM nns_domains WHERE name = $1", &[name.into()])?;
+            storage.execute("DELETE FROM nns_records WHERE domain = $1", &[name.into()])?;
+        }
+        Action::Renew => {
+            storage.execute(
+                "UPDATE nns_domains SET expiration = $2, block_index = $3 WHERE name = $1",
+                &[
+                    name.into(),
+                    event.expiration.unwrap_or_default().into(),
+                    block_index.into(),
+                ],
+            )?;
+        }
+        Action::Extend => {
+            storage.execute(
+                "UPDATE nns_domains SET expiration = expiration + $2, block_index = $3
+                WHERE name = $1",
+                &[
+                    name.into(),
+                    event.expiration.unwrap_or_default().into(),
+                    block_index.into(),
+                ],
+            )?;
+        }
+        Action::SetRecord => {
+            let (Some(record_type), Some(data)) = (event.record_type, event.data.as_deref()) else {
+                return Ok(());
+            };
+            storage.execute(
+                "INSERT INTO nns_records (domain, name, record_type, data, block_index)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (name, record_type) DO UPDATE SET
+                data = excluded.data, block_index = excluded.block_index",
+                &[
+                    nns_domain(name).into(),
+                    name.into(),
+                    record_type.into(),
+                    data.into(),
+                    block_index.into(),
+                ],
+            )?;
+        }
+        Action::DeleteRecord => {
+            storage.execute(
+                "DELETE FROM nns_records WHERE name = $1 AND record_type = $2",
+                &[name.into(), event.record_type.into()],
+            )?;
+        }
+    }
+
+    Ok(())
+}
+
+// recomputes a domain and its records from its logged events
+fn rebuild(storage: &dyn Storage, domain: &str) -> Result<()> {
+    storage.execute("DELETE FROM nns_domains WHERE name = $1", &[domain.into()])?;
+    storage.execute(
+        "DELETE FROM nns_records WHERE domain = $1",
+        &[domain.into()],
+    )?;
+
+    let rows = storage.query(
+        "SELECT name, action, owner, expiration, record_type, data, block_index
+        FROM nns_events WHERE domain = $1 ORDER BY block_index, id",
+        &[domain.into()],
+    )?;
+
+    for row in rows {
+        let action: String = row.get(1)?;
+        let Some(action) = Action::parse(&action) else {
+            continue;
+        };
+        let event = NnsEvent {
+            name: row.get(0)?,
+            action,
+            owner: row.get(2)?,
+            expiration: row.get(3)?,
+            record_type: row.get(4)?,
+            data: row.get(5)?,
+        };
+        apply(storage, &event, row.get(6)?)?;
+    }
+
+    Ok(())
+}
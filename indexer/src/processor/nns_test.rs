@@ -0,0 +1,177 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::database::Database;
+    use crate::db::model::{Block, BlockBatch, Transaction};
+    use lib::db::sqlite::SqliteStorage;
+    use lib::integer::BigInt;
+    use lib::neo::nns_domain;
+    use lib::stack_item::StackItem;
+    use serde_json::{json, Value};
+
+    const NNS_CONTRACT: &str = "0x50ac1c37690cc2cfc594472833cf57505d5f46de";
+    const ONE_YEAR: u64 = 365 * 24 * 3600 * 1000;
+    // the address of the script hash 0..20
+    const ALICE: &str = "NKuzPnNtxubufwT9MvNKJ557TRPWe1jGDF";
+
+    // nns.setRecord("test.neo", 16, "NKuzPnNtxubufwT9MvNKJ557TRPWe1jGDF")
+    const SET_RECORD: &str = "0c224e4b757a506e4e7478756275667754394d764e4b4a3535375452505765316a474446200c08746573742e6e656f13c01f0c097365745265636f72640c14de465f5d5057cf33284794c5cfc20c69371cac5041627d5b52";
+
+    fn account(first: u8) -> Value {
+        StackItem::ByteString((first..first + 20).collect()).to_json()
+    }
+
+    fn transfer(from: Value, to: Value) -> Value {
+        json!([{
+            "contract": NNS_CONTRACT,
+            "eventname": "Transfer",
+            "state": {
+                "type": "Array",
+                "value": [
+                    from,
+                    to,
+                    { "type": "Integer", "value": "1" },
+                    StackItem::ByteString(b"test.neo".to_vec()).to_json()
+                ]
+            }
+        }])
+    }
+
+    fn block(index: u64) -> Block {
+        Block {
+            index,
+            hash: format!("0xb{index}"),
+            size: 114,
+            version: 0,
+            merkle_root: String::from("0x00"),
+            time: index * 1000,
+            nonce: String::from("00"),
+            speaker: 0,
+            next_consensus: String::from("N"),
+            reward: BigInt::from(50_000_000),
+            reward_receiver: String::from("N"),
+            witnesses: String::from("[]"),
+        }
+    }
+
+    fn transaction(
+        hash: &str,
+        block_index: u64,
+        script: &str,
+        notifications: Value,
+    ) -> Transaction {
+        Transaction {
+            hash: hash.to_string(),
+            block_index,
+            vm_state: String::from("HALT"),
+            size: 250,
+            version: 0,
+            nonce: 0,
+            sender: String::from("N"),
+            sysfee: String::from("0"),
+            netfee: String::from("0"),
+            valid_until: 5760,
+            signers: String::from("[]"),
+            script: script.to_string(),
+            witnesses: String::from("[]"),
+            stack_result: String::from("[]"),
+            notifications: notifications.to_string(),
+            block_time: block_index * 1000,
+            exception: None,
+            gas_consumed: String::from("0"),
+            attributes: String::from("[]"),
+        }
+    }
+
+    fn domain(db: &Database) -> Option<(String, u64)> {
+        db.blocking(|storage| {
+            storage
+                .query_row(
+                    "SELECT owner, expiration FROM nns_domains WHERE name = 'test.neo'",
+                    &[],
+                )
+                .unwrap()
+                .map(|row| (row.get(0).unwrap(), row.get(1).unwrap()))
+        })
+    }
+
+    fn records(db: &Database) -> Vec<(String, u64, String)> {
+        db.blocking(|storage| {
+            storage
+                .query(
+                    "SELECT name, record_type, data FROM nns_records ORDER BY id",
+                    &[],
+                )
+                .unwrap()
+                .iter()
+                .map(|row| {
+                    (
+                        row.get(0).unwrap(),
+                        row.get(1).unwrap(),
+                        row.get(2).unwrap(),
+                    )
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_nns_domain() {
+        assert_eq!(nns_domain("test.neo"), "test.neo");
+        assert_eq!(nns_domain("www.test.neo"), "test.neo");
+        assert_eq!(nns_domain("neo"), "neo");
+    }
+
+    #[test]
+    fn test_domains_follow_their_events() {
+        let db = Database::from_storage(Box::new(SqliteStorage::open_in_memory().unwrap()));
+        db.migrate().unwrap();
+
+        let batch = BlockBatch {
+            blocks: (0..4).map(block).collect(),
+            transactions: vec![
+                // registered by Alice
+                transaction(
+                    "0xt1",
+                    1,
+                    "",
+                    transfer(json!({ "type": "Any" }), account(0)),
+                ),
+                transaction("0xt2", 2, SET_RECORD, json!([])),
+                // then transferred
+                transaction("0xt3", 3, "", transfer(account(0), account(1))),
+            ],
+            ..Default::default()
+        };
+        db.insert_batch(&batch).unwrap();
+
+        let bob = StackItem::from_json(&account(1))
+            .unwrap()
+            .as_address()
+            .unwrap();
+        assert_eq!(domain(&db), Some((bob.clone(), 1000 + ONE_YEAR)));
+        assert_eq!(
+            records(&db),
+            vec![(String::from("test.neo"), 16, String::from(ALICE))]
+        );
+
+        // an earlier range is processed again on top of the later events
+        db.reprocess(1, 2, &[String::from("nns")]).unwrap();
+        assert_eq!(domain(&db), Some((bob, 1000 + ONE_YEAR)));
+        assert_eq!(records(&db).len(), 1);
+
+        // a burn releases the domain and its records
+        let burn = BlockBatch {
+            blocks: vec![block(4)],
+            transactions: vec![transaction(
+                "0xt4",
+                4,
+                "",
+                transfer(account(1), json!({ "type": "Any" })),
+            )],
+            ..Default::default()
+        };
+        db.insert_batch(&burn).unwrap();
+        assert_eq!(domain(&db), None);
+        assert!(records(&db).is_empty());
+    }
+}
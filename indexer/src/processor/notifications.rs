@@ -0,0 +1,303 @@
+use anyhow::Result;
+use lib::db::{Storage, StorageError};
+use lib::network::{CONTRACT_MANAGEMENT, GAS_TOKEN, NEO_TOKEN, ORACLE_CONTRACT, ROLE_MANAGEMENT};
+use lib::stack_item::StackItem;
+use once_cell::sync::Lazy;
+use serde_json::{json, Map, Value};
+
+use std::collections::HashMap;
+
+use crate::db::migrations::{execute_ddl, Migration};
+use crate::utils::conversion;
+
+use super::{Notification, Processor};
+
+// Native contracts are never deployed by a transaction, so their event ABIs are fixed here
+static NATIVE_EVENTS: Lazy<HashMap<&'static str, Value>> = Lazy::new(|| {
+    let transfer = json!({
+        "name": "Transfer",
+        "parameters": [
+            { "name": "from", "type": "Hash160" },
+            { "name": "to", "type": "Hash160" },
+            { "name": "amount", "type": "Integer" }
+        ]
+    });
+
+    HashMap::from([
+        (
+            CONTRACT_MANAGEMENT,
+            json!([
+                { "name": "Deploy", "parameters": [{ "name": "Hash", "type": "Hash160" }] },
+                { "name": "Update", "parameters": [{ "name": "Hash", "type": "Hash160" }] },
+                { "name": "Destroy", "parameters": [{ "name": "Hash", "type": "Hash160" }] }
+            ]),
+        ),
+        (
+            NEO_TOKEN,
+            json!([
+                transfer,
+                {
+                    "name": "CandidateStateChanged",
+                    "parameters": [
+                        { "name": "pubkey", "type": "PublicKey" },
+                        { "name": "registered", "type": "Boolean" },
+                        { "name": "votes", "type": "Integer" }
+                    ]
+                },
+                {
+                    "name": "Vote",
+                    "parameters": [
+                        { "name": "account", "type": "Hash160" },
+                        { "name": "from", "type": "PublicKey" },
+                        { "name": "to", "type": "PublicKey" },
+                        { "name": "amount", "type": "Integer" }
+                    ]
+                },
+                {
+                    "name": "CommitteeChanged",
+                    "parameters": [
+                        { "name": "old", "type": "Array" },
+                        { "name": "new", "type": "Array" }
+                    ]
+                }
+            ]),
+        ),
+        (GAS_TOKEN, json!([transfer])),
+        (
+            ROLE_MANAGEMENT,
+            json!([{
+                "name": "Designation",
+                "parameters": [
+                    { "name": "Role", "type": "Integer" },
+                    { "name": "BlockIndex", "type": "Integer" }
+                ]
+            }]),
+        ),
+        (
+            ORACLE_CONTRACT,
+            json!([
+                {
+                    "name": "OracleRequest",
+                    "parameters": [
+                        { "name": "Id", "type": "Integer" },
+                        { "name": "RequestContract", "type": "Hash160" },
+                        { "name": "Url", "type": "String" },
+                        { "name": "Filter", "type": "String" }
+                    ]
+                },
+                {
+                    "name": "OracleResponse",
+                    "parameters": [
+                        { "name": "Id", "type": "Integer" },
+                        { "name": "OriginalTx", "type": "Hash256" }
+                    ]
+                }
+            ]),
+        ),
+    ])
+});
+
+static MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "create notifications and contract event ABIs",
+    up: create_tables,
+}];
+
+fn create_tables(storage: &dyn Storage) -> std::result::Result<(), StorageError> {
+    execute_ddl(
+        storage,
+        "CREATE TABLE IF NOT EXISTS notifications (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            txid                TEXT NOT NULL,
+            block_index         INTEGER NOT NULL,
+            notification_index  INTEGER NOT NULL,
+            contract            TEXT NOT NULL,
+            event_name          TEXT NOT NULL,
+            state               TEXT NOT NULL,
+            parameters          TEXT,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE TABLE IF NOT EXISTS contract_events (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            contract            TEXT NOT NULL,
+            block_index         INTEGER NOT NULL,
+            events              TEXT NOT NULL,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_notification_contract_event ON notifications (contract, event_name);
+        CREATE INDEX IF NOT EXISTS idx_notification_txid ON notifications (txid);
+        CREATE INDEX IF NOT EXISTS idx_notification_block_index ON notifications (block_index);
+        CREATE INDEX IF NOT EXISTS idx_contract_events_contract ON contract_events (contract, block_index);",
+    )
+}
+
+// Every notification with its raw state, plus its parameters by name when the emitting
+// contract's manifest declares the event. Manifests are taken from deploy and update scripts
+// and kept in `contract_events`, so each notification is decoded with the ABI it was emitted under.
+pub struct NotificationProcessor;
+
+impl Processor for NotificationProcessor {
+    fn name(&self) -> &'static str {
+        "notifications"
+    }
+
+    fn migrations(&self) -> &'static [Migration] {
+        MIGRATIONS
+    }
+
+    fn process_notification(
+        &self,
+        storage: &dyn Storage,
+        notification: &Notification,
+    ) -> Result<()> {
+        let transaction = notification.transaction;
+
+        if notification.contract() == CONTRACT_MANAGEMENT
+            && matches!(notification.event_name(), "Deploy" | "Update")
+        {
+            store_events(storage, notification)?;
+        }
+
+        let parameters =
+            match contract_events(storage, notification.contract(), transaction.block_index)? {
+                Some(events) => {
+                    decode_parameters(&events, notification.event_name(), notification.state())
+                }
+                None => None,
+            };
+
+        storage.execute(
+            "INSERT INTO notifications (
+            txid, block_index, notification_index, contract, event_name, state, parameters
+        ) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            &[
+                transaction.hash.as_str().into(),
+                transaction.block_index.into(),
+                notification.index.into(),
+                notification.contract().into(),
+                notification.event_name().into(),
+                notification.state().to_json().to_string().into(),
+                parameters.map(|parameters| parameters.to_string()).into(),
+            ],
+        )?;
+
+        Ok(())
+    }
+
+    fn delete_range(&self, storage: &dyn Storage, start: u64, end: u64) -> Result<()> {
+        for table in ["notifications", "contract_events"] {
+            storage.execute(
+                &format!("DELETE FROM {table} WHERE block_index >= $1 AND block_index < $2"),
+                &[start.into(), end.into()],
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// keeps the event ABI of a deployed or updated contract, when its manifest is in the script
+fn store_events(storage: &dyn Storage, notification: &Notification) -> Result<()> {
+    let Some(hash) = notification
+        .state()
+        .as_array()
+        .and_then(|items| items.first())
+        .and_then(StackItem::as_script_hash)
+    else {
+        return Ok(());
+    };
+    let Some(manifest) = conversion::deploy_manifest(&notification.transaction.script) else {
+        return Ok(());
+    };
+
+    storage.execute(
+        "INSERT INTO contract_events (contract, block_index, events) VALUES ($1, $2, $3)",
+        &[
+            hash.into(),
+            notification.transaction.block_index.into(),
+            manifest["abi"]["events"].to_string().into(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+// the events declared by a contract as of the given block
+fn contract_events(
+    storage: &dyn Storage,
+    contract: &str,
+    block_index: u64,
+) -> Result<Option<Value>> {
+    if let Some(events) = NATIVE_EVENTS.get(contract) {
+        return Ok(Some(events.clone()));
+    }
+
+    let row = storage.query_row(
+        "SELECT events FROM contract_events WHERE contract = $1 AND block_index <= $2
+        ORDER BY block_index DESC, id DESC LIMIT 1",
+        &[contract.into(), block_index.into()],
+    )?;
+
+    match row {
+        Some(row) => Ok(Some(serde_json::from_str(&row.get::<String>(0)?)?)),
+        None => Ok(None),
+    }
+}
+
+// Names the items of a notification's state after the matching ABI event. None when the event
+// isn't declared or its parameters don't line up with the state.
+pub fn decode_parameters(events: &Value, event_name: &str, state: &StackItem) -> Option<Value> {
+    let items = state.as_array()?;
+    let event = events
+        .as_array()?
+        .iter()
+        .find(|event| event["name"] == event_name)?;
+    let parameters = event["parameters"].as_array()?;
+
+    if parameters.len() != items.len() {
+        return None;
+    }
+
+    let decoded: Map<String, Value> = parameters
+        .iter()
+        .zip(items)
+        .map(|(parameter, item)| {
+            let name = parameter["name"].as_str().unwrap_or_default().to_string();
+            let abi_type = parameter["type"].as_str().unwrap_or_default();
+
+            (name, decode_item(abi_type, item))
+        })
+        .collect();
+
+    Some(Value::Object(decoded))
+}
+
+// Converts a stack item to the readable form of its ABI type, e.g. a Hash160 to a script hash.
+// Items that don't fit the declared type are kept as they are.
+pub fn decode_item(abi_type: &str, item: &StackItem) -> Value {
+    if item.is_null() {
+        return Value::Null;
+    }
+
+    let decoded = match (abi_type, item) {
+        ("Hash160", StackItem::ByteString(_)) => item.as_script_hash().map(Value::String),
+        ("Hash256", StackItem::ByteString(_)) => item.as_u256().map(Value::String),
+        ("PublicKey", StackItem::ByteString(bytes)) if bytes.len() == 33 => {
+            Some(Value::String(hex::encode(bytes)))
+        }
+        ("ByteArray", StackItem::ByteString(bytes) | StackItem::Buffer(bytes)) => {
+            Some(Value::String(hex::encode(bytes)))
+        }
+        ("String", StackItem::ByteString(_)) => item.as_utf8().map(Value::String),
+        // integers can exceed any JSON number, so they stay strings
+        ("Integer", StackItem::Integer(integer)) => Some(Value::String(integer.to_string())),
+        ("Boolean", StackItem::Boolean(_) | StackItem::Integer(_)) => {
+            item.as_bool().map(Value::Bool)
+        }
+        _ => None,
+    };
+
+    decoded.unwrap_or_else(|| item.to_json())
+}
@@ -0,0 +1,254 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::database::Database;
+    use crate::db::model::{Block, BlockBatch, Transaction};
+    use crate::processor::notifications::{decode_item, decode_parameters};
+    use lib::db::sqlite::SqliteStorage;
+    use lib::integer::BigInt;
+    use lib::stack_item::StackItem;
+    use serde_json::{json, Value};
+
+    const CONTRACT_MANAGEMENT: &str = "0xfffdc93764dbaddd97c48f252a53ea4643faa3fd";
+
+    // the stack item and its script hash
+    fn hash160() -> (Value, String) {
+        let bytes: Vec<u8> = (1..=20).collect();
+        let mut reversed = bytes.clone();
+        reversed.reverse();
+
+        (
+            StackItem::ByteString(bytes).to_json(),
+            format!("0x{}", hex::encode(reversed)),
+        )
+    }
+
+    fn item(json: Value) -> StackItem {
+        StackItem::from_json(&json).unwrap()
+    }
+
+    #[test]
+    fn test_decode_item() {
+        let (hash, script_hash) = hash160();
+
+        assert_eq!(decode_item("Hash160", &item(hash)), json!(script_hash));
+        assert_eq!(
+            decode_item(
+                "String",
+                &item(json!({ "type": "ByteString", "value": "bmVv" }))
+            ),
+            json!("neo")
+        );
+        assert_eq!(
+            decode_item(
+                "Integer",
+                &item(json!({ "type": "Integer", "value": "100000" }))
+            ),
+            json!("100000")
+        );
+        assert_eq!(
+            decode_item("Boolean", &item(json!({ "type": "Integer", "value": "0" }))),
+            json!(false)
+        );
+        assert_eq!(
+            decode_item("Hash160", &item(json!({ "type": "Any" }))),
+            Value::Null
+        );
+
+        // items that don't fit the declared type are kept as they are
+        let json = json!({ "type": "ByteString", "value": "bmVv" });
+        assert_eq!(decode_item("Hash160", &item(json.clone())), json);
+    }
+
+    #[test]
+    fn test_decode_parameters() {
+        let events = json!([{
+            "name": "Swap",
+            "parameters": [
+                { "name": "amount", "type": "Integer" },
+                { "name": "memo", "type": "String" }
+            ]
+        }]);
+        let state = item(json!({
+            "type": "Array",
+            "value": [
+                { "type": "Integer", "value": "5" },
+                { "type": "ByteString", "value": "bmVv" }
+            ]
+        }));
+
+        assert_eq!(
+            decode_parameters(&events, "Swap", &state),
+            Some(json!({ "amount": "5", "memo": "neo" }))
+        );
+        assert_eq!(decode_parameters(&events, "Transfer", &state), None);
+
+        // the state doesn't line up with the declared parameters
+        let state =
+            item(json!({ "type": "Array", "value": [{ "type": "Integer", "value": "5" }] }));
+        assert_eq!(decode_parameters(&events, "Swap", &state), None);
+    }
+
+    fn block(index: u64) -> Block {
+        Block {
+            index,
+            hash: format!("0xb{index}"),
+            size: 114,
+            version: 0,
+            merkle_root: String::from("0x00"),
+            time: 1468595301000,
+            nonce: String::from("00"),
+            speaker: 0,
+            next_consensus: String::from("N"),
+            reward: BigInt::from(50_000_000),
+            reward_receiver: String::from("N"),
+            witnesses: String::from("[]"),
+        }
+    }
+
+    fn transaction(
+        hash: &str,
+        block_index: u64,
+        script: String,
+        notifications: Value,
+    ) -> Transaction {
+        Transaction {
+            hash: hash.to_string(),
+            block_index,
+            vm_state: String::from("HALT"),
+            size: 250,
+            version: 0,
+            nonce: 0,
+            sender: String::from("N"),
+            sysfee: String::from("0"),
+            netfee: String::from("0"),
+            valid_until: 5760,
+            signers: String::from("[]"),
+            script,
+            witnesses: String::from("[]"),
+            stack_result: String::from("[]"),
+            notifications: notifications.to_string(),
+            block_time: 1468595301000,
+            exception: None,
+            gas_consumed: String::from("0"),
+            attributes: String::from("[]"),
+        }
+    }
+
+    #[test]
+    fn test_notifications_are_decoded_with_the_deployed_abi() {
+        let db = Database::from_storage(Box::new(SqliteStorage::open_in_memory().unwrap()));
+        db.migrate().unwrap();
+
+        let (hash, script_hash) = hash160();
+        let manifest = json!({
+            "name": "Swapper",
+            "abi": {
+                "events": [{
+                    "name": "Swap",
+                    "parameters": [
+                        { "name": "amount", "type": "Integer" },
+                        { "name": "to", "type": "Hash160" }
+                    ]
+                }]
+            }
+        })
+        .to_string();
+        // PUSHDATA1 <manifest>, the rest of the deploy call doesn't matter here
+        let deploy_script = format!("0c{:02x}{}", manifest.len(), hex::encode(&manifest));
+
+        let batch = BlockBatch {
+            blocks: vec![block(0), block(1), block(2)],
+            transactions: vec![
+                transaction(
+                    "0xt1",
+                    1,
+                    deploy_script,
+                    json!([{
+                        "contract": CONTRACT_MANAGEMENT,
+                        "eventname": "Deploy",
+                        "state": { "type": "Array", "value": [hash] }
+                    }]),
+                ),
+                transaction(
+                    "0xt2",
+                    2,
+                    String::new(),
+                    json!([
+                        {
+                            "contract": script_hash,
+                            "eventname": "Swap",
+                            "state": {
+                                "type": "Array",
+                                "value": [
+                                    { "type": "Integer", "value": "5" },
+                                    hash
+                                ]
+                            }
+                        },
+                        {
+                            "contract": "0x0000000000000000000000000000000000000001",
+                            "eventname": "Swap",
+                            "state": { "type": "Array", "value": [] }
+                        }
+                    ]),
+                ),
+            ],
+            ..Default::default()
+        };
+        db.insert_batch(&batch).unwrap();
+
+        let rows: Vec<(String, u64, String, Option<String>)> = db.blocking(|storage| {
+            storage
+                .query(
+                    "SELECT txid, notification_index, event_name, parameters
+                    FROM notifications ORDER BY id",
+                    &[],
+                )
+                .unwrap()
+                .iter()
+                .map(|row| {
+                    (
+                        row.get(0).unwrap(),
+                        row.get(1).unwrap(),
+                        row.get(2).unwrap(),
+                        row.get(3).unwrap(),
+                    )
+                })
+                .collect()
+        });
+
+        let parameters = |row: usize| {
+            rows[row]
+                .3
+                .as_deref()
+                .map(|parameters| serde_json::from_str::<Value>(parameters).unwrap())
+        };
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(parameters(0), Some(json!({ "Hash": script_hash })));
+        assert_eq!(
+            (rows[1].0.as_str(), rows[1].1, rows[1].2.as_str()),
+            ("0xt2", 0, "Swap")
+        );
+        assert_eq!(
+            parameters(1),
+            Some(json!({ "amount": "5", "to": script_hash }))
+        );
+        // no manifest for that contract
+        assert_eq!(rows[2].1, 1);
+        assert_eq!(parameters(2), None);
+
+        // the ABI goes with the blocks it was stored for
+        db.reprocess(1, 2, &[String::from("notifications")])
+            .unwrap();
+        let abis: u64 = db.blocking(|storage| {
+            storage
+                .query_row("SELECT COUNT(*) FROM contract_events", &[])
+                .unwrap()
+                .unwrap()
+                .get(0)
+                .unwrap()
+        });
+        assert_eq!(abis, 1);
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::{Context, Result};
+use lib::db::{Storage, StorageError};
+use lib::network::{GAS_TOKEN, ORACLE_CONTRACT};
+use lib::stack_item::StackItem;
+
+use crate::db::migrations::{execute_ddl, Migration};
+use crate::rpc::models::{self, TransactionAttribute};
+
+use super::{Notification, Processor};
+
+static MIGRATIONS: &[Migration] = &[Migration {
+    version: 1,
+    description: "create oracle requests and responses",
+    up: create_tables,
+}];
+
+fn create_tables(storage: &dyn Storage) -> std::result::Result<(), StorageError> {
+    execute_ddl(
+        storage,
+        "CREATE TABLE IF NOT EXISTS oracle_requests (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            request_id          INTEGER NOT NULL,
+            txid                TEXT NOT NULL,
+            block_index         INTEGER NOT NULL,
+            block_time          INTEGER NOT NULL,
+            contract            TEXT NOT NULL,
+            url                 TEXT NOT NULL,
+            filter              TEXT,
+            gas_for_response    TEXT,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE TABLE IF NOT EXISTS oracle_responses (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            request_id          INTEGER NOT NULL,
+            txid                TEXT NOT NULL,
+            block_index         INTEGER NOT NULL,
+            block_time          INTEGER NOT NULL,
+            code                TEXT,
+            result              TEXT,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_oracle_request_id ON oracle_requests (request_id);
+        CREATE INDEX IF NOT EXISTS idx_oracle_request_contract ON oracle_requests (contract, block_index);
+        CREATE INDEX IF NOT EXISTS idx_oracle_request_block_index ON oracle_requests (block_index);
+        CREATE INDEX IF NOT EXISTS idx_oracle_response_id ON oracle_responses (request_id);
+        CREATE INDEX IF NOT EXISTS idx_oracle_response_block_index ON oracle_responses (block_index);",
+    )
+}
+
+// Oracle requests from OracleContract's OracleRequest events, and their responses from its
+// OracleResponse events. The two are kept apart and joined on `request_id`, since a request and
+// its response are usually in different blocks and can be reindexed separately.
+pub struct OracleProcessor;
+
+impl Processor for OracleProcessor {
+    fn name(&self) -> &'static str {
+        "oracles"
+    }
+
+    fn migrations(&self) -> &'static [Migration] {
+        MIGRATIONS
+    }
+
+    fn process_notification(
+        &self,
+        storage: &dyn Storage,
+        notification: &Notification,
+    ) -> Result<()> {
+        if notification.contract() != ORACLE_CONTRACT {
+            return Ok(());
+        }
+
+        match notification.event_name() {
+            "OracleRequest" => store_request(storage, notification),
+            "OracleResponse" => store_response(storage, notification),
+            _ => Ok(()),
+        }
+    }
+
+    fn delete_range(&self, storage: &dyn Storage, start: u64, end: u64) -> Result<()> {
+        for table in ["oracle_requests", "oracle_responses"] {
+            storage.execute(
+                &format!("DELETE FROM {table} WHERE block_index >= $1 AND block_index < $2"),
+                &[start.into(), end.into()],
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// [Id, RequestContract, Url, Filter]
+fn store_request(storage: &dyn Storage, notification: &Notification) -> Result<()> {
+    let state = notification.state().as_array().unwrap_or_default();
+    let (Some(request_id), Some(contract), Some(url)) = (
+        state.first().and_then(StackItem::as_integer),
+        state.get(1).and_then(StackItem::as_script_hash),
+        state.get(2).and_then(StackItem::as_utf8),
+    ) else {
+        return Ok(());
+    };
+    let filter = state.get(3).and_then(StackItem::as_utf8);
+    let transaction = notification.transaction;
+
+    storage.execute(
+        "INSERT INTO oracle_requests (
+        request_id, txid, block_index, block_time, contract, url, filter, gas_for_response
+    ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        &[
+            u64::try_from(&request_id)?.into(),
+            transaction.hash.as_str().into(),
+            transaction.block_index.into(),
+            transaction.block_time.into(),
+            contract.into(),
+            url.into(),
+            filter.into(),
+            gas_for_response(notification)?.into(),
+        ],
+    )?;
+
+    Ok(())
+}
+
+// The GAS for the response is minted to OracleContract just before the request is emitted
+fn gas_for_response(notification: &Notification) -> Result<Option<String>> {
+    let transaction = notification.transaction;
+    let notifications: Vec<models::Notification> = serde_json::from_str(&transaction.notifications)
+        .with_context(|| format!("Invalid notifications in transaction {}", transaction.hash))?;
+
+    let amount = notifications[..notification.index.min(notifications.len())]
+        .iter()
+        .rev()
+        .filter(|event| event.contract == GAS_TOKEN && event.eventname == "Transfer")
+        .filter_map(|event| event.state.as_array())
+        .find(|state| {
+            state.first().is_some_and(StackItem::is_null)
+                && state.get(1).and_then(StackItem::as_script_hash).as_deref()
+                    == Some(ORACLE_CONTRACT)
+        })
+        .and_then(|state| state.get(2)?.as_integer())
+        .map(|amount| amount.to_string());
+
+    Ok(amount)
+}
+
+// [Id, OriginalTx], the code and result come from the transaction's OracleResponse attribute
+fn store_response(storage: &dyn Storage, notification: &Notification) -> Result<()> {
+    let Some(request_id) = notification
+        .state()
+        .as_array()
+        .and_then(|state| state.first())
+        .and_then(StackItem::as_integer)
+    else {
+        return Ok(());
+    };
+    let request_id = u64::try_from(&request_id)?;
+    let transaction = notification.transaction;
+
+    let attributes: Vec<TransactionAttribute> = serde_json::from_str(&transaction.attributes)
+        .with_context(|| format!("Invalid attributes in transaction {}", transaction.hash))?;
+    let (code, result) = attributes
+        .into_iter()
+        .find_map(|attribute| match attribute {
+            TransactionAttribute::OracleResponse { id, code, result } if id == request_id => {
+                Some((Some(code), Some(result)))
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
+
+    storage.execute(
+        "INSERT INTO oracle_responses (
+        request_id, txid, block_index, block_time, code, result
+    ) VALUES ($1, $2, $3, $4, $5, $6)",
+        &[
+            request_id.into(),
+            transaction.hash.as_str().into(),
+            transaction.block_index.into(),
+            transaction.block_time.into(),
+            code.into(),
+            result.into(),
+        ],
+    )?;
+
+    Ok(())
+}
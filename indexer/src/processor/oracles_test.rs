@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::database::Database;
+    use crate::db::model::{Block, BlockBatch, Transaction};
+    use lib::db::sqlite::SqliteStorage;
+    use lib::integer::BigInt;
+    use lib::stack_item::StackItem;
+    use serde_json::{json, Value};
+
+    const ORACLE_CONTRACT: &str = "0xfe924b7cfe89ddd271abaf7210a80a7e11178758";
+    const GAS_TOKEN: &str = "0xd2a4cff31913016155e38e474a2c06d08be276cf";
+    const REQUESTER: &str = "0x0102030405060708090a0b0c0d0e0f1011121314";
+
+    // the stack item of a script hash
+    fn hash160(script_hash: &str) -> Value {
+        let mut bytes = hex::decode(script_hash.trim_start_matches("0x")).unwrap();
+        bytes.reverse();
+        StackItem::ByteString(bytes).to_json()
+    }
+
+    fn utf8(value: &str) -> Value {
+        StackItem::ByteString(value.as_bytes().to_vec()).to_json()
+    }
+
+    fn block(index: u64) -> Block {
+        Block {
+            index,
+            hash: format!("0xb{index}"),
+            size: 114,
+            version: 0,
+            merkle_root: String::from("0x00"),
+            time: 1468595301000 + index * 15000,
+            nonce: String::from("00"),
+            speaker: 0,
+            next_consensus: String::from("N"),
+            reward: BigInt::from(50_000_000),
+            reward_receiver: String::from("N"),
+            witnesses: String::from("[]"),
+        }
+    }
+
+    fn transaction(
+        hash: &str,
+        block_index: u64,
+        notifications: Value,
+        attributes: Value,
+    ) -> Transaction {
+        Transaction {
+            hash: hash.to_string(),
+            block_index,
+            vm_state: String::from("HALT"),
+            size: 250,
+            version: 0,
+            nonce: 0,
+            sender: String::from("N"),
+            sysfee: String::from("0"),
+            netfee: String::from("0"),
+            valid_until: 5760,
+            signers: String::from("[]"),
+            script: String::new(),
+            witnesses: String::from("[]"),
+            stack_result: String::from("[]"),
+            notifications: notifications.to_string(),
+            block_time: 1468595301000 + block_index * 15000,
+            exception: None,
+            gas_consumed: String::from("0"),
+            attributes: attributes.to_string(),
+        }
+    }
+
+    fn count(db: &Database, table: &str) -> u64 {
+        db.blocking(|storage| {
+            storage
+                .query_row(&format!("SELECT COUNT(*) FROM {table}"), &[])
+                .unwrap()
+                .unwrap()
+                .get(0)
+                .unwrap()
+        })
+    }
+
+    #[test]
+    fn test_requests_are_linked_to_their_response() {
+        let db = Database::from_storage(Box::new(SqliteStorage::open_in_memory().unwrap()));
+        db.migrate().unwrap();
+
+        let request = transaction(
+            "0xt1",
+            1,
+            json!([
+                {
+                    "contract": GAS_TOKEN,
+                    "eventname": "Transfer",
+                    "state": {
+                        "type": "Array",
+                        "value": [
+                            { "type": "Any" },
+                            hash160(ORACLE_CONTRACT),
+                            { "type": "Integer", "value": "50000000" }
+                        ]
+                    }
+                },
+                {
+                    "contract": ORACLE_CONTRACT,
+                    "eventname": "OracleRequest",
+                    "state": {
+                        "type": "Array",
+                        "value": [
+                            { "type": "Integer", "value": "3" },
+                            hash160(REQUESTER),
+                            utf8("https://example.com/price"),
+                            utf8("$.price")
+                        ]
+                    }
+                }
+            ]),
+            json!([]),
+        );
+        let response = transaction(
+            "0xt2",
+            3,
+            json!([{
+                "contract": ORACLE_CONTRACT,
+                "eventname": "OracleResponse",
+                "state": {
+                    "type": "Array",
+                    "value": [
+                        { "type": "Integer", "value": "3" },
+                        StackItem::ByteString(vec![1; 32]).to_json()
+                    ]
+                }
+            }]),
+            json!([{ "type": "OracleResponse", "id": 3, "code": "Success", "result": "MTIz" }]),
+        );
+
+        let batch = BlockBatch {
+            blocks: (0..4).map(block).collect(),
+            transactions: vec![request, response],
+            ..Default::default()
+        };
+        db.insert_batch(&batch).unwrap();
+
+        let row: (String, String, Option<String>, Option<String>, String, String, u64) =
+            db.blocking(|storage| {
+                let row = storage
+                    .query_row(
+                        "SELECT q.contract, q.url, q.filter, q.gas_for_response, r.txid, r.code,
+                        r.block_time - q.block_time
+                        FROM oracle_requests q JOIN oracle_responses r ON r.request_id = q.request_id",
+                        &[],
+                    )
+                    .unwrap()
+                    .unwrap();
+                (
+                    row.get(0).unwrap(),
+                    row.get(1).unwrap(),
+                    row.get(2).unwrap(),
+                    row.get(3).unwrap(),
+                    row.get(4).unwrap(),
+                    row.get(5).unwrap(),
+                    row.get(6).unwrap(),
+                )
+            });
+
+        assert_eq!(
+            row,
+            (
+                String::from(REQUESTER),
+                String::from("https://example.com/price"),
+                Some(String::from("$.price")),
+                Some(String::from("50000000")),
+                String::from("0xt2"),
+                String::from("Success"),
+                30000
+            )
+        );
+
+        // reprocessing the response's block leaves the request alone
+        db.reprocess(2, 4, &[String::from("oracles")]).unwrap();
+        assert_eq!(count(&db, "oracle_requests"), 1);
+        assert_eq!(count(&db, "oracle_responses"), 1);
+    }
+}
@@ -0,0 +1,221 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::database::Database;
+    use crate::db::migrations::{execute_ddl, Migration};
+    use crate::db::model::{Block, BlockBatch, Transaction};
+    use crate::processor::{Notification, Processor};
+    use anyhow::{anyhow, Result};
+    use lib::db::sqlite::SqliteStorage;
+    use lib::db::{Storage, StorageError};
+    use lib::integer::BigInt;
+    use serde_json::json;
+
+    // records every notification by event name
+    struct EventProcessor;
+
+    fn create_events(storage: &dyn Storage) -> std::result::Result<(), StorageError> {
+        execute_ddl(
+            storage,
+            "CREATE TABLE events (
+                block_index         INTEGER NOT NULL,
+                txid                TEXT NOT NULL,
+                notification_index  INTEGER NOT NULL,
+                name                TEXT NOT NULL
+            );",
+        )
+    }
+
+    static EVENT_MIGRATIONS: &[Migration] = &[Migration {
+        version: 1,
+        description: "create events",
+        up: create_events,
+    }];
+
+    impl Processor for EventProcessor {
+        fn name(&self) -> &'static str {
+            "events"
+        }
+
+        fn migrations(&self) -> &'static [Migration] {
+            EVENT_MIGRATIONS
+        }
+
+        fn process_notification(
+            &self,
+            storage: &dyn Storage,
+            notification: &Notification,
+        ) -> Result<()> {
+            if notification.event_name() == "Fail" {
+                return Err(anyhow!("processor failed"));
+            }
+
+            storage.execute(
+                "INSERT INTO events (block_index, txid, notification_index, name)
+                VALUES ($1, $2, $3, $4)",
+                &[
+                    notification.transaction.block_index.into(),
+                    notification.transaction.hash.as_str().into(),
+                    notification.index.into(),
+                    notification.event_name().into(),
+                ],
+            )?;
+
+            Ok(())
+        }
+
+        fn delete_range(&self, storage: &dyn Storage, start: u64, end: u64) -> Result<()> {
+            storage.execute(
+                "DELETE FROM events WHERE block_index >= $1 AND block_index < $2",
+                &[start.into(), end.into()],
+            )?;
+
+            Ok(())
+        }
+    }
+
+    fn block(index: u64) -> Block {
+        Block {
+            index,
+            hash: format!("0xb{index}"),
+            size: 114,
+            version: 0,
+            merkle_root: String::from("0x00"),
+            time: 1468595301000,
+            nonce: String::from("00"),
+            speaker: 0,
+            next_consensus: String::from("N"),
+            reward: BigInt::from(50_000_000),
+            reward_receiver: String::from("N"),
+            witnesses: String::from("[]"),
+        }
+    }
+
+    fn transaction(hash: &str, block_index: u64, notifications: serde_json::Value) -> Transaction {
+        Transaction {
+            hash: hash.to_string(),
+            block_index,
+            vm_state: String::from("HALT"),
+            size: 250,
+            version: 0,
+            nonce: 0,
+            sender: String::from("N"),
+            sysfee: String::from("0"),
+            netfee: String::from("0"),
+            valid_until: 5760,
+            signers: String::from("[]"),
+            script: String::new(),
+            witnesses: String::from("[]"),
+            stack_result: String::from("[]"),
+            notifications: notifications.to_string(),
+            block_time: 1468595301000,
+            exception: None,
+            gas_consumed: String::from("0"),
+            attributes: String::from("[]"),
+        }
+    }
+
+    fn transfer() -> serde_json::Value {
+        json!({
+            "contract": "0xef4073a0f2b305a38ec4050e4d3d28bc40ea63f5",
+            "eventname": "Transfer",
+            "state": {
+                "type": "Array",
+                "value": [
+                    { "type": "ByteString", "value": "axI92L7HGGSIUrvHhZXjU2oFj58=" },
+                    { "type": "ByteString", "value": "dVE6zv92GLfukg8P5gFa0cDxb/0=" },
+                    { "type": "Integer", "value": "100000" }
+                ]
+            }
+        })
+    }
+
+    fn count(db: &Database, table: &str) -> u64 {
+        db.blocking(|storage| {
+            storage
+                .query_row(&format!("SELECT COUNT(*) FROM {table}"), &[])
+                .unwrap()
+                .unwrap()
+                .get(0)
+                .unwrap()
+        })
+    }
+
+    fn database() -> Database {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        let mut db = Database::from_storage(Box::new(storage));
+        db.register(Box::new(EventProcessor));
+        db.migrate().unwrap();
+
+        db
+    }
+
+    #[test]
+    fn test_processors_run_with_the_batch() {
+        let db = database();
+        assert_eq!(
+            db.processor_names(),
+            vec![
+                "contracts",
+                "addresses",
+                "notifications",
+                "signers",
+                "attributes",
+                "oracles",
+                "nns",
+                "mempool",
+                "events"
+            ]
+        );
+
+        let batch = BlockBatch {
+            blocks: vec![block(0), block(1)],
+            transactions: vec![transaction("0xt1", 1, json!([transfer(), transfer()]))],
+            ..Default::default()
+        };
+        db.insert_batch(&batch).unwrap();
+
+        assert_eq!(count(&db, "events"), 2);
+        // the built-in address processor saw the same transfers
+        assert_eq!(count(&db, "addresses"), 4);
+
+        // a failing processor rolls back the whole batch
+        let batch = BlockBatch {
+            blocks: vec![block(2)],
+            transactions: vec![transaction(
+                "0xt2",
+                2,
+                json!([transfer(), {
+                    "contract": "0xef4073a0f2b305a38ec4050e4d3d28bc40ea63f5",
+                    "eventname": "Fail",
+                    "state": { "type": "Array", "value": [] }
+                }]),
+            )],
+            ..Default::default()
+        };
+        assert!(db.insert_batch(&batch).is_err());
+        assert_eq!(db.get_stored_height().unwrap(), Some(1));
+        assert_eq!(count(&db, "events"), 2);
+        assert_eq!(count(&db, "addresses"), 4);
+    }
+
+    #[test]
+    fn test_processor_migrations_are_tracked() {
+        let db = database();
+
+        // already applied, so creating the table again would fail
+        db.migrate().unwrap();
+
+        let version: u32 = db.blocking(|storage| {
+            storage
+                .query_row(
+                    "SELECT version FROM processor_versions WHERE name = 'events'",
+                    &[],
+                )
+                .unwrap()
+                .unwrap()
+                .get(0)
+                .unwrap()
+        });
+        assert_eq!(version, 1);
+    }
+}
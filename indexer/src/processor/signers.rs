@@ -0,0 +1,139 @@
+use anyhow::{Context, Result};
+use lib::db::{Storage, StorageError};
+use lib::neo;
+
+use crate::db::migrations::{execute_ddl, Migration};
+use crate::db::model::Transaction;
+use crate::rpc::models::Signer;
+
+use super::Processor;
+
+static MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "create signers",
+        up: create_table,
+    },
+    Migration {
+        version: 2,
+        description: "fill signers of stored transactions",
+        up: backfill,
+    },
+];
+
+// transactions read per query while backfilling
+const BACKFILL_PAGE: u64 = 10_000;
+
+fn create_table(storage: &dyn Storage) -> std::result::Result<(), StorageError> {
+    execute_ddl(
+        storage,
+        "CREATE TABLE IF NOT EXISTS signers (
+            id                  INTEGER PRIMARY KEY AUTOINCREMENT,
+            txid                TEXT NOT NULL,
+            block_index         INTEGER NOT NULL,
+            position            INTEGER NOT NULL,
+            account             TEXT NOT NULL,
+            scopes              TEXT NOT NULL,
+            FOREIGN KEY (block_index) REFERENCES blocks (id)
+        );
+
+        CREATE INDEX IF NOT EXISTS idx_signer_account ON signers (account, block_index);
+        CREATE INDEX IF NOT EXISTS idx_signer_txid ON signers (txid);
+        CREATE INDEX IF NOT EXISTS idx_signer_block_index ON signers (block_index);",
+    )
+}
+
+// Transactions stored before the table existed. Every transaction has a signer, so those
+// processed since are the ones that already have rows.
+fn backfill(storage: &dyn Storage) -> std::result::Result<(), StorageError> {
+    let mut start: u64 = 0;
+    loop {
+        let rows = storage.query(
+            "SELECT t.id, t.hash, t.block_index, t.signers FROM transactions t
+            WHERE t.id >= $1 AND NOT EXISTS (SELECT 1 FROM signers s WHERE s.txid = t.hash)
+            ORDER BY t.id LIMIT $2",
+            &[start.into(), BACKFILL_PAGE.into()],
+        )?;
+        if rows.is_empty() {
+            return Ok(());
+        }
+
+        for row in &rows {
+            let id: u64 = row.get(0)?;
+            let hash: String = row.get(1)?;
+            let signers: String = row.get(3)?;
+            let signers: Vec<Signer> = serde_json::from_str(&signers).map_err(|e| {
+                StorageError::Decode(format!("invalid signers in transaction {hash}: {e}"))
+            })?;
+
+            insert_signers(storage, &hash, row.get(2)?, &signers)?;
+            start = id + 1;
+        }
+    }
+}
+
+fn insert_signers(
+    storage: &dyn Storage,
+    txid: &str,
+    block_index: u64,
+    signers: &[Signer],
+) -> std::result::Result<(), StorageError> {
+    for (position, signer) in signers.iter().enumerate() {
+        storage.execute(
+            "INSERT INTO signers (
+            txid, block_index, position, account, scopes
+        ) VALUES ($1, $2, $3, $4, $5)",
+            &[
+                txid.into(),
+                block_index.into(),
+                position.into(),
+                account_address(&signer.account).into(),
+                signer.scopes.as_str().into(),
+            ],
+        )?;
+    }
+
+    Ok(())
+}
+
+// One row per transaction signer, in signing order, with the account as an address so it can be
+// matched like `transactions.sender` (which is always the signer at position 0)
+pub struct SignerProcessor;
+
+impl Processor for SignerProcessor {
+    fn name(&self) -> &'static str {
+        "signers"
+    }
+
+    fn migrations(&self) -> &'static [Migration] {
+        MIGRATIONS
+    }
+
+    fn process_transaction(&self, storage: &dyn Storage, transaction: &Transaction) -> Result<()> {
+        let signers: Vec<Signer> = serde_json::from_str(&transaction.signers)
+            .with_context(|| format!("Invalid signers in transaction {}", transaction.hash))?;
+
+        insert_signers(
+            storage,
+            &transaction.hash,
+            transaction.block_index,
+            &signers,
+        )?;
+
+        Ok(())
+    }
+
+    fn delete_range(&self, storage: &dyn Storage, start: u64, end: u64) -> Result<()> {
+        storage.execute(
+            "DELETE FROM signers WHERE block_index >= $1 AND block_index < $2",
+            &[start.into(), end.into()],
+        )?;
+
+        Ok(())
+    }
+}
+
+// signer accounts are big-endian script hashes
+pub fn account_address(account: &str) -> String {
+    neo::scripthash_to_address(&neo::reverse_hex(account.trim_start_matches("0x")))
+}
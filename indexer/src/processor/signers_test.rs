@@ -0,0 +1,252 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::database::Database;
+    use crate::db::model::{Block, BlockBatch, Transaction};
+    use crate::processor::signers::account_address;
+    use crate::rpc::models::{Signer, WitnessCondition, WitnessRuleAction};
+    use lib::db::sqlite::SqliteStorage;
+    use lib::integer::BigInt;
+    use serde_json::{json, Value};
+
+    // script hash of the bytes 0..20, and its address
+    const ACCOUNT: &str = "0x131211100f0e0d0c0b0a09080706050403020100";
+    const ADDRESS: &str = "NKuzPnNtxubufwT9MvNKJ557TRPWe1jGDF";
+
+    fn signers() -> Value {
+        json!([
+            {
+                "account": ACCOUNT,
+                "scopes": "CalledByEntry"
+            },
+            {
+                "account": "0xd2a4cff31913016155e38e474a2c06d08be276cf",
+                "scopes": "CustomContracts, CustomGroups, WitnessRules",
+                "allowedcontracts": ["0xef4073a0f2b305a38ec4050e4d3d28bc40ea63f5"],
+                "allowedgroups": ["02a7bc55fe8684e0119768d104ba30795bdcc86619e864add26156723ed185cd62"],
+                "rules": [
+                    {
+                        "action": "Allow",
+                        "condition": {
+                            "type": "And",
+                            "expressions": [
+                                { "type": "CalledByEntry" },
+                                {
+                                    "type": "Not",
+                                    "expression": { "type": "Boolean", "expression": false }
+                                },
+                                {
+                                    "type": "Or",
+                                    "expressions": [
+                                        {
+                                            "type": "ScriptHash",
+                                            "hash": "0xef4073a0f2b305a38ec4050e4d3d28bc40ea63f5"
+                                        },
+                                        {
+                                            "type": "Group",
+                                            "group": "02a7bc55fe8684e0119768d104ba30795bdcc86619e864add26156723ed185cd62"
+                                        }
+                                    ]
+                                }
+                            ]
+                        }
+                    },
+                    {
+                        "action": "Deny",
+                        "condition": {
+                            "type": "CalledByContract",
+                            "hash": "0xef4073a0f2b305a38ec4050e4d3d28bc40ea63f5"
+                        }
+                    },
+                    {
+                        "action": "Allow",
+                        "condition": {
+                            "type": "CalledByGroup",
+                            "group": "02a7bc55fe8684e0119768d104ba30795bdcc86619e864add26156723ed185cd62"
+                        }
+                    }
+                ]
+            }
+        ])
+    }
+
+    #[test]
+    fn test_signers_round_trip() {
+        let parsed: Vec<Signer> = serde_json::from_value(signers()).unwrap();
+
+        assert_eq!(parsed[0].rules, None);
+        let rules = parsed[1].rules.as_ref().unwrap();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[1].action, WitnessRuleAction::Deny);
+        assert!(matches!(
+            &rules[0].condition,
+            WitnessCondition::And { expressions } if expressions[0] == WitnessCondition::CalledByEntry
+        ));
+
+        // nothing is dropped or added when written back
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), signers());
+    }
+
+    #[test]
+    fn test_account_address() {
+        assert_eq!(account_address(ACCOUNT), ADDRESS);
+    }
+
+    fn block(index: u64) -> Block {
+        Block {
+            index,
+            hash: format!("0xb{index}"),
+            size: 114,
+            version: 0,
+            merkle_root: String::from("0x00"),
+            time: 1468595301000,
+            nonce: String::from("00"),
+            speaker: 0,
+            next_consensus: String::from("N"),
+            reward: BigInt::from(50_000_000),
+            reward_receiver: String::from("N"),
+            witnesses: String::from("[]"),
+        }
+    }
+
+    fn transaction(hash: &str, block_index: u64, signers: Value) -> Transaction {
+        Transaction {
+            hash: hash.to_string(),
+            block_index,
+            vm_state: String::from("HALT"),
+            size: 250,
+            version: 0,
+            nonce: 0,
+            sender: String::from("N"),
+            sysfee: String::from("0"),
+            netfee: String::from("0"),
+            valid_until: 5760,
+            signers: signers.to_string(),
+            script: String::new(),
+            witnesses: String::from("[]"),
+            stack_result: String::from("[]"),
+            notifications: String::from("[]"),
+            block_time: 1468595301000,
+            exception: None,
+            gas_consumed: String::from("0"),
+            attributes: String::from("[]"),
+        }
+    }
+
+    #[test]
+    fn test_signers_are_stored_in_order() {
+        let db = Database::from_storage(Box::new(SqliteStorage::open_in_memory().unwrap()));
+        db.migrate().unwrap();
+
+        let batch = BlockBatch {
+            blocks: vec![block(0), block(1)],
+            transactions: vec![
+                transaction("0xt1", 1, signers()),
+                transaction(
+                    "0xt2",
+                    1,
+                    json!([{ "account": ACCOUNT, "scopes": "Global" }]),
+                ),
+            ],
+            ..Default::default()
+        };
+        db.insert_batch(&batch).unwrap();
+
+        let rows: Vec<(String, u64, String, String)> = db.blocking(|storage| {
+            storage
+                .query(
+                    "SELECT txid, position, account, scopes FROM signers ORDER BY id",
+                    &[],
+                )
+                .unwrap()
+                .iter()
+                .map(|row| {
+                    (
+                        row.get(0).unwrap(),
+                        row.get(1).unwrap(),
+                        row.get(2).unwrap(),
+                        row.get(3).unwrap(),
+                    )
+                })
+                .collect()
+        });
+
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            (
+                String::from("0xt1"),
+                0,
+                String::from(ADDRESS),
+                String::from("CalledByEntry")
+            )
+        );
+        assert_eq!(rows[1].1, 1);
+        assert_eq!(rows[1].3, "CustomContracts, CustomGroups, WitnessRules");
+        assert_eq!((rows[2].0.as_str(), rows[2].1), ("0xt2", 0));
+
+        db.reprocess(1, 2, &[String::from("signers")]).unwrap();
+        let count: u64 = db.blocking(|storage| {
+            storage
+                .query_row("SELECT COUNT(*) FROM signers", &[])
+                .unwrap()
+                .unwrap()
+                .get(0)
+                .unwrap()
+        });
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_signers_backfill() {
+        let db = Database::from_storage(Box::new(SqliteStorage::open_in_memory().unwrap()));
+        db.migrate().unwrap();
+
+        let batch = BlockBatch {
+            blocks: vec![block(0), block(1)],
+            transactions: vec![
+                transaction("0xt1", 1, signers()),
+                transaction(
+                    "0xt2",
+                    1,
+                    json!([{ "account": ACCOUNT, "scopes": "Global" }]),
+                ),
+            ],
+            ..Default::default()
+        };
+        db.insert_batch(&batch).unwrap();
+
+        // as if 0xt2 had been stored before the table existed
+        db.blocking(|storage| {
+            storage
+                .execute("DELETE FROM signers WHERE txid = '0xt2'", &[])
+                .unwrap();
+            storage
+                .execute(
+                    "UPDATE processor_versions SET version = 1 WHERE name = 'signers'",
+                    &[],
+                )
+                .unwrap();
+        });
+        db.migrate().unwrap();
+
+        let rows: Vec<(String, u64)> = db.blocking(|storage| {
+            storage
+                .query(
+                    "SELECT txid, position FROM signers ORDER BY txid, position",
+                    &[],
+                )
+                .unwrap()
+                .iter()
+                .map(|row| (row.get(0).unwrap(), row.get(1).unwrap()))
+                .collect()
+        });
+        assert_eq!(
+            rows,
+            vec![
+                (String::from("0xt1"), 0),
+                (String::from("0xt1"), 1),
+                (String::from("0xt2"), 0)
+            ]
+        );
+    }
+}
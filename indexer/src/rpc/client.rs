@@ -1,17 +1,26 @@
 use anyhow::Result;
 use reqwest::Client as ReqwestClient;
 
+use std::time::Instant;
+
 use crate::config::AppConfig;
+use crate::utils::metrics::METRICS;
+use crate::utils::verify::verify_block_hashes;
 
-use super::method::{GetApplicationLog, GetBlock, GetBlockCount, RpcMethod};
+use super::method::{
+    GetApplicationLog, GetBlock, GetBlockCount, GetRawMempool, GetRawTransaction, GetStateHeight,
+    GetStateRoot, GetVersion, RpcMethod,
+};
 use super::models::{
-    BlockAppLogResult, BlockResult, RpcRequest, RpcResponse, TransactionAppLogResult,
-    TransactionResult,
+    BlockAppLogResult, BlockResult, RpcRequest, RpcResponse, StateHeightResult, StateRootResult,
+    TransactionAppLogResult, TransactionResult, VersionResult,
 };
 
+#[derive(Clone)]
 pub struct Client {
     client: ReqwestClient,
     base_url: String,
+    verify_hashes: bool,
 }
 
 impl Client {
@@ -19,6 +28,7 @@ impl Client {
         Self {
             client: ReqwestClient::new(),
             base_url: config.node_path.clone(),
+            verify_hashes: config.verify_hashes,
         }
     }
 
@@ -32,16 +42,21 @@ impl Client {
             method: method.method_name().to_string(),
             params: method.params(),
         };
-        let response: RpcResponse<R> = self
-            .client
-            .post(&self.base_url)
-            .json(&request_body)
-            .send()
-            .await?
-            .json()
-            .await?;
 
-        Ok(response.result)
+        let started = Instant::now();
+        let response: Result<RpcResponse<R>, reqwest::Error> = async {
+            self.client
+                .post(&self.base_url)
+                .json(&request_body)
+                .send()
+                .await?
+                .json()
+                .await
+        }
+        .await;
+        METRICS.observe_rpc(method.method_name(), started, response.is_ok());
+
+        Ok(response?.result)
     }
 
     pub async fn get_current_height(&self) -> Result<u64> {
@@ -49,6 +64,11 @@ impl Client {
         Ok(response)
     }
 
+    pub async fn get_version(&self) -> Result<VersionResult> {
+        let response = self.send_request(GetVersion).await?;
+        Ok(response)
+    }
+
     pub async fn get_block(&self, height: u64) -> Result<BlockResult> {
         let response = self
             .send_request(GetBlock {
@@ -59,6 +79,17 @@ impl Client {
         Ok(response)
     }
 
+    // the block in base64 as it is serialized on chain
+    pub async fn get_raw_block(&self, height: u64) -> Result<String> {
+        let response = self
+            .send_request(GetBlock {
+                block_height: height,
+                verbosity: 0,
+            })
+            .await?;
+        Ok(response)
+    }
+
     pub async fn get_application_log<T: serde::de::DeserializeOwned>(
         &self,
         hash: &str,
@@ -71,8 +102,39 @@ impl Client {
         Ok(app_log)
     }
 
+    // hashes of the verified transactions waiting in the node's mempool
+    pub async fn get_raw_mempool(&self) -> Result<Vec<String>> {
+        let response = self.send_request(GetRawMempool).await?;
+        Ok(response)
+    }
+
+    pub async fn get_raw_transaction(&self, hash: &str) -> Result<TransactionResult> {
+        let response = self
+            .send_request(GetRawTransaction {
+                hash: hash.to_string(),
+            })
+            .await?;
+        Ok(response)
+    }
+
+    // None when the node has no state root for the block
+    pub async fn get_state_root(&self, index: u64) -> Result<Option<StateRootResult>> {
+        let response = self.send_request(GetStateRoot { index }).await?;
+        Ok(response)
+    }
+
+    // None when the node doesn't track state roots
+    pub async fn get_state_height(&self) -> Result<Option<StateHeightResult>> {
+        let response = self.send_request(GetStateHeight).await?;
+        Ok(response)
+    }
+
     pub async fn fetch_full_block(&self, height: u64) -> Result<(BlockResult, BlockAppLogResult)> {
         let block = self.get_block(height).await?;
+        if self.verify_hashes {
+            let raw = self.get_raw_block(height).await?;
+            verify_block_hashes(&block, &raw)?;
+        }
         let block_app_log: BlockAppLogResult = self.get_application_log(&block.hash).await?;
 
         Ok((block, block_app_log))
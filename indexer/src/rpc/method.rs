@@ -1,6 +1,9 @@
 use serde::Deserialize;
 
-use super::models::{AppLogResult, BlockResult, NeoParam};
+use super::models::{
+    AppLogResult, BlockResult, NeoParam, StateHeightResult, StateRootResult, TransactionResult,
+    VersionResult,
+};
 
 pub trait RpcMethod {
     type ReturnType: for<'de> Deserialize<'de>;
@@ -58,3 +61,78 @@ impl RpcMethod for GetApplicationLog {
         vec![NeoParam::String(self.hash.clone())]
     }
 }
+
+pub struct GetVersion;
+
+impl RpcMethod for GetVersion {
+    type ReturnType = VersionResult;
+
+    fn method_name(&self) -> &'static str {
+        "getversion"
+    }
+
+    fn params(&self) -> Vec<NeoParam> {
+        vec![]
+    }
+}
+
+pub struct GetRawMempool;
+
+impl RpcMethod for GetRawMempool {
+    type ReturnType = Vec<String>;
+
+    fn method_name(&self) -> &'static str {
+        "getrawmempool"
+    }
+
+    fn params(&self) -> Vec<NeoParam> {
+        vec![]
+    }
+}
+
+pub struct GetRawTransaction {
+    pub hash: String,
+}
+
+impl RpcMethod for GetRawTransaction {
+    type ReturnType = TransactionResult;
+
+    fn method_name(&self) -> &'static str {
+        "getrawtransaction"
+    }
+
+    // verbose
+    fn params(&self) -> Vec<NeoParam> {
+        vec![NeoParam::String(self.hash.clone()), NeoParam::Integer(1)]
+    }
+}
+
+pub struct GetStateRoot {
+    pub index: u64,
+}
+
+impl RpcMethod for GetStateRoot {
+    type ReturnType = Option<StateRootResult>;
+
+    fn method_name(&self) -> &'static str {
+        "getstateroot"
+    }
+
+    fn params(&self) -> Vec<NeoParam> {
+        vec![NeoParam::Integer(self.index)]
+    }
+}
+
+pub struct GetStateHeight;
+
+impl RpcMethod for GetStateHeight {
+    type ReturnType = Option<StateHeightResult>;
+
+    fn method_name(&self) -> &'static str {
+        "getstateheight"
+    }
+
+    fn params(&self) -> Vec<NeoParam> {
+        vec![]
+    }
+}
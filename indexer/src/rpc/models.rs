@@ -1,3 +1,4 @@
+use lib::stack_item::StackItem;
 use serde::{Deserialize, Serialize, Serializer};
 
 #[derive(Deserialize, Debug)]
@@ -26,6 +27,7 @@ pub struct RpcRequest {
     pub id: u32,
 }
 
+#[allow(dead_code)]
 #[derive(Deserialize, Debug)]
 pub struct RpcResponse<T> {
     pub jsonrpc: String,
@@ -38,6 +40,7 @@ pub struct BlockResult {
     pub hash: String,
     pub size: u32,
     pub version: u8,
+    pub previousblockhash: String,
     pub merkleroot: String,
     pub time: u64,
     pub nonce: String,
@@ -48,6 +51,7 @@ pub struct BlockResult {
     pub tx: Vec<TransactionResult>,
 }
 
+#[allow(dead_code)]
 #[derive(Deserialize, Debug, Clone)]
 pub struct TransactionResult {
     pub hash: String,
@@ -60,22 +64,27 @@ pub struct TransactionResult {
     pub netfee: String,
     pub validuntilblock: u64,
     pub signers: Vec<Signer>,
+    #[serde(default)]
+    pub attributes: Vec<TransactionAttribute>,
     pub script: String,
     pub witnesses: Vec<Witness>,
 }
 
+#[allow(dead_code)]
 #[derive(Deserialize, Debug, Clone)]
 pub enum AppLogResult {
     BlockAppLogResult(BlockAppLogResult),
     TransactionAppLogResult(TransactionAppLogResult),
 }
 
+#[allow(dead_code)]
 #[derive(Deserialize, Debug, Clone)]
 pub struct BlockAppLogResult {
     pub blockhash: String,
     pub executions: Vec<Execution>,
 }
 
+#[allow(dead_code)]
 #[derive(Deserialize, Debug, Clone)]
 pub struct TransactionAppLogResult {
     pub txid: String,
@@ -88,7 +97,7 @@ pub struct Execution {
     pub vmstate: String,
     pub exception: Option<String>,
     pub gasconsumed: String,
-    pub stack: Vec<StateValue>,
+    pub stack: Vec<StackItem>,
     pub notifications: Vec<Notification>,
 }
 
@@ -96,21 +105,36 @@ pub struct Execution {
 pub struct Notification {
     pub contract: String,
     pub eventname: String,
-    pub state: State,
+    pub state: StackItem,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct State {
-    #[serde(rename = "type")]
-    pub _type: String,
-    pub value: Vec<StateValue>,
+#[derive(Deserialize, Debug, Clone)]
+pub struct VersionResult {
+    pub useragent: String,
+    pub protocol: ProtocolResult,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct StateValue {
-    #[serde(rename = "type")]
-    pub _type: String,
-    pub value: Option<serde_json::Value>,
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProtocolResult {
+    pub network: u32,
+    pub addressversion: u8,
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize, Debug, Clone)]
+pub struct StateRootResult {
+    pub version: u8,
+    pub index: u64,
+    pub roothash: String,
+    #[serde(default)]
+    pub witnesses: Vec<Witness>,
+}
+
+#[allow(dead_code)]
+#[derive(Deserialize, Debug, Clone)]
+pub struct StateHeightResult {
+    pub localrootindex: u64,
+    pub validatedrootindex: u64,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -119,9 +143,86 @@ pub struct Witness {
     pub verification: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Signer {
     pub account: String,
     pub scopes: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub allowedcontracts: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allowedgroups: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Vec<WitnessRule>>,
+}
+
+// NotaryAssisted is a NeoGo extension, only seen on networks with the Notary service enabled
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum TransactionAttribute {
+    HighPriority,
+    OracleResponse {
+        id: u64,
+        code: String,
+        result: String,
+    },
+    NotValidBefore {
+        height: u64,
+    },
+    Conflicts {
+        hash: String,
+    },
+    NotaryAssisted {
+        nkeys: u8,
+    },
+}
+
+impl TransactionAttribute {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            TransactionAttribute::HighPriority => "HighPriority",
+            TransactionAttribute::OracleResponse { .. } => "OracleResponse",
+            TransactionAttribute::NotValidBefore { .. } => "NotValidBefore",
+            TransactionAttribute::Conflicts { .. } => "Conflicts",
+            TransactionAttribute::NotaryAssisted { .. } => "NotaryAssisted",
+        }
+    }
+
+    // what the attribute is looked up by: the oracle request id, the height or the conflicting hash
+    pub fn key(&self) -> Option<String> {
+        match self {
+            TransactionAttribute::OracleResponse { id, .. } => Some(id.to_string()),
+            TransactionAttribute::NotValidBefore { height } => Some(height.to_string()),
+            TransactionAttribute::Conflicts { hash } => Some(hash.clone()),
+            TransactionAttribute::HighPriority | TransactionAttribute::NotaryAssisted { .. } => {
+                None
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct WitnessRule {
+    pub action: WitnessRuleAction,
+    pub condition: WitnessCondition,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WitnessRuleAction {
+    Deny,
+    Allow,
+}
+
+// Not, And and Or nest other conditions
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum WitnessCondition {
+    Boolean { expression: bool },
+    Not { expression: Box<WitnessCondition> },
+    And { expressions: Vec<WitnessCondition> },
+    Or { expressions: Vec<WitnessCondition> },
+    ScriptHash { hash: String },
+    Group { group: String },
+    CalledByEntry,
+    CalledByContract { hash: String },
+    CalledByGroup { group: String },
 }
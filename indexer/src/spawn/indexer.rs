@@ -1,6 +1,6 @@
 use anyhow::Context;
-use futures::future::join_all;
-use log::{error, info};
+use log::{error, info, warn};
+use tokio::sync::watch;
 use tokio::time::sleep;
 
 use std::time::{Duration, SystemTime};
@@ -8,31 +8,59 @@ use std::time::{Duration, SystemTime};
 use crate::config::AppConfig;
 use crate::db::database::Database;
 use crate::rpc::client::Client;
-use crate::rpc::models::TransactionResult;
-use crate::utils::{conversion, logger};
+use crate::spawn::pipeline::{
+    fetch_batch, index_range, log_throughput, PipelineOptions, Throughput,
+};
+use crate::spawn::supervisor::NodeStatus;
+use crate::utils::logger;
+use crate::utils::metrics::METRICS;
+use crate::utils::state_roots::validate_state_roots;
+
+// stored state roots checked against the node after each pass of the keep-alive loop
+const STATE_ROOTS_PER_PASS: u64 = 100;
+// how often a restarted node is asked for its height until its RPC server answers
+const NODE_PROBE_MIN: Duration = Duration::from_millis(500);
+const NODE_PROBE_MAX: Duration = Duration::from_secs(10);
+
 pub struct Indexer {
     client: Client,
     db: Database,
     config: AppConfig,
+    node: watch::Receiver<NodeStatus>,
 }
 
 impl Indexer {
-    pub fn new(client: Client, db: Database, config: AppConfig) -> Self {
-        Self { client, db, config }
+    pub fn new(
+        client: Client,
+        db: Database,
+        config: AppConfig,
+        node: watch::Receiver<NodeStatus>,
+    ) -> Self {
+        Self {
+            client,
+            db,
+            config,
+            node,
+        }
     }
 
     pub async fn run(&self) -> Result<(), anyhow::Error> {
         let current_height = self.client.get_current_height().await?;
-        let stored_height = self.db.get_last_index("blocks")?;
+        let stored_height = self.db.get_stored_height()?;
         info!("Chain height is {}.", current_height);
 
+        METRICS.set_chain_height(current_height);
+        if let Some(height) = stored_height {
+            METRICS.set_stored_height(height);
+        }
+
         // Ensure chain height isn't lower than stored height
-        if current_height < stored_height {
+        if stored_height.is_some_and(|h| current_height < h) {
             error!("Chain height is lower than stored height. Exiting..");
 
             Ok(())
         } else {
-            let start_height = stored_height + 1;
+            let start_height = stored_height.map_or(0, |h| h + 1);
             let index_start = SystemTime::now();
             info!("Started indexing.");
             info!(
@@ -41,15 +69,15 @@ impl Indexer {
                 current_height - start_height
             );
 
-            self.initial_sync(start_height, current_height, self.config.batch_size)
-                .await?;
+            self.initial_sync(start_height, current_height).await?;
 
             let index_end = SystemTime::now();
             let index_duration = index_end.duration_since(index_start)?;
             let new_stored_height = self
                 .db
-                .get_last_index("blocks")
-                .context("Failed to get latest stored index")?;
+                .get_stored_height()
+                .context("Failed to get latest stored index")?
+                .ok_or_else(|| anyhow::anyhow!("No blocks stored after initial sync."))?;
             info!("Indexing completed in {} ms.", index_duration.as_millis());
             info!("New stored height is {}.", new_stored_height);
 
@@ -66,124 +94,56 @@ impl Indexer {
         &self,
         mut start_height: u64,
         current_height: u64,
-        batch_size: u64,
     ) -> Result<(), anyhow::Error> {
-        let mut count = 0;
         info!("Updating tables:");
-        while start_height < current_height {
-            let end_height = std::cmp::min(start_height + batch_size, current_height);
-
-            self.sync_between(start_height, end_height)
-                .await
-                .context("Failed to synchronize block range")?;
-
-            count += end_height - start_height;
-            start_height = end_height;
-
-            logger::inline_print(&format!("\rIndexed {count} block(s)."));
-        }
-        println!();
-        Ok(())
-    }
-
-    async fn sync_between(&self, start_height: u64, end_height: u64) -> Result<(), anyhow::Error> {
-        let future_blocks = (start_height..end_height).map(|i| self.client.fetch_full_block(i));
-        let all_blocks = join_all(future_blocks).await;
-
-        // Have to clone to keep all_blocks unmoved for future steps
-        let transactions_with_index: Vec<(TransactionResult, u64)> = all_blocks
-            .iter()
-            .filter_map(|result| {
-                if let Ok((block, _)) = result {
-                    Some(
-                        block
-                            .tx
-                            .iter()
-                            .map(move |tx| {
-                                (
-                                    TransactionResult {
-                                        hash: tx.hash.clone(),
-                                        blockhash: Some(block.hash.clone()),
-                                        size: tx.size,
-                                        version: tx.version,
-                                        nonce: tx.nonce,
-                                        sender: tx.sender.clone(),
-                                        sysfee: tx.sysfee.clone(),
-                                        netfee: tx.netfee.clone(),
-                                        validuntilblock: tx.validuntilblock,
-                                        signers: tx.signers.clone(),
-                                        script: tx.script.clone(),
-                                        witnesses: tx.witnesses.clone(),
-                                    },
-                                    block.index,
-                                )
-                            })
-                            .collect::<Vec<_>>(),
-                    )
-                } else {
-                    None
+        loop {
+            let result = self
+                .sync_between(&mut start_height, current_height, |throughput| {
+                    logger::inline_print(&format!(
+                        "\rIndexed {} block(s). {:.1} blocks/s, {:.1} tx/s.",
+                        throughput.blocks,
+                        throughput.blocks_per_second(),
+                        throughput.transactions_per_second()
+                    ))
+                })
+                .await;
+
+            match result {
+                Ok(throughput) => {
+                    println!();
+                    log_throughput(&throughput);
+                    return Ok(());
+                }
+                Err(e) if self.node_running() => {
+                    return Err(e.context("Failed to synchronize block range"))
                 }
-            })
-            .flatten()
-            .collect();
-
-        let (transactions, block_indexes): (Vec<TransactionResult>, Vec<u64>) =
-            transactions_with_index.into_iter().unzip();
-
-        let future_transactions = transactions
-            .into_iter()
-            .map(|tx| self.client.fetch_full_transaction(tx));
-        let all_transactions = join_all(future_transactions).await;
-
-        let all_transactions_with_index =
-            all_transactions.into_iter().zip(block_indexes.into_iter());
-
-        let prepped_blocks = all_blocks.into_iter().filter_map(|result| match result {
-            Ok((b, a)) => Some(conversion::convert_block_result(b, &a)),
-            Err(e) => {
-                panic!("Error fetching or converting block: {e:?}");
-            }
-        });
-
-        let prepped_tx: Vec<_> = all_transactions_with_index
-            .into_iter()
-            .filter_map(|(result, block_index)| match result {
-                Ok((t, a)) => Some(conversion::convert_transaction_result(t, &a, block_index)),
                 Err(e) => {
-                    panic!("Error fetching or converting transaction: {e:?}");
+                    println!();
+                    warn!("Node went down while indexing: {e:#}");
+                    self.wait_for_node().await?;
                 }
-            })
-            .collect();
-
-        let prepped_contracts = prepped_tx.iter().flat_map(|transaction| {
-            conversion::convert_contract_result(
-                transaction.script.clone(),
-                serde_json::from_str(&transaction.notifications).unwrap(),
-                transaction.block_index,
-            )
-        });
-
-        let prepped_addresses = prepped_tx.iter().flat_map(|transaction| {
-            conversion::convert_address_result(
-                serde_json::from_str(&transaction.notifications).unwrap(),
-                transaction.block_index,
-            )
-        });
-
-        // synced rollback point
-        self.db
-            .insert_blocks_transactions(prepped_blocks, prepped_tx.iter().cloned())
-            .context("Failed to insert data")?;
-
-        self.db
-            .insert_contracts(prepped_contracts)
-            .context("Failed to insert contracts")?;
-
-        self.db
-            .insert_addresses(prepped_addresses)
-            .context("Failed to insert addresses")?;
+            }
+        }
+    }
 
-        Ok(())
+    // moves `start_height` past everything written, also when it fails part way
+    async fn sync_between(
+        &self,
+        start_height: &mut u64,
+        end_height: u64,
+        on_batch: impl FnMut(&Throughput),
+    ) -> Result<Throughput, anyhow::Error> {
+        let options = PipelineOptions::from_config(&self.config);
+
+        index_range(
+            &self.client,
+            &self.db,
+            options,
+            start_height,
+            end_height,
+            on_batch,
+        )
+        .await
     }
 
     async fn continuous_sync(&self, start_height: u64, interval: u64) -> Result<(), anyhow::Error> {
@@ -191,14 +151,92 @@ impl Indexer {
 
         info!("Listening for new blocks:");
         loop {
-            let new_height = self.client.get_current_height().await?;
+            let new_height = match self.client.get_current_height().await {
+                Ok(height) => {
+                    METRICS.set_chain_height(height);
+                    height
+                }
+                Err(e) if !self.node_running() => {
+                    println!();
+                    warn!("Node is unavailable: {e:#}");
+                    self.wait_for_node().await?;
+                    continue;
+                }
+                Err(e) => return Err(e),
+            };
+
             if new_height > current_height {
-                self.sync_between(current_height, new_height).await?;
+                if let Err(e) = self
+                    .sync_between(&mut current_height, new_height, |_| ())
+                    .await
+                {
+                    if self.node_running() {
+                        return Err(e);
+                    }
+                    println!();
+                    warn!("Node went down while indexing: {e:#}");
+                    self.wait_for_node().await?;
+                    continue;
+                }
 
                 logger::inline_print(&format!("\rCurrent synced height: {new_height}"));
-                current_height = new_height;
+            }
+
+            // the roots are checked again on the next pass
+            if let Err(e) = validate_state_roots(&self.client, &self.db, STATE_ROOTS_PER_PASS).await
+            {
+                println!();
+                warn!("Failed to validate state roots: {e:#}");
             }
             sleep(Duration::from_secs(interval)).await;
         }
     }
+
+    fn node_running(&self) -> bool {
+        self.node.borrow().running
+    }
+
+    // The supervisor restarts the node on exit, so wait for it rather than failing. A freshly
+    // spawned node takes a while to open its RPC server, so it is polled until it answers.
+    async fn wait_for_node(&self) -> Result<(), anyhow::Error> {
+        let mut node = self.node.clone();
+        let mut backoff = NODE_PROBE_MIN;
+        info!("Waiting for node to restart..");
+        loop {
+            while !node.borrow_and_update().running {
+                node.changed().await.context("Node supervisor stopped")?;
+            }
+            if self.client.get_current_height().await.is_ok() {
+                break;
+            }
+
+            sleep(backoff).await;
+            backoff = std::cmp::min(backoff * 2, NODE_PROBE_MAX);
+        }
+        info!("Node is answering again, resuming.");
+
+        Ok(())
+    }
+}
+
+// replaces whatever is stored for [start_height, end_height) with fresh data from the node,
+// one batch per transaction so an interrupted run can simply be repeated
+pub async fn reindex_range(
+    client: &Client,
+    db: &Database,
+    start_height: u64,
+    end_height: u64,
+    batch_size: u64,
+) -> Result<(), anyhow::Error> {
+    let mut start = start_height;
+    while start < end_height {
+        let end = std::cmp::min(start + batch_size, end_height);
+        let batch = fetch_batch(client, start, end).await?;
+
+        db.replace_range(start, end, &batch)
+            .with_context(|| format!("Failed to replace blocks {start} to {}", end - 1))?;
+        start = end;
+    }
+
+    Ok(())
 }
@@ -0,0 +1,80 @@
+use anyhow::Result;
+use log::{debug, info, warn};
+use tokio::time::sleep;
+
+use std::collections::HashSet;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::db::database::Database;
+use crate::processor::mempool::{self, PendingTransaction};
+use crate::rpc::client::Client;
+
+// Polls the node's mempool and records each transaction the first time it is seen, then
+// expires the ones that can no longer be included. Confirmations are recorded by the
+// mempool processor as blocks are indexed.
+pub struct MempoolObserver {
+    client: Client,
+    db: Database,
+    interval: u64,
+    seen: HashSet<String>,
+}
+
+impl MempoolObserver {
+    pub fn new(client: Client, db: Database, interval: u64) -> Self {
+        Self {
+            client,
+            db,
+            interval,
+            seen: HashSet::new(),
+        }
+    }
+
+    // runs until aborted, a failed poll is retried on the next one
+    pub async fn run(mut self) {
+        info!("Watching the mempool.");
+        loop {
+            if let Err(e) = self.poll().await {
+                warn!("Failed to poll the mempool: {e:#}");
+            }
+            sleep(Duration::from_secs(self.interval)).await;
+        }
+    }
+
+    async fn poll(&mut self) -> Result<()> {
+        let hashes = self.client.get_raw_mempool().await?;
+        let now = unix_millis();
+
+        let mut pending = Vec::new();
+        for hash in hashes.iter().filter(|hash| !self.seen.contains(*hash)) {
+            match self.client.get_raw_transaction(hash).await {
+                Ok(transaction) => pending.push(PendingTransaction::new(transaction, now)),
+                // it left the mempool in the meantime
+                Err(e) => debug!("Failed to get pending transaction {hash}: {e:#}"),
+            }
+        }
+        // only what is still in the mempool, so the set stays small
+        self.seen = hashes.into_iter().collect();
+
+        let height = self.db.get_stored_height()?;
+        let expired = self.db.blocking(|storage| {
+            mempool::insert_pending(storage, &pending)?;
+            match height {
+                Some(height) => mempool::expire_pending(storage, height, now),
+                None => Ok(0),
+            }
+        })?;
+
+        if !pending.is_empty() || expired > 0 {
+            debug!("Mempool: {} new, {} expired.", pending.len(), expired);
+        }
+
+        Ok(())
+    }
+}
+
+fn unix_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
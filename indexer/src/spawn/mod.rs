@@ -1,2 +1,6 @@
 pub mod indexer;
-pub mod sync;
+pub mod mempool;
+pub mod pipeline;
+mod pipeline_test;
+pub mod supervisor;
+mod supervisor_test;
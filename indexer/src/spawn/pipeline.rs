@@ -0,0 +1,280 @@
+use anyhow::{anyhow, Context, Result};
+use futures::future::join_all;
+use futures::stream::{self, StreamExt};
+use log::info;
+use tokio::sync::mpsc;
+use tokio::task::{self, JoinHandle};
+
+use std::time::Instant;
+
+use crate::config::AppConfig;
+use crate::db::database::Database;
+use crate::db::model::BlockBatch;
+use crate::rpc::client::Client;
+use crate::rpc::models::{
+    BlockAppLogResult, BlockResult, StateRootResult, TransactionAppLogResult, TransactionResult,
+};
+use crate::utils::conversion;
+use crate::utils::metrics::METRICS;
+
+// node responses for a range of blocks, before conversion
+pub struct RawBatch {
+    pub blocks: Vec<(BlockResult, BlockAppLogResult)>,
+    // with the index and time of the block they are in
+    pub transactions: Vec<(TransactionResult, TransactionAppLogResult, u64, u64)>,
+    // for the blocks the node has one for
+    pub state_roots: Vec<StateRootResult>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PipelineOptions {
+    pub batch_size: u64,
+    pub fetch_workers: usize,
+    pub convert_workers: usize,
+}
+
+impl PipelineOptions {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            batch_size: config.batch_size.max(1),
+            fetch_workers: config.fetch_workers.max(1),
+            convert_workers: config.convert_workers.max(1),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Throughput {
+    pub blocks: u64,
+    pub transactions: u64,
+    pub seconds: f64,
+}
+
+impl Throughput {
+    pub fn blocks_per_second(&self) -> f64 {
+        per_second(self.blocks, self.seconds)
+    }
+
+    pub fn transactions_per_second(&self) -> f64 {
+        per_second(self.transactions, self.seconds)
+    }
+}
+
+fn per_second(count: u64, seconds: f64) -> f64 {
+    if seconds > 0.0 {
+        count as f64 / seconds
+    } else {
+        0.0
+    }
+}
+
+// splits [start, end) into consecutive batches of at most `batch_size` blocks
+pub fn batch_ranges(start: u64, end: u64, batch_size: u64) -> Vec<(u64, u64)> {
+    (start..end)
+        .step_by(batch_size as usize)
+        .map(|batch_start| (batch_start, std::cmp::min(batch_start + batch_size, end)))
+        .collect()
+}
+
+// Indexes [*next, end) in three stages joined by bounded channels: up to `fetch_workers` batches
+// are fetched at once, up to `convert_workers` are converted on the blocking pool, and a single
+// writer stores them in order. `next` is moved past every batch written, so a failed run can
+// be resumed from it.
+pub async fn index_range(
+    client: &Client,
+    db: &Database,
+    options: PipelineOptions,
+    next: &mut u64,
+    end: u64,
+    mut on_batch: impl FnMut(&Throughput),
+) -> Result<Throughput> {
+    let ranges = batch_ranges(*next, end, options.batch_size);
+
+    let (raw_sender, raw_receiver) = mpsc::channel(options.fetch_workers);
+    let fetcher = spawn_fetchers(client.clone(), ranges, options.fetch_workers, raw_sender);
+
+    let (batch_sender, mut batch_receiver) = mpsc::channel(options.convert_workers);
+    let converter = spawn_converters(raw_receiver, options.convert_workers, batch_sender);
+
+    let started = Instant::now();
+    let mut throughput = Throughput::default();
+    let result = async {
+        while let Some(result) = batch_receiver.recv().await {
+            let (batch_start, batch_end, batch) = result?;
+            let transactions = batch.transactions.len() as u64;
+
+            write_batch(db, batch)?;
+            *next = batch_end;
+
+            METRICS.blocks_indexed.inc_by(batch_end - batch_start);
+            METRICS.transactions_indexed.inc_by(transactions);
+            METRICS.set_stored_height(batch_end - 1);
+
+            throughput.blocks += batch_end - batch_start;
+            throughput.transactions += transactions;
+            throughput.seconds = started.elapsed().as_secs_f64();
+            on_batch(&throughput);
+        }
+
+        if *next < end {
+            return Err(anyhow!("Pipeline stopped at block {}", *next));
+        }
+
+        Ok(throughput)
+    }
+    .await;
+
+    // stop fetching batches that will never be written
+    fetcher.abort();
+    converter.abort();
+
+    result
+}
+
+type Sent<T> = Result<(u64, u64, T)>;
+
+fn spawn_fetchers(
+    client: Client,
+    ranges: Vec<(u64, u64)>,
+    workers: usize,
+    sender: mpsc::Sender<Sent<RawBatch>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut fetched = stream::iter(ranges)
+            .map(|(start, end)| {
+                let client = &client;
+                async move { Ok((start, end, fetch_raw(client, start, end).await?)) }
+            })
+            .buffered(workers);
+
+        while let Some(result) = fetched.next().await {
+            let failed = result.is_err();
+            if sender.send(result).await.is_err() || failed {
+                break;
+            }
+        }
+    })
+}
+
+fn spawn_converters(
+    mut receiver: mpsc::Receiver<Sent<RawBatch>>,
+    workers: usize,
+    sender: mpsc::Sender<Sent<BlockBatch>>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut converted = stream::poll_fn(move |cx| receiver.poll_recv(cx))
+            .map(|result| async move {
+                let (start, end, raw) = result?;
+                let batch = task::spawn_blocking(move || convert_raw(raw))
+                    .await
+                    .with_context(|| format!("Failed to convert blocks {start} to {}", end - 1))?;
+
+                Ok((start, end, batch))
+            })
+            .buffered(workers);
+
+        while let Some(result) = converted.next().await {
+            let failed = result.is_err();
+            if sender.send(result).await.is_err() || failed {
+                break;
+            }
+        }
+    })
+}
+
+fn write_batch(db: &Database, batch: BlockBatch) -> Result<()> {
+    // synced rollback point
+    db.insert_batch(&batch).context("Failed to insert data")
+}
+
+// fetches the blocks in [start_height, end_height), their state roots and the application logs
+// of their transactions
+pub async fn fetch_raw(client: &Client, start_height: u64, end_height: u64) -> Result<RawBatch> {
+    let blocks = join_all((start_height..end_height).map(|i| client.fetch_full_block(i)))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()
+        .context("Error fetching block")?;
+
+    let state_roots = join_all((start_height..end_height).map(|i| client.get_state_root(i)))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()
+        .context("Error fetching state root")?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let future_transactions = blocks.iter().flat_map(|(block, _)| {
+        block.tx.iter().map(move |tx| async move {
+            let tx = TransactionResult {
+                blockhash: Some(block.hash.clone()),
+                ..tx.clone()
+            };
+            let (tx, app_log) = client.fetch_full_transaction(tx).await?;
+
+            Ok((tx, app_log, block.index, block.time))
+        })
+    });
+    let transactions = join_all(future_transactions)
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()
+        .context("Error fetching transaction")?;
+
+    Ok(RawBatch {
+        blocks,
+        transactions,
+        state_roots,
+    })
+}
+
+pub fn convert_raw(raw: RawBatch) -> BlockBatch {
+    let blocks = raw
+        .blocks
+        .into_iter()
+        .map(|(block, app_log)| conversion::convert_block_result(block, &app_log))
+        .collect();
+
+    let transactions = raw
+        .transactions
+        .into_iter()
+        .map(|(tx, app_log, block_index, block_time)| {
+            conversion::convert_transaction_result(tx, &app_log, block_index, block_time)
+        })
+        .collect();
+
+    let state_roots = raw
+        .state_roots
+        .into_iter()
+        .map(conversion::convert_state_root)
+        .collect();
+
+    BlockBatch {
+        blocks,
+        transactions,
+        state_roots,
+    }
+}
+
+// fetches and converts the blocks in [start_height, end_height) without writing anything
+pub async fn fetch_batch(
+    client: &Client,
+    start_height: u64,
+    end_height: u64,
+) -> Result<BlockBatch> {
+    let raw = fetch_raw(client, start_height, end_height).await?;
+
+    Ok(convert_raw(raw))
+}
+
+pub fn log_throughput(throughput: &Throughput) {
+    info!(
+        "Indexed {} block(s) and {} transaction(s) in {:.1} s. {:.1} blocks/s, {:.1} tx/s.",
+        throughput.blocks,
+        throughput.transactions,
+        throughput.seconds,
+        throughput.blocks_per_second(),
+        throughput.transactions_per_second()
+    );
+}
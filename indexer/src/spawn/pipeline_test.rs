@@ -0,0 +1,23 @@
+#[cfg(test)]
+mod tests {
+    use crate::spawn::pipeline::{batch_ranges, Throughput};
+
+    #[test]
+    fn test_batch_ranges() {
+        assert_eq!(batch_ranges(10, 63, 25), vec![(10, 35), (35, 60), (60, 63)]);
+        assert_eq!(batch_ranges(0, 2, 25), vec![(0, 2)]);
+        assert!(batch_ranges(5, 5, 25).is_empty());
+    }
+
+    #[test]
+    fn test_throughput() {
+        let throughput = Throughput {
+            blocks: 50,
+            transactions: 120,
+            seconds: 4.0,
+        };
+        assert_eq!(throughput.blocks_per_second(), 12.5);
+        assert_eq!(throughput.transactions_per_second(), 30.0);
+        assert_eq!(Throughput::default().blocks_per_second(), 0.0);
+    }
+}
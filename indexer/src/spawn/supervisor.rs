@@ -0,0 +1,375 @@
+use log::{debug, error, info, log, warn, Level};
+use serde_json::{Map, Value};
+use tokio::fs::File;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncSeekExt, BufReader, SeekFrom};
+use tokio::process::Command;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Duration, Instant};
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+
+use crate::config::AppConfig;
+use crate::utils::metrics::METRICS;
+use crate::utils::{logger, node};
+
+// a node that stays up this long is considered healthy again and resets the backoff
+const STABLE_UPTIME: Duration = Duration::from_secs(300);
+const LOG_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const LOG_TARGET: &str = "neogo";
+
+#[derive(Debug, Clone, Default)]
+pub struct NodeStatus {
+    pub running: bool,
+    pub synchronized: bool,
+    pub block_height: u64,
+    pub header_height: u64,
+    pub restarts: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeLogLine {
+    pub level: Level,
+    pub message: String,
+    pub fields: Map<String, Value>,
+}
+
+struct SupervisorSettings {
+    node_args: Vec<String>,
+    log_path: PathBuf,
+    backoff_min: Duration,
+    backoff_max: Duration,
+    report_interval: Duration,
+}
+
+pub struct NodeSupervisor {
+    status: watch::Receiver<NodeStatus>,
+    shutdown_tx: watch::Sender<bool>,
+    handle: JoinHandle<()>,
+}
+
+impl NodeSupervisor {
+    pub fn start(config: &AppConfig) -> Self {
+        let settings = SupervisorSettings {
+            node_args: node::node_args(&config.network.node_config),
+            log_path: PathBuf::from(&config.node_log_path),
+            backoff_min: Duration::from_secs(config.node_restart_backoff_min),
+            backoff_max: Duration::from_secs(config.node_restart_backoff_max),
+            report_interval: Duration::from_secs(config.node_report_interval),
+        };
+
+        let (status_tx, status) = watch::channel(NodeStatus::default());
+        let (shutdown_tx, shutdown_rx) = watch::channel(false);
+        let handle = tokio::spawn(supervise(settings, Arc::new(status_tx), shutdown_rx));
+
+        Self {
+            status,
+            shutdown_tx,
+            handle,
+        }
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<NodeStatus> {
+        self.status.clone()
+    }
+
+    // blocks until the node reports it is synchronized or passes the height limit
+    pub async fn wait_for_sync(&mut self, max_height: u64) -> Result<(), anyhow::Error> {
+        loop {
+            {
+                let status = self.status.borrow_and_update();
+                if status.header_height > 0 {
+                    logger::inline_print(&format!("\rCurrent height: {}", status.header_height));
+                }
+
+                if max_height != 0 && status.header_height >= max_height {
+                    println!();
+                    warn!("Exceeded target height.");
+                    return Ok(());
+                }
+
+                if status.synchronized {
+                    println!();
+                    return Ok(());
+                }
+            }
+
+            self.status
+                .changed()
+                .await
+                .map_err(|_| anyhow::anyhow!("Node supervisor stopped unexpectedly."))?;
+        }
+    }
+
+    pub async fn shutdown(self) -> Result<(), anyhow::Error> {
+        let _ = self.shutdown_tx.send(true);
+        self.handle.await?;
+
+        Ok(())
+    }
+}
+
+async fn supervise(
+    settings: SupervisorSettings,
+    status_tx: Arc<watch::Sender<NodeStatus>>,
+    mut shutdown_rx: watch::Receiver<bool>,
+) {
+    let mut backoff = settings.backoff_min;
+    let reporter = tokio::spawn(report_heights(
+        status_tx.subscribe(),
+        settings.report_interval,
+    ));
+
+    loop {
+        // only read log lines written by this instance of the node
+        let log_offset = tokio::fs::metadata(&settings.log_path)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        let started = Instant::now();
+
+        let spawned = Command::new(node::NEOGO_PATH)
+            .args(&settings.node_args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn();
+
+        match spawned {
+            Ok(mut child) => {
+                status_tx.send_modify(|s| {
+                    s.running = true;
+                    s.synchronized = false;
+                });
+
+                let mut forwarders = vec![tokio::spawn(tail_log(
+                    settings.log_path.clone(),
+                    log_offset,
+                    status_tx.clone(),
+                ))];
+                if let Some(stdout) = child.stdout.take() {
+                    forwarders.push(tokio::spawn(forward_output(stdout, Level::Debug)));
+                }
+                if let Some(stderr) = child.stderr.take() {
+                    forwarders.push(tokio::spawn(forward_output(stderr, Level::Error)));
+                }
+
+                let exit = tokio::select! {
+                    exit = child.wait() => Some(exit),
+                    _ = shutdown_rx.changed() => None,
+                };
+
+                for forwarder in forwarders {
+                    forwarder.abort();
+                }
+                status_tx.send_modify(|s| {
+                    s.running = false;
+                    s.synchronized = false;
+                });
+
+                match exit {
+                    Some(Ok(code)) => error!("NeoGo exited unexpectedly ({code})."),
+                    Some(Err(e)) => error!("Failed to wait on NeoGo: {e}"),
+                    None => {
+                        warn!("Shutdown signal received.");
+                        let _ = child.kill().await;
+                        warn!("Node killed.");
+                        reporter.abort();
+                        return;
+                    }
+                }
+            }
+            Err(e) => error!("Failed to run node: {e}"),
+        }
+
+        if started.elapsed() >= STABLE_UPTIME {
+            backoff = settings.backoff_min;
+        }
+
+        status_tx.send_modify(|s| s.restarts += 1);
+        METRICS.node_restarts.inc();
+        warn!("Restarting NeoGo in {} s.", backoff.as_secs());
+
+        tokio::select! {
+            _ = sleep(backoff) => {},
+            _ = shutdown_rx.changed() => {
+                reporter.abort();
+                return;
+            }
+        }
+        backoff = std::cmp::min(backoff * 2, settings.backoff_max);
+    }
+}
+
+async fn tail_log(path: PathBuf, offset: u64, status_tx: Arc<watch::Sender<NodeStatus>>) {
+    // Wait for log file to be created
+    while !path.exists() {
+        sleep(LOG_POLL_INTERVAL).await;
+    }
+
+    let mut file = match open_at(&path, offset).await {
+        Ok(file) => BufReader::new(file),
+        Err(e) => {
+            error!("Failed to open node log at {}: {e}", path.display());
+            return;
+        }
+    };
+
+    let mut line = String::new();
+    loop {
+        match file.read_line(&mut line).await {
+            // EOF or a line that is still being written
+            Ok(0) => sleep(LOG_POLL_INTERVAL).await,
+            Ok(_) if !line.ends_with('\n') => sleep(LOG_POLL_INTERVAL).await,
+            Ok(_) => {
+                if let Some(parsed) = parse_log_line(&line) {
+                    update_status(&status_tx, &parsed);
+                    forward_log_line(&parsed);
+                }
+                line.clear();
+            }
+            Err(e) => {
+                error!("Failed to read node log: {e}");
+                return;
+            }
+        }
+    }
+}
+
+async fn open_at(path: &Path, offset: u64) -> Result<File, std::io::Error> {
+    let mut file = File::open(path).await?;
+    file.seek(SeekFrom::Start(offset)).await?;
+
+    Ok(file)
+}
+
+async fn forward_output(output: impl AsyncRead + Unpin, level: Level) {
+    let mut lines = BufReader::new(output).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if !line.trim().is_empty() {
+            log!(target: LOG_TARGET, level, "{}", line.trim_end());
+        }
+    }
+}
+
+async fn report_heights(mut status_rx: watch::Receiver<NodeStatus>, interval: Duration) {
+    loop {
+        sleep(interval).await;
+
+        let status = status_rx.borrow_and_update().clone();
+        if status.running && status.synchronized {
+            info!(
+                "Node height {} / header height {} ({} behind, {} restart(s)).",
+                status.block_height,
+                status.header_height,
+                status.header_height.saturating_sub(status.block_height),
+                status.restarts
+            );
+        } else if !status.running {
+            warn!("Node is not running ({} restart(s)).", status.restarts);
+        }
+    }
+}
+
+fn update_status(status_tx: &watch::Sender<NodeStatus>, line: &NodeLogLine) {
+    let header_height = line.fields.get("headerHeight").and_then(Value::as_u64);
+    let block_height = line.fields.get("blockHeight").and_then(Value::as_u64);
+    let synchronized = line.message.contains("synchronized");
+
+    if header_height.is_none() && block_height.is_none() && !synchronized {
+        return;
+    }
+
+    status_tx.send_modify(|s| {
+        if let Some(height) = header_height {
+            s.header_height = height;
+        }
+        if let Some(height) = block_height {
+            s.block_height = height;
+        }
+        if synchronized {
+            s.synchronized = true;
+        }
+    });
+}
+
+fn forward_log_line(line: &NodeLogLine) {
+    if line.fields.is_empty() {
+        log!(target: LOG_TARGET, line.level, "{}", line.message);
+    } else {
+        log!(
+            target: LOG_TARGET,
+            line.level,
+            "{} {}",
+            line.message,
+            Value::Object(line.fields.clone())
+        );
+    }
+}
+
+// NeoGo writes either JSON lines or tab separated console lines, depending on its log encoding
+pub fn parse_log_line(line: &str) -> Option<NodeLogLine> {
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+
+    if line.starts_with('{') {
+        if let Ok(Value::Object(mut fields)) = serde_json::from_str::<Value>(line) {
+            let level = fields
+                .remove("level")
+                .and_then(|l| l.as_str().and_then(parse_level))
+                .unwrap_or(Level::Info);
+            let message = fields
+                .remove("msg")
+                .and_then(|m| m.as_str().map(str::to_string))
+                .unwrap_or_default();
+            fields.remove("ts");
+            fields.remove("caller");
+
+            return Some(NodeLogLine {
+                level,
+                message,
+                fields,
+            });
+        }
+    }
+
+    let parts: Vec<&str> = line.split('\t').collect();
+    let level_position = parts.iter().position(|p| parse_level(p).is_some());
+
+    match level_position {
+        Some(position) => {
+            let level = parse_level(parts[position]).unwrap_or(Level::Info);
+            let message = parts.get(position + 1).unwrap_or(&"").to_string();
+            let fields = parts
+                .last()
+                .filter(|_| parts.len() > position + 2)
+                .and_then(|f| serde_json::from_str::<Map<String, Value>>(f).ok())
+                .unwrap_or_default();
+
+            Some(NodeLogLine {
+                level,
+                message,
+                fields,
+            })
+        }
+        None => {
+            debug!(target: LOG_TARGET, "Unrecognized node log line: {line}");
+            None
+        }
+    }
+}
+
+fn parse_level(level: &str) -> Option<Level> {
+    match level.trim().to_ascii_lowercase().as_str() {
+        "debug" => Some(Level::Debug),
+        "info" => Some(Level::Info),
+        "warn" | "warning" => Some(Level::Warn),
+        "error" | "dpanic" | "panic" | "fatal" => Some(Level::Error),
+        _ => None,
+    }
+}
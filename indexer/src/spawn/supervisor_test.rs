@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    use crate::spawn::supervisor::parse_log_line;
+    use log::Level;
+
+    #[test]
+    fn test_parse_json_log_line() {
+        let line = r#"{"level":"info","ts":"2023-05-10T12:00:00.000Z","msg":"persisted to disk","blocks":1,"keys":120,"headerHeight":4677439,"blockHeight":4677438,"took":"1.2ms"}"#;
+
+        let parsed = parse_log_line(line).unwrap();
+
+        assert_eq!(parsed.level, Level::Info);
+        assert_eq!(parsed.message, "persisted to disk");
+        assert_eq!(parsed.fields["headerHeight"], 4677439);
+        assert_eq!(parsed.fields["blockHeight"], 4677438);
+        assert!(!parsed.fields.contains_key("ts"));
+    }
+
+    #[test]
+    fn test_parse_console_log_line() {
+        let line = "2023-05-10T12:00:00.000Z\tWARN\tpeer disconnected\t{\"addr\": \"1.2.3.4:10333\", \"peerCount\": 9}\n";
+
+        let parsed = parse_log_line(line).unwrap();
+
+        assert_eq!(parsed.level, Level::Warn);
+        assert_eq!(parsed.message, "peer disconnected");
+        assert_eq!(parsed.fields["peerCount"], 9);
+
+        let synced = parse_log_line(
+            "2023-05-10T12:00:00.000Z\tINFO\tnode reached synchronized state, starting services",
+        )
+        .unwrap();
+        assert_eq!(synced.level, Level::Info);
+        assert!(synced.message.contains("synchronized"));
+        assert!(synced.fields.is_empty());
+    }
+
+    #[test]
+    fn test_parse_unrecognized_log_line() {
+        assert!(parse_log_line("").is_none());
+        assert!(parse_log_line("/NEO-GO:0.107.2/").is_none());
+    }
+}
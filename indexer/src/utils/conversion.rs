@@ -1,27 +1,38 @@
-use lib::neo::{
-    base64_to_address, base64_to_hex, base64_to_script_hash, hex_decode, hex_to_base64,
-    neo3_disassemble,
-};
+use lib::binary::Serializable;
+use lib::integer;
+use lib::nef::NefFile;
+use lib::neo::{base64_to_hex, hex_decode, hex_to_base64, neo3_disassemble};
+use lib::network::CONTRACT_MANAGEMENT;
+use lib::stack_item::{BigInt, StackItem};
 use serde_json::to_string;
 
-use crate::db::model::{Address, Block, Contract, Transaction};
+use crate::db::model::{Address, Block, Contract, StateRoot, Transaction};
 use crate::rpc::models::{
-    BlockAppLogResult, BlockResult, TransactionAppLogResult, TransactionResult,
+    BlockAppLogResult, BlockResult, Notification, StateRootResult, TransactionAppLogResult,
+    TransactionResult,
 };
 
 pub fn convert_block_result(r: BlockResult, a: &BlockAppLogResult) -> Block {
-    let block_reward = &a.executions[1].notifications[0].state.value[2].value;
-    let block_receiver = &a.executions[1].notifications[0].state.value[1].value;
+    // the GAS minted to the primary node in PostPersist
+    let reward_state = a
+        .executions
+        .get(1)
+        .and_then(|execution| execution.notifications.first())
+        .and_then(|notification| notification.state.as_array())
+        .unwrap_or_default();
 
-    let reward_string = block_reward.clone().unwrap().as_str().unwrap().to_string();
-    let reward = reward_string.parse::<u64>().unwrap();
-    let reward_as_float = reward as f64 / 100_000_000_f64;
+    let reward = reward_state
+        .get(2)
+        .and_then(StackItem::as_integer)
+        .unwrap_or_default();
 
-    let receiver = serde_json::to_string(block_receiver).unwrap();
-    let stripped = &receiver[1..29];
-    let address = base64_to_address(stripped);
+    let address = reward_state
+        .get(1)
+        .and_then(StackItem::as_address)
+        .unwrap_or_default();
 
     Block {
+        index: r.index,
         hash: r.hash,
         size: r.size,
         version: r.version,
@@ -30,20 +41,43 @@ pub fn convert_block_result(r: BlockResult, a: &BlockAppLogResult) -> Block {
         nonce: r.nonce,
         speaker: r.primary,
         next_consensus: r.nextconsensus,
-        reward: reward_as_float,
+        reward,
         reward_receiver: address,
         witnesses: to_string(&r.witnesses).unwrap(),
     }
 }
 
+// a root is validated once state validators have signed it
+pub fn convert_state_root(r: StateRootResult) -> StateRoot {
+    StateRoot {
+        index: r.index,
+        root_hash: r.roothash,
+        validated: !r.witnesses.is_empty(),
+        witnesses: to_string(&r.witnesses).unwrap(),
+    }
+}
+
 pub fn convert_transaction_result(
     t: TransactionResult,
     a: &TransactionAppLogResult,
     block_height: u64,
+    block_time: u64,
 ) -> Transaction {
-    let state = &a.executions[0].vmstate;
-    let stack = &a.executions[0].stack;
-    let notifs = &a.executions[0].notifications;
+    // a transaction runs once, in the Application trigger
+    let execution = a
+        .executions
+        .iter()
+        .find(|execution| execution.trigger == "Application")
+        .or(a.executions.first());
+
+    let (state, stack, notifs) = match execution {
+        Some(execution) => (
+            execution.vmstate.as_str(),
+            execution.stack.as_slice(),
+            execution.notifications.as_slice(),
+        ),
+        None => ("NONE", [].as_slice(), [].as_slice()),
+    };
 
     Transaction {
         hash: t.hash,
@@ -61,88 +95,168 @@ pub fn convert_transaction_result(
         witnesses: to_string(&t.witnesses).unwrap(),
         stack_result: to_string(&stack).unwrap(),
         notifications: to_string(&notifs).unwrap(),
+        block_time,
+        exception: execution.and_then(|execution| execution.exception.clone()),
+        gas_consumed: execution.map_or_else(
+            || "0".to_string(),
+            |execution| execution.gasconsumed.clone(),
+        ),
+        attributes: to_string(&t.attributes).unwrap(),
     }
 }
 
-pub fn convert_contract_result(
-    script: String,
-    notifications: serde_json::Value,
+// a contract for ContractManagement's Deploy event, None for any other notification
+pub fn convert_deploy_notification(
+    script: &str,
+    notification: &Notification,
     block_height: u64,
-) -> Vec<Contract> {
-    let mut contracts = Vec::new();
+) -> Option<Contract> {
+    if notification.eventname != "Deploy" || notification.contract != CONTRACT_MANAGEMENT {
+        return None;
+    }
 
-    for notification in notifications.as_array().unwrap() {
-        if notification["eventname"] == "Deploy"
-            && notification["contract"] == "0xfffdc93764dbaddd97c48f252a53ea4643faa3fd"
-        {
-            let full_disassembled_script = neo3_disassemble(&hex_to_base64(&script));
-            let disassembled_script: Vec<&str> = full_disassembled_script.split("\n").collect();
+    let contract_script_hash = notification.state.as_array()?.first()?.as_script_hash()?;
 
-            let mut contract_supported_standard: String = "[]".to_string();
+    let contract_supported_standard = match deploy_manifest(script) {
+        Some(manifest) => manifest["supportedstandards"].to_string(),
+        None => "[]".to_string(),
+    };
 
-            if let Some(data) = disassembled_script
-                .iter()
-                .find(|&s| s.contains("PUSHDATA2"))
-            {
-                let parts: Vec<&str> = data.split_whitespace().collect();
-                let metadata_hex = parts.get(1).unwrap_or(&"");
-                let metadata_hex_decoded = hex_decode(metadata_hex);
-                let metadata = String::from_utf8(metadata_hex_decoded).unwrap();
+    Some(Contract {
+        block_index: block_height,
+        hash: contract_script_hash,
+        contract_type: contract_supported_standard,
+    })
+}
 
-                if metadata.starts_with("{") {
-                    let metadata_json: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+// The manifest pushed by a ContractManagement deploy or update script, the first pushed data
+// that is a JSON object. None when the contract was deployed some other way, e.g. by a factory.
+pub fn deploy_manifest(script: &str) -> Option<serde_json::Value> {
+    let disassembled_script = neo3_disassemble(&hex_to_base64(script));
 
-                    contract_supported_standard = metadata_json["supportedstandards"].to_string();
-                }
-            }
+    disassembled_script
+        .lines()
+        .filter(|line| line.starts_with("PUSHDATA"))
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .filter_map(|data| String::from_utf8(hex_decode(data)).ok())
+        .filter(|data| data.starts_with('{'))
+        .find_map(|data| serde_json::from_str(&data).ok())
+}
 
-            let contract_hash_base64 = notification["state"]["value"][0]["value"].clone();
-            let contract_script_hash =
-                base64_to_script_hash(contract_hash_base64.as_str().unwrap());
+// The NEF pushed by the same scripts, the first pushed data that parses as one
+pub fn deploy_nef(script: &str) -> Option<NefFile> {
+    let disassembled_script = neo3_disassemble(&hex_to_base64(script));
 
-            contracts.push(Contract {
-                block_index: block_height,
-                hash: contract_script_hash,
-                contract_type: contract_supported_standard,
-            });
-        }
-    }
+    disassembled_script
+        .lines()
+        .filter(|line| line.starts_with("PUSHDATA"))
+        .filter_map(|line| line.split_whitespace().nth(1))
+        .map(hex_decode)
+        .filter(|data| data.starts_with(b"NEF3"))
+        .find_map(|data| NefFile::from_bytes(&data).ok())
+}
 
-    return contracts;
+// System.Contract.Call, as the disassembler prints its interop hash
+const SYSTEM_CONTRACT_CALL: &str = "1381727586";
+
+// A call made by a transaction script with the arguments it pushed. Only the calls the script
+// makes itself are seen, not the ones the called contracts make in turn.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContractCall {
+    pub contract: String,
+    pub method: String,
+    pub args: Vec<StackItem>,
 }
 
-pub fn convert_address_result(notifications: serde_json::Value, block_height: u64) -> Vec<Address> {
-    let mut addresses = Vec::new();
-
-    for notification in notifications.as_array().unwrap() {
-        if notification["eventname"] == "Transfer" {
-            let state = notification["state"].clone();
-
-            if let (Some(sender_type), Some(recipient_type)) = (
-                state["value"][0]["type"].as_str(),
-                state["value"][1]["type"].as_str(),
-            ) {
-                if sender_type == "ByteString" && recipient_type == "ByteString" {
-                    let sender_address =
-                        base64_to_address(state["value"][0]["value"].as_str().unwrap());
-                    let recipient_address =
-                        base64_to_address(state["value"][1]["value"].as_str().unwrap());
-
-                    addresses.push(Address {
-                        block_index: block_height,
-                        address: sender_address,
-                        balances: "{}".to_string(),
-                    });
+// Follows the constant pushes of a script the way ScriptBuilder emits them for
+// `contract.method(args)`. Any other instruction leaves the stack unknown, so calls whose
+// arguments are computed are skipped.
+pub fn contract_calls(script: &str) -> Vec<ContractCall> {
+    let disassembled_script = neo3_disassemble(&hex_to_base64(script));
+    let mut stack: Vec<StackItem> = Vec::new();
+    let mut calls = Vec::new();
+
+    for line in disassembled_script.lines() {
+        let (opcode, data) = line.split_once(' ').unwrap_or((line, ""));
+
+        match opcode {
+            "PUSHDATA1" | "PUSHDATA2" | "PUSHDATA4" => {
+                stack.push(StackItem::ByteString(hex_decode(data)))
+            }
+            "PUSHINT8" | "PUSHINT16" | "PUSHINT32" | "PUSHINT64" | "PUSHINT128" | "PUSHINT256" => {
+                stack.push(StackItem::Integer(integer::decode(&hex_decode(data))))
+            }
+            "PUSHM1" => stack.push(StackItem::Integer(BigInt::from(-1))),
+            "PUSHT" => stack.push(StackItem::Boolean(true)),
+            "PUSHF" => stack.push(StackItem::Boolean(false)),
+            "PUSHNULL" => stack.push(StackItem::Any),
+            "NEWARRAY0" => stack.push(StackItem::Array(Vec::new())),
+            "PACK" => {
+                let items = stack
+                    .pop()
+                    .and_then(|count| usize::try_from(count.as_integer()?).ok())
+                    .filter(|count| *count <= stack.len())
+                    .map(|count| stack.split_off(stack.len() - count));
+
+                match items {
+                    // the first argument is pushed last
+                    Some(items) => stack.push(StackItem::Array(items.into_iter().rev().collect())),
+                    None => stack.clear(),
+                }
+            }
+            "SYSCALL" if data == SYSTEM_CONTRACT_CALL => {
+                let contract = stack.pop().and_then(|item| item.as_script_hash());
+                let method = stack.pop().and_then(|item| item.as_utf8());
+                let _flags = stack.pop();
+                let args = stack
+                    .pop()
+                    .and_then(|item| item.as_array().map(<[_]>::to_vec));
 
-                    addresses.push(Address {
-                        block_index: block_height,
-                        address: recipient_address,
-                        balances: "{}".to_string(),
+                if let (Some(contract), Some(method), Some(args)) = (contract, method, args) {
+                    calls.push(ContractCall {
+                        contract,
+                        method,
+                        args,
                     });
                 }
+                stack.clear();
             }
+            _ => match opcode
+                .strip_prefix("PUSH")
+                .and_then(|n| n.parse::<u8>().ok())
+            {
+                Some(n) => stack.push(StackItem::Integer(BigInt::from(n))),
+                None => stack.clear(),
+            },
         }
     }
 
-    return addresses;
+    calls
+}
+
+// the sender and recipient of a Transfer event, nothing for mints, burns or other notifications
+pub fn convert_transfer_notification(
+    notification: &Notification,
+    block_height: u64,
+) -> Vec<Address> {
+    if notification.eventname != "Transfer" {
+        return Vec::new();
+    }
+
+    let state = notification.state.as_array().unwrap_or_default();
+    let (Some(sender), Some(recipient)) = (
+        state.first().and_then(StackItem::as_address),
+        state.get(1).and_then(StackItem::as_address),
+    ) else {
+        return Vec::new();
+    };
+
+    [sender, recipient]
+        .into_iter()
+        .map(|address| Address {
+            block_index: block_height,
+            address,
+            balances: "{}".to_string(),
+        })
+        .collect()
 }
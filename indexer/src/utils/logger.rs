@@ -6,18 +6,23 @@ use crate::config::AppConfig;
 
 pub fn init() {
     let config = AppConfig::new();
-    let level = match config.log_level.as_str() {
+
+    // NeoGo output is forwarded under its own target so it can be filtered separately
+    env_logger::builder()
+        .filter_level(level_filter(&config.log_level))
+        .filter_module("neogo", level_filter(&config.node_log_level))
+        .format_timestamp(None)
+        .init();
+}
+
+fn level_filter(level: &str) -> LevelFilter {
+    match level {
         "debug" => LevelFilter::Debug,
         "info" => LevelFilter::Info,
         "warn" => LevelFilter::Warn,
         "error" => LevelFilter::Error,
         _ => LevelFilter::Off,
-    };
-
-    env_logger::builder()
-        .filter_level(level)
-        .format_timestamp(None)
-        .init();
+    }
 }
 
 pub fn inline_print(message: &str) {
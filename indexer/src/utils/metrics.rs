@@ -0,0 +1,173 @@
+use anyhow::{Context, Result};
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{header, Body, Method, Request, Response, Server, StatusCode};
+use log::{error, info};
+use once_cell::sync::Lazy;
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, Opts, Registry,
+    TextEncoder,
+};
+use tokio::task::JoinHandle;
+
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::time::Instant;
+
+pub static METRICS: Lazy<Metrics> = Lazy::new(Metrics::new);
+
+pub struct Metrics {
+    registry: Registry,
+    pub stored_height: IntGauge,
+    pub chain_height: IntGauge,
+    pub lag: IntGauge,
+    pub blocks_indexed: IntCounter,
+    pub transactions_indexed: IntCounter,
+    pub rpc_duration: HistogramVec,
+    pub rpc_errors: IntCounterVec,
+    pub db_write_duration: HistogramVec,
+    pub node_restarts: IntCounter,
+    pub state_root_mismatches: IntCounter,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some(String::from("shrike")), None)
+            .expect("Failed to create metrics registry");
+
+        let metrics = Self {
+            stored_height: IntGauge::new("stored_height", "Highest block stored").unwrap(),
+            chain_height: IntGauge::new("chain_height", "Block count reported by the node")
+                .unwrap(),
+            lag: IntGauge::new("lag_blocks", "Blocks the node has that are not stored yet")
+                .unwrap(),
+            blocks_indexed: IntCounter::new("blocks_indexed_total", "Blocks written").unwrap(),
+            transactions_indexed: IntCounter::new(
+                "transactions_indexed_total",
+                "Transactions written",
+            )
+            .unwrap(),
+            rpc_duration: HistogramVec::new(
+                HistogramOpts::new("rpc_duration_seconds", "Node RPC latency by method"),
+                &["method"],
+            )
+            .unwrap(),
+            rpc_errors: IntCounterVec::new(
+                Opts::new("rpc_errors_total", "Failed node RPC calls by method"),
+                &["method"],
+            )
+            .unwrap(),
+            db_write_duration: HistogramVec::new(
+                HistogramOpts::new("db_write_duration_seconds", "Database write time by table"),
+                &["table"],
+            )
+            .unwrap(),
+            node_restarts: IntCounter::new("node_restarts_total", "NeoGo restarts").unwrap(),
+            state_root_mismatches: IntCounter::new(
+                "state_root_mismatches_total",
+                "Stored state roots the node no longer agrees with",
+            )
+            .unwrap(),
+            registry,
+        };
+
+        metrics.register().expect("Failed to register metrics");
+        metrics
+    }
+
+    fn register(&self) -> prometheus::Result<()> {
+        self.registry
+            .register(Box::new(self.stored_height.clone()))?;
+        self.registry
+            .register(Box::new(self.chain_height.clone()))?;
+        self.registry.register(Box::new(self.lag.clone()))?;
+        self.registry
+            .register(Box::new(self.blocks_indexed.clone()))?;
+        self.registry
+            .register(Box::new(self.transactions_indexed.clone()))?;
+        self.registry
+            .register(Box::new(self.rpc_duration.clone()))?;
+        self.registry.register(Box::new(self.rpc_errors.clone()))?;
+        self.registry
+            .register(Box::new(self.db_write_duration.clone()))?;
+        self.registry
+            .register(Box::new(self.node_restarts.clone()))?;
+        self.registry
+            .register(Box::new(self.state_root_mismatches.clone()))?;
+
+        Ok(())
+    }
+
+    pub fn set_stored_height(&self, height: u64) {
+        self.stored_height.set(height as i64);
+        self.update_lag();
+    }
+
+    pub fn set_chain_height(&self, height: u64) {
+        self.chain_height.set(height as i64);
+        self.update_lag();
+    }
+
+    // the block count is one past the last block
+    fn update_lag(&self) {
+        let lag = self.chain_height.get() - 1 - self.stored_height.get();
+        self.lag.set(lag.max(0));
+    }
+
+    pub fn observe_rpc(&self, method: &str, started: Instant, ok: bool) {
+        self.rpc_duration
+            .with_label_values(&[method])
+            .observe(started.elapsed().as_secs_f64());
+        if !ok {
+            self.rpc_errors.with_label_values(&[method]).inc();
+        }
+    }
+
+    pub fn observe_write(&self, table: &str, started: Instant) {
+        self.db_write_duration
+            .with_label_values(&[table])
+            .observe(started.elapsed().as_secs_f64());
+    }
+
+    pub fn render(&self) -> String {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .expect("Failed to encode metrics");
+
+        String::from_utf8(buffer).expect("Metrics are not valid UTF-8")
+    }
+}
+
+// Binds the endpoint up front so a bad address fails at startup, then serves GET /metrics
+// in the Prometheus text format in the background
+pub fn serve(address: &str) -> Result<JoinHandle<()>> {
+    let address: SocketAddr = address
+        .parse()
+        .with_context(|| format!("Invalid metrics address {address}"))?;
+
+    let make_service =
+        make_service_fn(|_| async { Ok::<_, Infallible>(service_fn(handle_request)) });
+    let server = Server::try_bind(&address)
+        .with_context(|| format!("Failed to bind metrics endpoint to {address}"))?
+        .serve(make_service);
+    info!("Serving metrics at http://{}/metrics.", address);
+
+    Ok(tokio::spawn(async move {
+        if let Err(e) = server.await {
+            error!("Metrics endpoint failed: {e}");
+        }
+    }))
+}
+
+async fn handle_request(request: Request<Body>) -> Result<Response<Body>, Infallible> {
+    let response = match (request.method(), request.uri().path()) {
+        (&Method::GET, "/metrics") => Response::builder()
+            .header(header::CONTENT_TYPE, TextEncoder::new().format_type())
+            .body(Body::from(METRICS.render())),
+        _ => Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::empty()),
+    };
+
+    Ok(response.expect("Failed to build metrics response"))
+}
@@ -0,0 +1,32 @@
+#[cfg(test)]
+mod tests {
+    use crate::utils::metrics::METRICS;
+
+    use std::time::Instant;
+
+    #[test]
+    fn test_render() {
+        METRICS.set_stored_height(90);
+        METRICS.set_chain_height(101);
+        assert_eq!(METRICS.lag.get(), 10);
+
+        // a stored height ahead of the node isn't negative lag
+        METRICS.set_stored_height(200);
+        assert_eq!(METRICS.lag.get(), 0);
+
+        METRICS.observe_rpc("getblock", Instant::now(), false);
+        // a label no other test writes, they share the registry
+        METRICS.observe_write("metrics_test", Instant::now());
+
+        let rendered = METRICS.render();
+        assert!(rendered.contains("shrike_stored_height 200"));
+        assert!(rendered.contains("shrike_chain_height 101"));
+        assert!(rendered.contains("shrike_rpc_errors_total{method=\"getblock\"} 1"));
+        assert!(rendered.contains("shrike_rpc_duration_seconds_count{method=\"getblock\"} 1"));
+        assert!(
+            rendered.contains("shrike_db_write_duration_seconds_count{table=\"metrics_test\"} 1")
+        );
+        assert!(rendered.contains("shrike_node_restarts_total 0"));
+        assert!(rendered.contains("shrike_state_root_mismatches_total"));
+    }
+}
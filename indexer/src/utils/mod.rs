@@ -1,4 +1,14 @@
 pub mod conversion;
+mod conversion_test;
 pub mod logger;
+pub mod metrics;
+mod metrics_test;
+pub mod network;
 pub mod node;
-mod conversion_test;
+mod node_test;
+pub mod reindex;
+mod reindex_test;
+pub mod state_roots;
+mod state_roots_test;
+pub mod verify;
+mod verify_test;
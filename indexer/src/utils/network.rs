@@ -0,0 +1,94 @@
+use anyhow::Context;
+use log::info;
+
+use crate::config::AppConfig;
+use crate::db::database::Database;
+use crate::db::model::NetworkMetadata;
+use crate::rpc::client::Client;
+
+// makes sure the node and the database agree with the selected network profile
+pub async fn check_network(
+    client: &Client,
+    db: &Database,
+    config: &AppConfig,
+) -> Result<(), anyhow::Error> {
+    let profile = &config.network;
+    let version = client
+        .get_version()
+        .await
+        .context("Failed to get node version")?;
+    info!("Connected to {}.", version.useragent.trim_matches('/'));
+
+    if let Some(magic) = profile.magic {
+        if version.protocol.network != magic {
+            return Err(anyhow::anyhow!(
+                "Node is running network magic {}, but {} expects {}.",
+                version.protocol.network,
+                profile.name,
+                magic
+            ));
+        }
+    }
+
+    if version.protocol.addressversion != profile.address_version {
+        return Err(anyhow::anyhow!(
+            "Node uses address version {}, but {} expects {}.",
+            version.protocol.addressversion,
+            profile.name,
+            profile.address_version
+        ));
+    }
+
+    let genesis = client
+        .get_block(0)
+        .await
+        .context("Failed to get genesis block")?;
+
+    let node_metadata = NetworkMetadata {
+        name: profile.name.clone(),
+        magic: version.protocol.network,
+        address_version: version.protocol.addressversion,
+        genesis_hash: genesis.hash,
+    };
+
+    match db.get_network_metadata()? {
+        Some(stored) if stored == node_metadata => {
+            info!(
+                "Database belongs to {} (magic {}).",
+                stored.name, stored.magic
+            );
+        }
+        Some(stored) => {
+            return Err(anyhow::anyhow!(
+                "Database belongs to {} (magic {}, genesis {}), but the node is on {} (magic {}, genesis {}).",
+                stored.name,
+                stored.magic,
+                stored.genesis_hash,
+                node_metadata.name,
+                node_metadata.magic,
+                node_metadata.genesis_hash
+            ));
+        }
+        None => {
+            // databases created before networks were tracked already hold the genesis block
+            if let Some(stored_genesis) = db.get_block_hash(0)? {
+                if stored_genesis != node_metadata.genesis_hash {
+                    return Err(anyhow::anyhow!(
+                        "Stored genesis block {} does not match the node's genesis block {}.",
+                        stored_genesis,
+                        node_metadata.genesis_hash
+                    ));
+                }
+            }
+
+            db.insert_network_metadata(&node_metadata)
+                .context("Failed to record network metadata")?;
+            info!(
+                "Recorded database network as {} (magic {}).",
+                node_metadata.name, node_metadata.magic
+            );
+        }
+    }
+
+    Ok(())
+}
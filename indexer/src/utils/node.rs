@@ -1,9 +1,10 @@
-use log::{error, info, warn};
-use text_io::read;
-use tokio::{fs::File, io::AsyncWriteExt};
+use lib::network::NodeConfig;
+use log::{info, warn};
+use sha2::{Digest, Sha256};
+use tokio::{fs, io::AsyncWriteExt};
 
 use crate::config::AppConfig;
-use std::{env, path::Path, process::Command};
+use std::{env, path::Path, path::PathBuf, process::Command};
 
 // move these to config in future
 #[cfg(target_os = "linux")]
@@ -13,98 +14,228 @@ pub static NEOGO_PATH: &str = "./neogo";
 #[cfg(target_os = "windows")]
 pub static NEOGO_PATH: &str = "./neogo.exe";
 
+static NEOGO_RELEASES: &str = "https://github.com/nspcc-dev/neo-go/releases";
+
+// sha256 sums of the release assets we are willing to install, one `<sha256>  <version>/<asset>` per line
+pub static PINNED_CHECKSUMS: &str = include_str!("../../config/neogo.sha256");
+
+#[derive(Debug, PartialEq)]
+pub enum NodeSource {
+    // base URL laid out like the GitHub releases, i.e. `{base}/{version}/{asset}`
+    Remote(String),
+    // a NeoGo binary, or a directory holding release assets
+    Local(PathBuf),
+}
+
+impl NodeSource {
+    pub fn from_config(source: Option<&str>) -> Self {
+        match source {
+            Some(s) if s.starts_with("http://") || s.starts_with("https://") => {
+                NodeSource::Remote(s.trim_end_matches('/').to_string())
+            }
+            Some(s) => NodeSource::Local(PathBuf::from(s)),
+            None => NodeSource::Remote(format!("{NEOGO_RELEASES}/download")),
+        }
+    }
+}
+
+pub fn node_args(node_config: &NodeConfig) -> Vec<String> {
+    let mut args = vec![String::from("node")];
+
+    match node_config {
+        NodeConfig::MainNet => args.push(String::from("-m")),
+        NodeConfig::TestNet => args.push(String::from("-t")),
+        NodeConfig::File(path) => {
+            args.push(String::from("--config-file"));
+            args.push(path.clone());
+        }
+    }
+
+    args
+}
+
 fn get_neogo_release_notes(config: &AppConfig) -> String {
-    #[cfg(target_os = "linux")]
-    {
-        format!(
-            "https://github.com/nspcc-dev/neo-go/releases/tag/{}",
-            config.node_version
-        )
+    format!("{NEOGO_RELEASES}/tag/{}", config.node_version)
+}
+
+pub fn neogo_asset(os: &str, arch: &str) -> Option<&'static str> {
+    match (os, arch) {
+        ("linux", "x86_64") => Some("neo-go-linux-amd64"),
+        ("linux", "aarch64") => Some("neo-go-linux-arm64"),
+        ("macos", "x86_64") => Some("neo-go-darwin-amd64"),
+        ("macos", "aarch64") => Some("neo-go-darwin-arm64"),
+        ("windows", "x86_64") => Some("neo-go-windows-amd64.exe"),
+        _ => None,
     }
-    #[cfg(target_os = "macos")]
-    {
-        format!(
-            "https://github.com/nspcc-dev/neo-go/releases/tag/{}",
-            config.node_version
-        )
+}
+
+pub fn pinned_checksum(manifest: &str, version: &str, asset: &str) -> Option<String> {
+    let target = format!("{version}/{asset}");
+
+    manifest
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| line.split_once(char::is_whitespace))
+        .find(|(_, name)| name.trim() == target)
+        .map(|(sum, _)| sum.to_ascii_lowercase())
+}
+
+pub async fn check_neogo(config: &AppConfig) -> Result<(), anyhow::Error> {
+    let path = Path::new(NEOGO_PATH);
+    let expected_version = config.node_version.to_string();
+
+    if !path.exists() {
+        warn!("NeoGo not found in directory.");
+        require_consent(config, "install")?;
+    } else {
+        let installed_version = check_neogo_version()?;
+
+        if installed_version == expected_version {
+            info!("NeoGo {} already installed.", installed_version);
+            return Ok(());
+        }
+
+        warn!(
+            "Installed NeoGo is {}, but {} is configured.",
+            installed_version, expected_version
+        );
+        warn!(
+            "Check the NeoGo version release notes at {} to see if chain state data is compatible.",
+            get_neogo_release_notes(config)
+        );
+        require_consent(config, "upgrade")?;
     }
-    #[cfg(target_os = "windows")]
-    {
-        format!(
-            "https://github.com/nspcc-dev/neo-go/releases/tag/{}",
-            config.node_version
-        )
+
+    install_neogo(config).await?;
+
+    let installed_version = check_neogo_version()?;
+    if installed_version != expected_version {
+        return Err(anyhow::anyhow!(
+            "NeoGo version mismatch after install. Expected {}, got {}.",
+            expected_version,
+            installed_version
+        ));
     }
+    info!("NeoGo {} installed.", installed_version);
+
+    Ok(())
 }
 
-fn get_neogo_dl(config: &AppConfig) -> String {
-    #[cfg(target_os = "linux")]
-    {
-        format!(
-            "https://github.com/nspcc-dev/neo-go/releases/download/{}/neo-go-linux-amd64",
-            config.node_version
-        )
+fn require_consent(config: &AppConfig, action: &str) -> Result<(), anyhow::Error> {
+    if config.auto_install {
+        return Ok(());
     }
-    #[cfg(target_os = "macos")]
-    {
-        format!(
-            "https://github.com/nspcc-dev/neo-go/releases/download/{}/neo-go-darwin-arm64",
-            config.node_version
+
+    Err(anyhow::anyhow!(
+        "Refusing to {} NeoGo {} without consent. Re-run with --yes to allow it.",
+        action,
+        config.node_version
+    ))
+}
+
+async fn install_neogo(config: &AppConfig) -> Result<(), anyhow::Error> {
+    let asset = neogo_asset(env::consts::OS, env::consts::ARCH).ok_or_else(|| {
+        anyhow::anyhow!(
+            "No NeoGo release available for {}/{}.",
+            env::consts::OS,
+            env::consts::ARCH
         )
+    })?;
+
+    let expected_checksum = config
+        .node_checksum
+        .as_ref()
+        .map(|c| c.to_ascii_lowercase())
+        .or_else(|| pinned_checksum(PINNED_CHECKSUMS, &config.node_version, asset))
+        .ok_or_else(|| {
+            anyhow::anyhow!(
+                "No pinned checksum for {}/{}. Add it to config/neogo.sha256 or pass --node-checksum.",
+                config.node_version,
+                asset
+            )
+        })?;
+
+    // download next to the final location so the rename stays on one filesystem
+    let temp_path = PathBuf::from(format!("{NEOGO_PATH}.download"));
+    let source = NodeSource::from_config(config.node_source.as_deref());
+
+    let result = fetch_neogo(&source, &config.node_version, asset, &temp_path).await;
+    let checksum = match result {
+        Ok(checksum) => checksum,
+        Err(e) => {
+            let _ = fs::remove_file(&temp_path).await;
+            return Err(e);
+        }
+    };
+
+    if checksum != expected_checksum {
+        let _ = fs::remove_file(&temp_path).await;
+        return Err(anyhow::anyhow!(
+            "Checksum mismatch for {}. Expected {}, got {}.",
+            asset,
+            expected_checksum,
+            checksum
+        ));
     }
-    #[cfg(target_os = "windows")]
+    info!("Checksum verified for {}.", asset);
+
+    #[cfg(unix)]
     {
-        format!(
-            "https://github.com/nspcc-dev/neo-go/releases/download/{}/neo-go-windows-amd64.exe",
-            config.node_version
-        )
+        use std::os::unix::fs::PermissionsExt;
+
+        info!("Updating permissions..");
+        fs::set_permissions(&temp_path, std::fs::Permissions::from_mode(0o755)).await?;
     }
+
+    fs::rename(&temp_path, NEOGO_PATH).await?;
+
+    Ok(())
 }
 
-pub async fn check_neogo(config: &AppConfig) -> Result<(), anyhow::Error> {
-    let path = Path::new(NEOGO_PATH);
-    if !path.exists() {
-        warn!("NeoGo not found in directory. Install? (y/n)");
-        let answer: char = read!();
-        assert!((answer == 'y'), "User declined to install NeoGo.");
-
-        let mut file = File::create(path).await?;
-        let mut response = reqwest::get(&get_neogo_dl(config)).await.unwrap();
-        while let Some(chunk) = response.chunk().await.unwrap() {
-            file.write_all(&chunk).await?;
-        }
+// copies the asset to `destination` and returns its sha256 checksum
+async fn fetch_neogo(
+    source: &NodeSource,
+    version: &str,
+    asset: &str,
+    destination: &Path,
+) -> Result<String, anyhow::Error> {
+    let mut hasher = Sha256::new();
+    let mut file = fs::File::create(destination).await?;
+
+    match source {
+        NodeSource::Remote(base) => {
+            let url = format!("{base}/{version}/{asset}");
+            info!("Downloading NeoGo from {}..", url);
 
-        if env::consts::OS != "windows" {
-            info!("Updating permissions..");
-            Command::new("chmod")
-                .arg("+x")
-                .arg(NEOGO_PATH)
-                .output()
-                .expect("failed to update permissions");
+            let mut response = reqwest::get(&url).await?.error_for_status()?;
+            while let Some(chunk) = response.chunk().await? {
+                hasher.update(&chunk);
+                file.write_all(&chunk).await?;
+            }
         }
-    } else {
-        info!("NeoGo already installed.");
-        let installed_version = check_neogo_version()?;
-        let expected_version = config.node_version.to_string();
-
-        if installed_version != expected_version {
-            error!("Incorrect NeoGo version detected. Remove {} and re-run to install the correct version.", NEOGO_PATH);
-            error!("Check the NeoGo version release notes at {} to see if chain state data is compatible.", get_neogo_release_notes(config));
-            return Err(anyhow::anyhow!(
-                "NeoGo version mismatch. Expected {}, got {}.",
-                expected_version,
-                installed_version
-            ));
+        NodeSource::Local(path) => {
+            let path = if path.is_dir() {
+                path.join(asset)
+            } else {
+                path.clone()
+            };
+            info!("Copying NeoGo from {}..", path.display());
+
+            let bytes = fs::read(&path).await?;
+            hasher.update(&bytes);
+            file.write_all(&bytes).await?;
         }
     }
-    Ok(())
+
+    file.flush().await?;
+    file.sync_all().await?;
+
+    Ok(hex::encode(hasher.finalize()))
 }
 
 pub fn check_neogo_version() -> Result<String, anyhow::Error> {
-    let command_output = Command::new(NEOGO_PATH)
-        .arg("-v")
-        .output()
-        .expect("Failed to execute version check");
+    let command_output = Command::new(NEOGO_PATH).arg("-v").output()?;
 
     if !command_output.status.success() {
         return Err(anyhow::anyhow!("NeoGo version check failed."));
@@ -115,8 +246,11 @@ pub fn check_neogo_version() -> Result<String, anyhow::Error> {
 
     if let Some(version_line) = version_line {
         let version = String::from_utf8_lossy(version_line);
-        let version = format!("v{}", version.trim().split(' ').nth(1).unwrap());
-        Ok(version)
+        let version = version
+            .split_whitespace()
+            .nth(1)
+            .ok_or_else(|| anyhow::anyhow!("NeoGo version check failed."))?;
+        Ok(format!("v{version}"))
     } else {
         Err(anyhow::anyhow!("NeoGo version check failed."))
     }
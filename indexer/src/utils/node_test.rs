@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::AppConfig;
+    use crate::utils::node::{neogo_asset, pinned_checksum, NodeSource, PINNED_CHECKSUMS};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_neogo_asset() {
+        assert_eq!(neogo_asset("linux", "x86_64"), Some("neo-go-linux-amd64"));
+        assert_eq!(neogo_asset("linux", "aarch64"), Some("neo-go-linux-arm64"));
+        assert_eq!(neogo_asset("macos", "x86_64"), Some("neo-go-darwin-amd64"));
+        assert_eq!(neogo_asset("macos", "aarch64"), Some("neo-go-darwin-arm64"));
+        assert_eq!(
+            neogo_asset("windows", "x86_64"),
+            Some("neo-go-windows-amd64.exe")
+        );
+        assert_eq!(neogo_asset("linux", "riscv64"), None);
+    }
+
+    #[test]
+    fn test_pinned_checksum() {
+        let manifest = "# comment\n\
+            AB12cd  v0.107.2/neo-go-linux-amd64\n\
+            ef34  v0.107.2/neo-go-linux-arm64\n\
+            \n\
+            0000  v0.106.0/neo-go-linux-amd64\n";
+
+        assert_eq!(
+            pinned_checksum(manifest, "v0.107.2", "neo-go-linux-amd64"),
+            Some("ab12cd".to_string())
+        );
+        assert_eq!(
+            pinned_checksum(manifest, "v0.107.2", "neo-go-linux-arm64"),
+            Some("ef34".to_string())
+        );
+        assert_eq!(
+            pinned_checksum(manifest, "v0.107.2", "neo-go-darwin-arm64"),
+            None
+        );
+    }
+
+    // a default install of any supported platform needs its entry in config/neogo.sha256
+    #[test]
+    fn test_default_version_is_pinned() {
+        let version = AppConfig::new().node_version;
+        let platforms = [
+            ("linux", "x86_64"),
+            ("linux", "aarch64"),
+            ("macos", "x86_64"),
+            ("macos", "aarch64"),
+            ("windows", "x86_64"),
+        ];
+
+        for (os, arch) in platforms {
+            let asset = neogo_asset(os, arch).unwrap();
+            assert!(
+                pinned_checksum(PINNED_CHECKSUMS, &version, asset).is_some(),
+                "no checksum for {version}/{asset} in config/neogo.sha256"
+            );
+        }
+    }
+
+    #[test]
+    fn test_node_source() {
+        assert_eq!(
+            NodeSource::from_config(None),
+            NodeSource::Remote("https://github.com/nspcc-dev/neo-go/releases/download".to_string())
+        );
+        assert_eq!(
+            NodeSource::from_config(Some("https://mirror.example.org/neo-go/")),
+            NodeSource::Remote("https://mirror.example.org/neo-go".to_string())
+        );
+        assert_eq!(
+            NodeSource::from_config(Some("./downloads")),
+            NodeSource::Local(PathBuf::from("./downloads"))
+        );
+    }
+}
@@ -0,0 +1,102 @@
+use anyhow::{anyhow, Context, Result};
+use log::info;
+
+use crate::config::AppConfig;
+use crate::db::database::Database;
+use crate::rpc::client::Client;
+use crate::spawn::indexer::reindex_range;
+use crate::utils::logger;
+
+// Regenerates the given tables for blocks [from, to], one batch per transaction. The first
+// unfinished block is recorded after every batch, so running the same command again after an
+// interruption resumes there. Every batch replaces what was there, so redoing one is harmless.
+pub async fn reindex(
+    client: &Client,
+    db: &Database,
+    config: &AppConfig,
+    from: u64,
+    to: u64,
+    tables: &[String],
+) -> Result<()> {
+    if from > to {
+        return Err(anyhow!("--from {} is past --to {}.", from, to));
+    }
+
+    // processor tables can be rebuilt locally, anything else needs the node
+    let from_node = tables.is_empty() || tables.iter().any(|table| is_node_table(table));
+
+    let processors = db.processor_names();
+    if let Some(unknown) = tables
+        .iter()
+        .find(|table| !is_node_table(table) && !processors.contains(&table.as_str()))
+    {
+        return Err(anyhow!(
+            "Unknown table {}. Expected blocks, transactions or one of {}.",
+            unknown,
+            processors.join(", ")
+        ));
+    }
+
+    // blocks past the stored height are the Indexer's to fill in, writing them here would leave gaps
+    let stored_height = db.get_stored_height()?;
+    if stored_height.is_none_or(|height| to > height) {
+        return Err(anyhow!(
+            "Blocks up to {} aren't stored yet, only indexed blocks can be re-indexed.",
+            to
+        ));
+    }
+
+    if from_node {
+        let chain_height = client
+            .get_current_height()
+            .await
+            .with_context(|| format!("Failed to reach the node at {}", config.node_path))?;
+        if to >= chain_height {
+            return Err(anyhow!(
+                "Node is at block {}, can't re-index up to {}.",
+                chain_height.saturating_sub(1),
+                to
+            ));
+        }
+        info!("Re-indexing blocks {} to {} from the node:", from, to);
+    } else {
+        info!("Regenerating {:?} for blocks {} to {}:", tables, from, to);
+    }
+
+    let key = tables.join(",");
+    let mut start = match db.get_reindex_progress(from, to, &key)? {
+        Some(next) if next > from => {
+            info!("Resuming the previous run at block {}.", next);
+            next
+        }
+        _ => from,
+    };
+    while start <= to {
+        let end = std::cmp::min(start + config.batch_size, to + 1);
+
+        let result = if from_node {
+            reindex_range(client, db, start, end, config.batch_size).await
+        } else {
+            db.reprocess(start, end, tables)
+        };
+        result.with_context(|| {
+            format!(
+                "Failed to re-index blocks {start} to {}. Run the same command again to resume",
+                end - 1
+            )
+        })?;
+        db.set_reindex_progress(from, to, &key, end)?;
+
+        logger::inline_print(&format!("\rRe-indexed up to block {}.", end - 1));
+        start = end;
+    }
+    println!();
+    db.clear_reindex_progress()?;
+    info!("Re-index complete.");
+
+    Ok(())
+}
+
+fn is_node_table(table: &str) -> bool {
+    table == "blocks" || table == "transactions"
+}
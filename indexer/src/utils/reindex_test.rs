@@ -0,0 +1,163 @@
+#[cfg(test)]
+mod tests {
+    use crate::config::AppConfig;
+    use crate::db::database::Database;
+    use crate::rpc::client::Client;
+    use crate::utils::reindex::reindex;
+    use lib::db::sqlite::SqliteStorage;
+    use lib::db::Storage;
+    use serde_json::json;
+
+    fn count(storage: &SqliteStorage, table: &str) -> u64 {
+        storage
+            .query_row(&format!("SELECT COUNT(*) FROM {table}"), &[])
+            .unwrap()
+            .unwrap()
+            .get(0)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_reprocess() {
+        let path = std::env::temp_dir().join(format!("shrike-reindex-{}.db3", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let db = Database::from_storage(Box::new(SqliteStorage::open(&path).unwrap()));
+        db.migrate().unwrap();
+
+        let notifications = json!([{
+            "contract": "0xef4073a0f2b305a38ec4050e4d3d28bc40ea63f5",
+            "eventname": "Transfer",
+            "state": {
+                "type": "Array",
+                "value": [
+                    { "type": "ByteString", "value": "axI92L7HGGSIUrvHhZXjU2oFj58=" },
+                    { "type": "ByteString", "value": "dVE6zv92GLfukg8P5gFa0cDxb/0=" },
+                    { "type": "Integer", "value": "100000" }
+                ]
+            }
+        }]);
+
+        let storage = SqliteStorage::open(&path).unwrap();
+        storage
+            .execute_batch(&format!(
+                "INSERT INTO blocks (id, hash, size, version, merkle_root, time, nonce, speaker,
+                next_consensus, reward, reward_receiver, witnesses, reward_amount)
+                VALUES (1, '0xb1', 0, 0, '', 0, '', 0, '', 0, '', '[]', '0');
+
+                INSERT INTO transactions (hash, block_index, vm_state, size, version, nonce, sender,
+                sysfee, netfee, valid_until, signers, script, witnesses, stack_result, notifications)
+                VALUES ('0xt1', 1, 'HALT', 0, 0, 0, '', '0', '0', 0, '[]', '', '[]', '[]', '{notifications}');
+
+                INSERT INTO contracts (block_index, hash, contract_type) VALUES (1, '0xc1', '[]');
+                INSERT INTO addresses (block_index, address, balances) VALUES (1, 'stale', '{{}}');"
+            ))
+            .unwrap();
+
+        // rerunning a batch gives the same result
+        for _ in 0..2 {
+            db.reprocess(0, 2, &[String::from("addresses")]).unwrap();
+            assert_eq!(count(&storage, "addresses"), 2);
+        }
+        assert_eq!(
+            storage
+                .query_row(
+                    "SELECT COUNT(*) FROM addresses WHERE address = 'stale'",
+                    &[]
+                )
+                .unwrap()
+                .unwrap()
+                .get::<u64>(0)
+                .unwrap(),
+            0
+        );
+
+        // tables that weren't asked for are left alone
+        assert_eq!(count(&storage, "contracts"), 1);
+
+        drop(storage);
+        drop(db);
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    // blocks 0 to 3 and an addresses row for block 1 that regenerating the table removes
+    fn stored_blocks() -> Database {
+        let db = Database::from_storage(Box::new(SqliteStorage::open_in_memory().unwrap()));
+        db.migrate().unwrap();
+        db.blocking(|storage| {
+            for index in 0..4 {
+                storage
+                    .execute(
+                        "INSERT INTO blocks (id, hash, size, version, merkle_root, time, nonce, speaker,
+                        next_consensus, reward, reward_receiver, witnesses, reward_amount)
+                        VALUES ($1, $2, 0, 0, '', 0, '', 0, '', 0, '', '[]', '0')",
+                        &[index.into(), format!("0xb{index}").into()],
+                    )
+                    .unwrap();
+            }
+            storage
+                .execute_batch(
+                    "INSERT INTO addresses (block_index, address, balances) VALUES (1, 'stale', '{}')",
+                )
+                .unwrap();
+        });
+
+        db
+    }
+
+    fn addresses(db: &Database) -> u64 {
+        db.blocking(|storage| {
+            storage
+                .query_row("SELECT COUNT(*) FROM addresses", &[])
+                .unwrap()
+                .unwrap()
+                .get(0)
+                .unwrap()
+        })
+    }
+
+    // the node is never asked, nothing listens here
+    fn config() -> AppConfig {
+        let mut config = AppConfig::new();
+        config.node_path = String::from("http://127.0.0.1:9");
+        config.batch_size = 1;
+        config
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reindex_resumes() {
+        let db = stored_blocks();
+        let config = config();
+        let client = Client::new(&config);
+        let tables = [String::from("addresses")];
+
+        // an earlier run got through blocks 0 and 1
+        db.set_reindex_progress(0, 3, "addresses", 2).unwrap();
+        reindex(&client, &db, &config, 0, 3, &tables).await.unwrap();
+        assert_eq!(addresses(&db), 1);
+        assert_eq!(db.get_reindex_progress(0, 3, "addresses").unwrap(), None);
+
+        // with nothing to resume it starts over
+        reindex(&client, &db, &config, 0, 3, &tables).await.unwrap();
+        assert_eq!(addresses(&db), 0);
+
+        // progress of another range isn't picked up
+        db.set_reindex_progress(0, 2, "addresses", 3).unwrap();
+        assert_eq!(db.get_reindex_progress(0, 3, "addresses").unwrap(), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reindex_stops_at_stored_height() {
+        let db = stored_blocks();
+        let config = config();
+        let client = Client::new(&config);
+
+        for tables in [vec![], vec![String::from("addresses")]] {
+            let error = reindex(&client, &db, &config, 2, 4, &tables)
+                .await
+                .unwrap_err();
+            assert!(error.to_string().contains("aren't stored yet"));
+        }
+        assert_eq!(addresses(&db), 1);
+    }
+}
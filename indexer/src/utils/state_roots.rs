@@ -0,0 +1,78 @@
+use anyhow::{Context, Result};
+use futures::future::join_all;
+use log::{error, info};
+use serde_json::to_string;
+
+use crate::db::database::Database;
+use crate::db::model::StateRoot;
+use crate::rpc::client::Client;
+use crate::rpc::models::StateRootResult;
+use crate::utils::metrics::METRICS;
+
+#[derive(Debug, PartialEq)]
+pub enum RootCheck {
+    // not signed by the state validators yet
+    Pending,
+    // with the witnesses to store
+    Validated(String),
+    // the root the node reports instead
+    Mismatch(String),
+}
+
+pub fn check_state_root(stored: &StateRoot, node: &StateRootResult) -> RootCheck {
+    if node.roothash != stored.root_hash {
+        RootCheck::Mismatch(node.roothash.clone())
+    } else if node.witnesses.is_empty() {
+        RootCheck::Pending
+    } else {
+        RootCheck::Validated(to_string(&node.witnesses).unwrap())
+    }
+}
+
+// Roots are usually indexed before the state validators sign them. This asks the node again
+// for up to `limit` of the stored roots it has validated since, and records them as validated.
+// A root that changed is logged as an error and counted in `state_root_mismatches_total`.
+// Returns how many roots were validated.
+pub async fn validate_state_roots(client: &Client, db: &Database, limit: u64) -> Result<u64> {
+    let Some(state_height) = client
+        .get_state_height()
+        .await
+        .context("Failed to get state height")?
+    else {
+        return Ok(0);
+    };
+
+    let stored = db.get_unvalidated_state_roots(state_height.validatedrootindex, limit)?;
+    let fetched = join_all(stored.iter().map(|root| client.get_state_root(root.index))).await;
+
+    let mut validated = 0;
+    for (root, node) in stored.iter().zip(fetched) {
+        let Some(node) =
+            node.with_context(|| format!("Failed to get state root {}", root.index))?
+        else {
+            continue;
+        };
+
+        match check_state_root(root, &node) {
+            RootCheck::Pending => {}
+            RootCheck::Validated(witnesses) => {
+                db.set_state_root_validated(root.index, &witnesses)?;
+                validated += 1;
+            }
+            RootCheck::Mismatch(root_hash) => {
+                error!(
+                    "State root mismatch at block {}: stored {} but the node has {}.",
+                    root.index, root.root_hash, root_hash
+                );
+                METRICS.state_root_mismatches.inc();
+                db.set_state_root_mismatch(root.index, &root_hash)?;
+            }
+        }
+    }
+
+    if validated > 0 {
+        info!("Validated {} state root(s).", validated);
+    }
+
+    Ok(validated)
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::database::Database;
+    use crate::db::model::{Block, BlockBatch, StateRoot};
+    use crate::rpc::models::{StateRootResult, Witness};
+    use crate::utils::state_roots::{check_state_root, RootCheck};
+    use lib::db::sqlite::SqliteStorage;
+    use lib::integer::BigInt;
+
+    fn block(index: u64) -> Block {
+        Block {
+            index,
+            hash: format!("0xb{index}"),
+            size: 114,
+            version: 0,
+            merkle_root: String::from("0x00"),
+            time: 1468595301000,
+            nonce: String::from("00"),
+            speaker: 0,
+            next_consensus: String::from("N"),
+            reward: BigInt::from(50_000_000),
+            reward_receiver: String::from("N"),
+            witnesses: String::from("[]"),
+        }
+    }
+
+    fn state_root(index: u64) -> StateRoot {
+        StateRoot {
+            index,
+            root_hash: format!("0xr{index}"),
+            validated: false,
+            witnesses: String::from("[]"),
+        }
+    }
+
+    fn node_root(roothash: &str, signed: bool) -> StateRootResult {
+        let witnesses = match signed {
+            true => vec![Witness {
+                invocation: String::from("DEA="),
+                verification: String::from("EQ=="),
+            }],
+            false => vec![],
+        };
+
+        StateRootResult {
+            version: 0,
+            index: 1,
+            roothash: roothash.to_string(),
+            witnesses,
+        }
+    }
+
+    #[test]
+    fn test_check_state_root() {
+        let stored = state_root(1);
+
+        assert_eq!(
+            check_state_root(&stored, &node_root("0xr1", false)),
+            RootCheck::Pending
+        );
+        assert_eq!(
+            check_state_root(&stored, &node_root("0xr1", true)),
+            RootCheck::Validated(String::from(
+                r#"[{"invocation":"DEA=","verification":"EQ=="}]"#
+            ))
+        );
+        // signed or not, a different root is a mismatch
+        assert_eq!(
+            check_state_root(&stored, &node_root("0xother", true)),
+            RootCheck::Mismatch(String::from("0xother"))
+        );
+    }
+
+    #[test]
+    fn test_unvalidated_state_roots() {
+        let db = Database::from_storage(Box::new(SqliteStorage::open_in_memory().unwrap()));
+        db.migrate().unwrap();
+
+        let mut validated = state_root(0);
+        validated.validated = true;
+
+        db.insert_batch(&BlockBatch {
+            blocks: vec![block(0), block(1), block(2), block(3)],
+            state_roots: vec![validated, state_root(1), state_root(2), state_root(3)],
+            ..Default::default()
+        })
+        .unwrap();
+
+        let indexes = |db: &Database| -> Vec<u64> {
+            db.get_unvalidated_state_roots(2, 10)
+                .unwrap()
+                .iter()
+                .map(|root| root.index)
+                .collect()
+        };
+        assert_eq!(indexes(&db), vec![1, 2]);
+
+        db.set_state_root_validated(1, "[]").unwrap();
+        db.set_state_root_mismatch(2, "0xother").unwrap();
+        assert!(indexes(&db).is_empty());
+
+        // replaced along with its block
+        db.replace_range(
+            2,
+            3,
+            &BlockBatch {
+                blocks: vec![block(2)],
+                state_roots: vec![state_root(2)],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(indexes(&db), vec![2]);
+    }
+}
@@ -0,0 +1,245 @@
+use anyhow::{anyhow, Context, Result};
+use futures::future::join_all;
+use lib::binary::Serializable;
+use lib::payloads::{self, hash_to_hex};
+use log::{info, warn};
+
+use crate::config::AppConfig;
+use crate::db::database::Database;
+use crate::rpc::client::Client;
+use crate::rpc::models::BlockResult;
+use crate::spawn::indexer::reindex_range;
+use crate::utils::logger;
+
+// Checks the stored chain for gaps and dangling transactions, then compares stored blocks
+// with the node. Broken ranges are re-indexed when `repair` is set, otherwise reported as an error.
+pub async fn verify(
+    client: &Client,
+    db: &Database,
+    config: &AppConfig,
+    sample: Option<u64>,
+    repair: bool,
+) -> Result<()> {
+    let Some(stored_height) = db.get_stored_height()? else {
+        info!("No blocks stored yet, nothing to verify.");
+        return Ok(());
+    };
+    info!("Verifying blocks 0 to {}..", stored_height);
+
+    let mut broken = Vec::new();
+
+    let gaps = db.find_block_gaps().context("Failed to look for gaps")?;
+    for (start, end) in &gaps {
+        warn!("Blocks {} to {} are missing.", start, end - 1);
+    }
+    broken.extend(gaps.iter().copied());
+
+    let orphaned = db
+        .find_orphaned_transactions()
+        .context("Failed to look for orphaned transactions")?;
+    for index in &orphaned {
+        warn!("Transactions reference missing block {}.", index);
+    }
+    broken.extend(orphaned.iter().map(|index| (*index, index + 1)));
+
+    let chain_height = client
+        .get_current_height()
+        .await
+        .with_context(|| format!("Failed to reach the node at {}", config.node_path))?;
+    // the block count is one past the last block
+    let last = std::cmp::min(stored_height, chain_height.saturating_sub(1));
+    if last < stored_height {
+        warn!(
+            "Node is behind the database, checking up to block {}.",
+            last
+        );
+    }
+
+    let heights: Vec<u64> = match sample {
+        Some(count) => sample_heights(last, count),
+        None => (0..=last).collect(),
+    }
+    .into_iter()
+    .filter(|height| {
+        !gaps
+            .iter()
+            .any(|(start, end)| (*start..*end).contains(height))
+    })
+    .collect();
+
+    info!("Comparing {} block(s) with the node:", heights.len());
+    let mut checked = 0;
+    for chunk in heights.chunks(config.batch_size as usize) {
+        let blocks = join_all(chunk.iter().map(|height| client.get_block(*height))).await;
+
+        for (height, block) in chunk.iter().zip(blocks) {
+            let block = block.with_context(|| format!("Failed to fetch block {height}"))?;
+
+            for (index, problem) in check_block(db, &block)? {
+                println!();
+                warn!("Block {}: {}.", index, problem);
+                broken.push((index, index + 1));
+            }
+        }
+
+        checked += chunk.len();
+        logger::inline_print(&format!("\rChecked {checked} block(s)."));
+    }
+    println!();
+
+    let broken = merge_ranges(broken);
+    if broken.is_empty() {
+        info!("No problems found.");
+        return Ok(());
+    }
+
+    if !repair {
+        return Err(anyhow!(
+            "Found {} broken range(s). Run with --repair to re-index them.",
+            broken.len()
+        ));
+    }
+
+    for (start, end) in broken {
+        info!("Re-indexing blocks {} to {}..", start, end - 1);
+        reindex_range(client, db, start, end, config.batch_size).await?;
+    }
+    info!("Repair complete.");
+
+    Ok(())
+}
+
+// problems found for a block fetched from the node, as (height, description) pairs
+fn check_block(db: &Database, block: &BlockResult) -> Result<Vec<(u64, String)>> {
+    let mut problems = Vec::new();
+
+    if let Some(hash) = db.get_block_hash(block.index)? {
+        if hash != block.hash {
+            problems.push((
+                block.index,
+                format!("stored hash {} but the node has {}", hash, block.hash),
+            ));
+        }
+    }
+
+    // the chain links are only checked one way, so a broken parent shows up here
+    // even when it isn't part of the sample
+    if block.index > 0 {
+        if let Some(previous) = db.get_block_hash(block.index - 1)? {
+            if previous != block.previousblockhash {
+                problems.push((
+                    block.index - 1,
+                    format!(
+                        "stored hash {} but block {} links to {}",
+                        previous, block.index, block.previousblockhash
+                    ),
+                ));
+            }
+        }
+    }
+
+    let stored = db.count_block_transactions(block.index)?;
+    if stored != block.tx.len() as u64 {
+        problems.push((
+            block.index,
+            format!(
+                "{} transaction(s) stored but the node has {}",
+                stored,
+                block.tx.len()
+            ),
+        ));
+    }
+
+    Ok(problems)
+}
+
+// Decodes the block the node serialized and checks that it hashes to what the JSON says, that
+// its transactions add up to the merkle root, and that the JSON describes the same block.
+pub fn verify_block_hashes(block: &BlockResult, raw: &str) -> Result<()> {
+    let fail = |problem: String| {
+        anyhow!(
+            "Block {} failed hash verification: {}.",
+            block.index,
+            problem
+        )
+    };
+
+    let bytes = base64::decode(raw).map_err(|e| fail(e.to_string()))?;
+    let decoded = payloads::Block::from_bytes(&bytes).map_err(|e| fail(e.to_string()))?;
+    let header = &decoded.header;
+
+    let hash = hash_to_hex(&decoded.hash());
+    if hash != block.hash {
+        return Err(fail(format!("it hashes to {hash}, not {}", block.hash)));
+    }
+
+    let merkle_root = hash_to_hex(&decoded.compute_merkle_root());
+    if merkle_root != hash_to_hex(&header.merkle_root) || merkle_root != block.merkleroot {
+        return Err(fail(format!(
+            "its transactions give the merkle root {merkle_root}, not {}",
+            block.merkleroot
+        )));
+    }
+
+    let fields = [
+        (u64::from(header.index), block.index, "index"),
+        (header.timestamp, block.time, "time"),
+        (
+            u64::from(header.primary_index),
+            u64::from(block.primary),
+            "primary",
+        ),
+    ];
+    for (decoded, reported, field) in fields {
+        if decoded != reported {
+            return Err(fail(format!("{field} is {decoded}, not {reported}")));
+        }
+    }
+    if hash_to_hex(&header.prev_hash) != block.previousblockhash {
+        return Err(fail(format!(
+            "previous hash is {}, not {}",
+            hash_to_hex(&header.prev_hash),
+            block.previousblockhash
+        )));
+    }
+
+    let hashes: Vec<String> = decoded
+        .transactions
+        .iter()
+        .map(|transaction| hash_to_hex(&transaction.hash()))
+        .collect();
+    let reported: Vec<&str> = block.tx.iter().map(|tx| tx.hash.as_str()).collect();
+    if hashes != reported {
+        return Err(fail(String::from("its transaction hashes differ")));
+    }
+
+    Ok(())
+}
+
+// `count` heights spread evenly over [0, height], always including the tip
+pub fn sample_heights(height: u64, count: u64) -> Vec<u64> {
+    if count > height {
+        return (0..=height).collect();
+    }
+
+    let step = height / count.max(1);
+    let mut heights: Vec<u64> = (0..count).map(|i| height - i * step).collect();
+    heights.reverse();
+
+    heights
+}
+
+// sorts [start, end) ranges and joins the ones that overlap or touch
+pub fn merge_ranges(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    ranges.sort_unstable();
+
+    let mut merged: Vec<(u64, u64)> = Vec::new();
+    for (start, end) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 => last.1 = last.1.max(end),
+            _ => merged.push((start, end)),
+        }
+    }
+
+    merged
+}
@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod tests {
+    use crate::rpc::models::BlockResult;
+    use crate::utils::verify::{merge_ranges, sample_heights, verify_block_hashes};
+    use serde_json::json;
+
+    // MainNet's genesis block, as returned by getblock with verbosity 0
+    const GENESIS: &str = "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAACI6hnvVQEAAB2sK3wAAAAAAAAAAABrEj3YvscYZIhSu8eFleNTagWPnwEAAREA";
+
+    fn genesis() -> BlockResult {
+        serde_json::from_value(json!({
+            "hash": "0x1f4d1defa46faa5e7b9b8d3f79a06bec777d7c26c4aa5f6f5899a291daa87c15",
+            "size": 114,
+            "version": 0,
+            "previousblockhash": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "merkleroot": "0x0000000000000000000000000000000000000000000000000000000000000000",
+            "time": 1468595301000u64,
+            "nonce": "000000007C2BAC1D",
+            "index": 0,
+            "primary": 0,
+            "nextconsensus": "NVg7LjGcUSrgxgjX3zEgqaksfMaiS8Z6e1",
+            "witnesses": [{ "invocation": "", "verification": "EQ==" }],
+            "tx": []
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_sample_heights() {
+        assert_eq!(sample_heights(10, 3), vec![4, 7, 10]);
+        assert_eq!(sample_heights(4, 1), vec![4]);
+        assert_eq!(sample_heights(4, 10), vec![0, 1, 2, 3, 4]);
+        assert_eq!(sample_heights(0, 5), vec![0]);
+        assert!(sample_heights(10, 0).is_empty());
+    }
+
+    #[test]
+    fn test_merge_ranges() {
+        assert_eq!(
+            merge_ranges(vec![(7, 8), (0, 3), (3, 4), (2, 3), (10, 12), (11, 15)]),
+            vec![(0, 4), (7, 8), (10, 15)]
+        );
+        assert!(merge_ranges(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_verify_block_hashes() {
+        assert!(verify_block_hashes(&genesis(), GENESIS).is_ok());
+
+        let mut block = genesis();
+        block.time += 1;
+        let error = verify_block_hashes(&block, GENESIS).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Block 0 failed hash verification: time is 1468595301000, not 1468595301001."
+        );
+
+        let mut block = genesis();
+        block.hash = block.merkleroot.clone();
+        assert!(verify_block_hashes(&block, GENESIS).is_err());
+
+        // truncated
+        assert!(verify_block_hashes(&genesis(), &GENESIS[..GENESIS.len() - 4]).is_err());
+    }
+}
@@ -0,0 +1,201 @@
+// Neo's binary format: little-endian integers, and lengths written as variable-length integers
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum BinaryError {
+    #[error("unexpected end of data at byte {0}")]
+    UnexpectedEnd(usize),
+    #[error("{0} exceeds the maximum of {1}")]
+    TooLarge(&'static str, u64),
+    #[error("invalid {0}")]
+    InvalidValue(&'static str),
+    #[error("{0} trailing byte(s)")]
+    TrailingBytes(usize),
+}
+
+pub trait Serializable: Sized {
+    fn serialize(&self, writer: &mut BinaryWriter);
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError>;
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = BinaryWriter::new();
+        self.serialize(&mut writer);
+
+        writer.into_bytes()
+    }
+
+    // the whole of `bytes`, anything left over is an error
+    fn from_bytes(bytes: &[u8]) -> Result<Self, BinaryError> {
+        let mut reader = BinaryReader::new(bytes);
+        let value = Self::deserialize(&mut reader)?;
+
+        match reader.remaining() {
+            0 => Ok(value),
+            left => Err(BinaryError::TrailingBytes(left)),
+        }
+    }
+}
+
+pub struct BinaryReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> BinaryReader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, position: 0 }
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.position
+    }
+
+    pub fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], BinaryError> {
+        if count > self.remaining() {
+            return Err(BinaryError::UnexpectedEnd(self.data.len()));
+        }
+
+        let bytes = &self.data[self.position..self.position + count];
+        self.position += count;
+
+        Ok(bytes)
+    }
+
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], BinaryError> {
+        Ok(self.read_bytes(N)?.try_into().unwrap())
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, BinaryError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_bool(&mut self) -> Result<bool, BinaryError> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(BinaryError::InvalidValue("boolean")),
+        }
+    }
+
+    pub fn read_u16(&mut self) -> Result<u16, BinaryError> {
+        Ok(u16::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, BinaryError> {
+        Ok(u32::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, BinaryError> {
+        Ok(u64::from_le_bytes(self.read_array()?))
+    }
+
+    pub fn read_i64(&mut self) -> Result<i64, BinaryError> {
+        Ok(i64::from_le_bytes(self.read_array()?))
+    }
+
+    // one byte below 0xfd, otherwise a 0xfd, 0xfe or 0xff prefix and a u16, u32 or u64
+    pub fn read_var_int(&mut self, max: u64) -> Result<u64, BinaryError> {
+        let value = match self.read_u8()? {
+            0xfd => u64::from(self.read_u16()?),
+            0xfe => u64::from(self.read_u32()?),
+            0xff => self.read_u64()?,
+            byte => u64::from(byte),
+        };
+
+        if value > max {
+            return Err(BinaryError::TooLarge("length", max));
+        }
+
+        Ok(value)
+    }
+
+    pub fn read_var_bytes(&mut self, max: usize) -> Result<Vec<u8>, BinaryError> {
+        let length = self.read_var_int(max as u64)? as usize;
+
+        Ok(self.read_bytes(length)?.to_vec())
+    }
+
+    // a var-int count of up to `max` items
+    pub fn read_items<T: Serializable>(&mut self, max: usize) -> Result<Vec<T>, BinaryError> {
+        let count = self.read_var_int(max as u64)?;
+
+        (0..count).map(|_| T::deserialize(self)).collect()
+    }
+}
+
+#[derive(Default)]
+pub struct BinaryWriter {
+    data: Vec<u8>,
+}
+
+impl BinaryWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.data.push(value);
+    }
+
+    pub fn write_bool(&mut self, value: bool) {
+        self.write_u8(u8::from(value));
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_i64(&mut self, value: i64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    // the shortest form, as Neo writes it
+    pub fn write_var_int(&mut self, value: u64) {
+        match value {
+            0..=0xfc => self.write_u8(value as u8),
+            0xfd..=0xffff => {
+                self.write_u8(0xfd);
+                self.write_u16(value as u16);
+            }
+            0x10000..=0xffff_ffff => {
+                self.write_u8(0xfe);
+                self.write_u32(value as u32);
+            }
+            _ => {
+                self.write_u8(0xff);
+                self.write_u64(value);
+            }
+        }
+    }
+
+    pub fn write_var_bytes(&mut self, bytes: &[u8]) {
+        self.write_var_int(bytes.len() as u64);
+        self.write_bytes(bytes);
+    }
+
+    pub fn write_items<T: Serializable>(&mut self, items: &[T]) {
+        self.write_var_int(items.len() as u64);
+        for item in items {
+            item.serialize(self);
+        }
+    }
+}
@@ -0,0 +1,35 @@
+#[cfg(test)]
+mod tests {
+    use crate::binary::{BinaryError, BinaryReader, BinaryWriter};
+
+    #[test]
+    fn test_var_int_round_trip() {
+        for (value, encoded) in [
+            (0, "00"),
+            (0xfc, "fc"),
+            (0xfd, "fdfd00"),
+            (0xffff, "fdffff"),
+            (0x10000, "fe00000100"),
+            (0x1_0000_0000, "ff0000000001000000"),
+        ] {
+            let mut writer = BinaryWriter::new();
+            writer.write_var_int(value);
+            let bytes = writer.into_bytes();
+            assert_eq!(hex::encode(&bytes), encoded);
+
+            let mut reader = BinaryReader::new(&bytes);
+            assert_eq!(reader.read_var_int(u64::MAX), Ok(value));
+            assert_eq!(reader.remaining(), 0);
+        }
+
+        let bytes = hex::decode("fd0001").unwrap();
+        assert_eq!(
+            BinaryReader::new(&bytes).read_var_int(0xff),
+            Err(BinaryError::TooLarge("length", 0xff))
+        );
+        assert_eq!(
+            BinaryReader::new(&bytes[..2]).read_var_int(u64::MAX),
+            Err(BinaryError::UnexpectedEnd(2))
+        );
+    }
+}
@@ -0,0 +1,47 @@
+use directories_next::ProjectDirs;
+use once_cell::sync::Lazy;
+
+use std::{fs, path::PathBuf};
+
+pub mod storage;
+pub mod value;
+mod value_test;
+
+#[cfg(feature = "postgres")]
+pub mod postgres;
+#[cfg(feature = "postgres")]
+mod postgres_test;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(feature = "sqlite")]
+mod sqlite_test;
+
+pub use storage::{in_transaction, is_postgres_url, open, Dialect, Storage, StorageError};
+pub use value::{FromValue, Row, Value};
+
+// latest schema written by the Indexer, tracked in `PRAGMA user_version` on SQLite
+// and the `schema_version` table on Postgres
+pub const SCHEMA_VERSION: u32 = 8;
+
+pub static DB_PATH: Lazy<PathBuf> = Lazy::new(|| db_path("mainnet"));
+
+// MainNet keeps the original file name so existing databases are picked up
+pub fn db_path(network: &str) -> PathBuf {
+    let project_dirs =
+        ProjectDirs::from("", "", "Shrike").expect("Failed to get project directories");
+    let mut path = project_dirs.data_local_dir().to_path_buf();
+
+    if network == "mainnet" {
+        path.push("shrike.db3");
+    } else {
+        path.push(format!("shrike.{network}.db3"));
+    }
+
+    // Check if the parent directory exists and create it if necessary
+    let parent = path.parent().expect("Failed to get db parent directory");
+    if !parent.exists() {
+        fs::create_dir_all(parent).expect("Failed to create db parent directory");
+    }
+
+    path
+}
@@ -0,0 +1,258 @@
+use bytes::BytesMut;
+use postgres::types::{to_sql_checked, FromSql, IsNull, ToSql, Type};
+use postgres::{Client, NoTls, Statement};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
+use std::ops::DerefMut;
+
+use super::storage::{Dialect, Storage, StorageError};
+use super::value::{Row, Value};
+
+type BoxError = Box<dyn Error + Sync + Send>;
+
+// `postgres::Client` needs `&mut self`, so it sits behind a RefCell to share the
+// `&self` signatures of the SQLite backend. Works on pooled clients too.
+pub struct PostgresStorage<C = Box<Client>> {
+    client: RefCell<C>,
+    statements: RefCell<HashMap<String, Statement>>,
+}
+
+impl PostgresStorage {
+    pub fn connect(url: &str) -> Result<Self, StorageError> {
+        Ok(Self::new(Box::new(Client::connect(url, NoTls)?)))
+    }
+}
+
+impl<C: DerefMut<Target = Client>> PostgresStorage<C> {
+    pub fn new(client: C) -> Self {
+        Self {
+            client: RefCell::new(client),
+            statements: RefCell::new(HashMap::new()),
+        }
+    }
+
+    fn prepare(&self, client: &mut Client, sql: &str) -> Result<Statement, StorageError> {
+        if let Some(statement) = self.statements.borrow().get(sql) {
+            return Ok(statement.clone());
+        }
+
+        let statement = client.prepare(sql)?;
+        self.statements
+            .borrow_mut()
+            .insert(sql.to_string(), statement.clone());
+
+        Ok(statement)
+    }
+
+    fn params(params: &[Value]) -> Vec<&(dyn ToSql + Sync)> {
+        params.iter().map(|p| p as &(dyn ToSql + Sync)).collect()
+    }
+}
+
+// Binds to whatever type Postgres inferred for the parameter, so callers don't
+// have to care whether a column is INTEGER or BIGINT
+impl ToSql for Value {
+    fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+        match self {
+            Value::Null => Ok(IsNull::Yes),
+            Value::Integer(i) => integer_to_sql(*i, ty, out),
+            Value::Real(f) => match *ty {
+                Type::FLOAT4 => (*f as f32).to_sql(ty, out),
+                Type::FLOAT8 => f.to_sql(ty, out),
+                _ if is_text(ty) => f.to_string().to_sql(ty, out),
+                _ => Err(format!("cannot bind a float to {ty}").into()),
+            },
+            Value::Text(s) => match *ty {
+                _ if is_text(ty) => s.to_sql(ty, out),
+                Type::INT2 | Type::INT4 | Type::INT8 => integer_to_sql(s.trim().parse()?, ty, out),
+                Type::FLOAT4 | Type::FLOAT8 => Value::Real(s.trim().parse()?).to_sql(ty, out),
+                _ => Err(format!("cannot bind text to {ty}").into()),
+            },
+            Value::Blob(b) => match *ty {
+                Type::BYTEA => b.as_slice().to_sql(ty, out),
+                _ => Err(format!("cannot bind a blob to {ty}").into()),
+            },
+        }
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    to_sql_checked!();
+}
+
+fn integer_to_sql(i: i64, ty: &Type, out: &mut BytesMut) -> Result<IsNull, BoxError> {
+    match *ty {
+        Type::INT2 => i16::try_from(i)?.to_sql(ty, out),
+        Type::INT4 => i32::try_from(i)?.to_sql(ty, out),
+        Type::INT8 => i.to_sql(ty, out),
+        Type::FLOAT4 => (i as f32).to_sql(ty, out),
+        Type::FLOAT8 => (i as f64).to_sql(ty, out),
+        Type::BOOL => (i != 0).to_sql(ty, out),
+        _ if is_text(ty) => i.to_string().to_sql(ty, out),
+        _ => Err(format!("cannot bind an integer to {ty}").into()),
+    }
+}
+
+fn is_text(ty: &Type) -> bool {
+    matches!(
+        *ty,
+        Type::TEXT | Type::VARCHAR | Type::BPCHAR | Type::NAME | Type::UNKNOWN
+    )
+}
+
+impl<'a> FromSql<'a> for Value {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, BoxError> {
+        Ok(match *ty {
+            Type::INT2 => Value::Integer(i16::from_sql(ty, raw)? as i64),
+            Type::INT4 => Value::Integer(i32::from_sql(ty, raw)? as i64),
+            Type::INT8 => Value::Integer(i64::from_sql(ty, raw)?),
+            Type::OID => Value::Integer(u32::from_sql(ty, raw)? as i64),
+            Type::BOOL => Value::Integer(bool::from_sql(ty, raw)? as i64),
+            Type::FLOAT4 => Value::Real(f32::from_sql(ty, raw)? as f64),
+            Type::FLOAT8 => Value::Real(f64::from_sql(ty, raw)?),
+            Type::NUMERIC => Value::Text(decode_numeric(raw)?),
+            Type::BYTEA => Value::Blob(raw.to_vec()),
+            Type::JSON => Value::Text(String::from_utf8(raw.to_vec())?),
+            // binary jsonb is a version byte followed by the text
+            Type::JSONB => Value::Text(String::from_utf8(
+                raw.get(1..).unwrap_or_default().to_vec(),
+            )?),
+            _ if is_text(ty) => Value::Text(String::from_sql(ty, raw)?),
+            _ => return Err(format!("unsupported column type {ty}").into()),
+        })
+    }
+
+    fn from_sql_null(_: &Type) -> Result<Self, BoxError> {
+        Ok(Value::Null)
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+// SUM and friends return NUMERIC, sent as base 10000 digits. Decoded to a decimal string.
+pub fn decode_numeric(raw: &[u8]) -> Result<String, BoxError> {
+    let read = |i: usize| -> Result<u16, BoxError> {
+        raw.get(i..i + 2)
+            .map(|b| u16::from_be_bytes([b[0], b[1]]))
+            .ok_or_else(|| "truncated numeric".into())
+    };
+
+    let ndigits = read(0)? as usize;
+    let weight = read(2)? as i16 as i32;
+    let sign = read(4)?;
+    let dscale = read(6)? as usize;
+    let digits = (0..ndigits)
+        .map(|i| read(8 + i * 2))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if sign == 0xC000 {
+        return Ok(String::from("NaN"));
+    }
+
+    let digit = |position: i32| -> u16 {
+        usize::try_from(position)
+            .ok()
+            .and_then(|p| digits.get(p).copied())
+            .unwrap_or(0)
+    };
+
+    let mut result = String::new();
+    if sign == 0x4000 {
+        result.push('-');
+    }
+
+    if weight < 0 {
+        result.push('0');
+    } else {
+        result.push_str(&digit(0).to_string());
+        for position in 1..=weight {
+            result.push_str(&format!("{:04}", digit(position)));
+        }
+    }
+
+    if dscale > 0 {
+        let mut fraction = String::new();
+        let mut position = weight + 1;
+        while fraction.len() < dscale {
+            fraction.push_str(&format!("{:04}", digit(position)));
+            position += 1;
+        }
+        fraction.truncate(dscale);
+
+        result.push('.');
+        result.push_str(&fraction);
+    }
+
+    Ok(result)
+}
+
+impl<C: DerefMut<Target = Client>> Storage for PostgresStorage<C> {
+    fn dialect(&self) -> Dialect {
+        Dialect::Postgres
+    }
+
+    fn execute(&self, sql: &str, params: &[Value]) -> Result<u64, StorageError> {
+        let mut client = self.client.borrow_mut();
+        let statement = self.prepare(&mut client, sql)?;
+
+        Ok(client.execute(&statement, &Self::params(params))?)
+    }
+
+    fn execute_batch(&self, sql: &str) -> Result<(), StorageError> {
+        Ok(self.client.borrow_mut().batch_execute(sql)?)
+    }
+
+    fn query(&self, sql: &str, params: &[Value]) -> Result<Vec<Row>, StorageError> {
+        let mut client = self.client.borrow_mut();
+        let statement = self.prepare(&mut client, sql)?;
+        let rows = client.query(&statement, &Self::params(params))?;
+
+        rows.iter()
+            .map(|row| {
+                (0..row.len())
+                    .map(|i| row.try_get::<_, Value>(i))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map(Row)
+                    .map_err(StorageError::from)
+            })
+            .collect()
+    }
+
+    fn table_exists(&self, table: &str) -> Result<bool, StorageError> {
+        let count: u64 = self
+            .query_row(
+                "SELECT COUNT(*) FROM information_schema.tables
+                WHERE table_schema = current_schema() AND table_name = $1",
+                &[table.into()],
+            )?
+            .map_or(Ok(0), |row| row.get(0))?;
+
+        Ok(count == 1)
+    }
+
+    fn schema_version(&self) -> Result<u32, StorageError> {
+        if !self.table_exists("schema_version")? {
+            return Ok(0);
+        }
+
+        self.query_row("SELECT version FROM schema_version", &[])?
+            .map_or(Ok(0), |row| row.get(0))
+    }
+
+    fn set_schema_version(&self, version: u32) -> Result<(), StorageError> {
+        self.execute_batch("CREATE TABLE IF NOT EXISTS schema_version (version BIGINT NOT NULL)")?;
+        self.execute("DELETE FROM schema_version", &[])?;
+        self.execute(
+            "INSERT INTO schema_version (version) VALUES ($1)",
+            &[version.into()],
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::postgres::{decode_numeric, PostgresStorage};
+    use crate::db::storage::{in_transaction, Storage, StorageError};
+
+    #[test]
+    fn test_decode_numeric() {
+        // 12345678.90, weight 1 with digits [1234, 5678, 9000] and scale 2
+        let raw = [0, 3, 0, 1, 0, 0, 0, 2, 0x04, 0xD2, 0x16, 0x2E, 0x23, 0x28];
+        assert_eq!(decode_numeric(&raw).unwrap(), "12345678.90");
+
+        // -0.0005
+        let raw = [0, 1, 0xFF, 0xFF, 0x40, 0, 0, 4, 0, 5];
+        assert_eq!(decode_numeric(&raw).unwrap(), "-0.0005");
+
+        // 20000, stored as a single digit of weight 1
+        let raw = [0, 1, 0, 1, 0, 0, 0, 0, 0, 2];
+        assert_eq!(decode_numeric(&raw).unwrap(), "20000");
+
+        // zero has no digits
+        assert_eq!(decode_numeric(&[0, 0, 0, 0, 0, 0, 0, 0]).unwrap(), "0");
+    }
+
+    // Needs a scratch database, e.g. SHRIKE_TEST_POSTGRES_URL=postgres://postgres@localhost/shrike_test
+    #[test]
+    fn test_postgres_storage() {
+        let Ok(url) = std::env::var("SHRIKE_TEST_POSTGRES_URL") else {
+            eprintln!("SHRIKE_TEST_POSTGRES_URL not set, skipping.");
+            return;
+        };
+        let storage = PostgresStorage::connect(&url).unwrap();
+
+        storage
+            .execute_batch(
+                "DROP TABLE IF EXISTS storage_test;
+                DROP TABLE IF EXISTS schema_version;
+                CREATE TABLE storage_test (id BIGINT PRIMARY KEY, name TEXT, amount DOUBLE PRECISION, small INTEGER);",
+            )
+            .unwrap();
+
+        storage
+            .execute(
+                "INSERT INTO storage_test (name, id, amount, small) VALUES ($2, $1, $3, $4)",
+                &[7.into(), "seven".into(), None::<f64>.into(), 3.into()],
+            )
+            .unwrap();
+
+        let row = storage
+            .query_row(
+                "SELECT id, name, amount, small, SUM(id) OVER () FROM storage_test WHERE id = $1",
+                &[7.into()],
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.get::<u64>(0).unwrap(), 7);
+        assert_eq!(row.get::<String>(1).unwrap(), "seven");
+        assert_eq!(row.get::<Option<f64>>(2).unwrap(), None);
+        assert_eq!(row.get::<u8>(3).unwrap(), 3);
+        assert_eq!(row.get::<u64>(4).unwrap(), 7);
+
+        let failed: Result<(), StorageError> = in_transaction(&storage, |s| {
+            s.execute("INSERT INTO storage_test (id) VALUES ($1)", &[8.into()])?;
+            s.execute("INSERT INTO storage_test (id) VALUES ($1)", &[7.into()])?;
+            Ok(())
+        });
+        assert!(failed.is_err());
+        assert!(storage
+            .query_row("SELECT id FROM storage_test WHERE id = 8", &[])
+            .unwrap()
+            .is_none());
+
+        assert!(storage.table_exists("storage_test").unwrap());
+        assert_eq!(storage.schema_version().unwrap(), 0);
+        storage.set_schema_version(2).unwrap();
+        assert_eq!(storage.schema_version().unwrap(), 2);
+
+        storage
+            .execute_batch("DROP TABLE storage_test; DROP TABLE schema_version;")
+            .unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+use rusqlite::types::{ToSqlOutput, ValueRef};
+use rusqlite::{params_from_iter, Connection, ToSql};
+
+use std::borrow::Cow;
+use std::ops::Deref;
+use std::path::Path;
+
+use super::storage::{Dialect, Storage, StorageError};
+use super::value::{Row, Value};
+
+// Works on an owned connection or anything that derefs to one, e.g. a pooled connection
+pub struct SqliteStorage<C = Box<Connection>> {
+    conn: C,
+}
+
+impl SqliteStorage {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        Ok(Self::new(Box::new(Connection::open(path)?)))
+    }
+
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Ok(Self::new(Box::new(Connection::open_in_memory()?)))
+    }
+}
+
+impl<C: Deref<Target = Connection>> SqliteStorage<C> {
+    pub fn new(conn: C) -> Self {
+        Self { conn }
+    }
+
+    pub fn connection(&self) -> &Connection {
+        &self.conn
+    }
+}
+
+impl ToSql for Value {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(match self {
+            Value::Null => ToSqlOutput::Borrowed(ValueRef::Null),
+            Value::Integer(i) => ToSqlOutput::Borrowed(ValueRef::Integer(*i)),
+            Value::Real(f) => ToSqlOutput::Borrowed(ValueRef::Real(*f)),
+            Value::Text(s) => ToSqlOutput::Borrowed(ValueRef::Text(s.as_bytes())),
+            Value::Blob(b) => ToSqlOutput::Borrowed(ValueRef::Blob(b)),
+        })
+    }
+}
+
+fn to_value(value: ValueRef) -> Value {
+    match value {
+        ValueRef::Null => Value::Null,
+        ValueRef::Integer(i) => Value::Integer(i),
+        ValueRef::Real(f) => Value::Real(f),
+        ValueRef::Text(s) => Value::Text(String::from_utf8_lossy(s).into_owned()),
+        ValueRef::Blob(b) => Value::Blob(b.to_vec()),
+    }
+}
+
+// SQLite numbers `$N` parameters in order of appearance, `?N` binds them by position like Postgres does
+pub fn numbered_placeholders(sql: &str) -> Cow<'_, str> {
+    if !sql.contains('$') {
+        return Cow::Borrowed(sql);
+    }
+
+    let mut result = String::with_capacity(sql.len());
+    let mut chars = sql.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => in_string = !in_string,
+            '$' if !in_string && chars.peek().is_some_and(char::is_ascii_digit) => {
+                result.push('?');
+                continue;
+            }
+            _ => {}
+        }
+        result.push(c);
+    }
+
+    Cow::Owned(result)
+}
+
+impl<C: Deref<Target = Connection>> Storage for SqliteStorage<C> {
+    fn dialect(&self) -> Dialect {
+        Dialect::Sqlite
+    }
+
+    fn execute(&self, sql: &str, params: &[Value]) -> Result<u64, StorageError> {
+        let mut stmt = self.conn.prepare_cached(&numbered_placeholders(sql))?;
+        let changed = stmt.execute(params_from_iter(params))?;
+
+        Ok(changed as u64)
+    }
+
+    fn execute_batch(&self, sql: &str) -> Result<(), StorageError> {
+        Ok(self.conn.execute_batch(sql)?)
+    }
+
+    fn query(&self, sql: &str, params: &[Value]) -> Result<Vec<Row>, StorageError> {
+        let mut stmt = self.conn.prepare_cached(&numbered_placeholders(sql))?;
+        let columns = stmt.column_count();
+        let mut rows = stmt.query(params_from_iter(params))?;
+
+        let mut result = Vec::new();
+        while let Some(row) = rows.next()? {
+            let values = (0..columns)
+                .map(|i| row.get_ref(i).map(to_value))
+                .collect::<Result<Vec<_>, _>>()?;
+            result.push(Row(values));
+        }
+
+        Ok(result)
+    }
+
+    fn table_exists(&self, table: &str) -> Result<bool, StorageError> {
+        let count: u32 = self.conn.query_row(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+            [table],
+            |row| row.get(0),
+        )?;
+
+        Ok(count == 1)
+    }
+
+    fn schema_version(&self) -> Result<u32, StorageError> {
+        Ok(self
+            .conn
+            .query_row("PRAGMA user_version", [], |row| row.get(0))?)
+    }
+
+    fn set_schema_version(&self, version: u32) -> Result<(), StorageError> {
+        Ok(self.conn.pragma_update(None, "user_version", version)?)
+    }
+}
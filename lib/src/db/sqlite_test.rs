@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::sqlite::{numbered_placeholders, SqliteStorage};
+    use crate::db::storage::{in_transaction, Storage, StorageError};
+    use crate::db::value::Value;
+    use std::borrow::Cow;
+
+    #[test]
+    fn test_numbered_placeholders() {
+        assert_eq!(
+            numbered_placeholders("SELECT * FROM blocks WHERE id = $2 AND hash = $1"),
+            "SELECT * FROM blocks WHERE id = ?2 AND hash = ?1"
+        );
+        assert_eq!(
+            numbered_placeholders("SELECT '$1' FROM t WHERE a = $1"),
+            "SELECT '$1' FROM t WHERE a = ?1"
+        );
+        assert!(matches!(
+            numbered_placeholders("SELECT 1"),
+            Cow::Borrowed("SELECT 1")
+        ));
+    }
+
+    #[test]
+    fn test_sqlite_storage() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        storage
+            .execute_batch("CREATE TABLE t (id INTEGER PRIMARY KEY, name TEXT, amount FLOAT)")
+            .unwrap();
+
+        let changed = storage
+            .execute(
+                "INSERT INTO t (name, id, amount) VALUES ($2, $1, $3)",
+                &[
+                    Value::from(7_u64),
+                    Value::from("seven"),
+                    Value::from(None::<f64>),
+                ],
+            )
+            .unwrap();
+        assert_eq!(changed, 1);
+
+        let row = storage
+            .query_row("SELECT id, name, amount FROM t WHERE id = $1", &[7.into()])
+            .unwrap()
+            .unwrap();
+        assert_eq!(row.get::<u64>(0).unwrap(), 7);
+        assert_eq!(row.get::<String>(1).unwrap(), "seven");
+        assert_eq!(row.get::<Option<f64>>(2).unwrap(), None);
+
+        assert!(storage.table_exists("t").unwrap());
+        assert!(!storage.table_exists("missing").unwrap());
+
+        assert_eq!(storage.schema_version().unwrap(), 0);
+        storage.set_schema_version(3).unwrap();
+        assert_eq!(storage.schema_version().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_sqlite_transaction_rollback() {
+        let storage = SqliteStorage::open_in_memory().unwrap();
+        storage
+            .execute_batch("CREATE TABLE t (id INTEGER)")
+            .unwrap();
+
+        let result: Result<(), StorageError> = in_transaction(&storage, |s| {
+            s.execute("INSERT INTO t VALUES ($1)", &[1.into()])?;
+            s.execute("INSERT INTO missing VALUES ($1)", &[2.into()])?;
+            Ok(())
+        });
+        assert!(result.is_err());
+
+        let count: u64 = storage
+            .query_row("SELECT COUNT(*) FROM t", &[])
+            .unwrap()
+            .unwrap()
+            .get(0)
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+}
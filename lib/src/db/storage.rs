@@ -0,0 +1,93 @@
+use super::value::{Row, Value};
+
+// SQL is written once with `$1`-style placeholders, which both backends understand.
+// Anything that is not portable (DDL types, journal settings) branches on the dialect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Sqlite,
+    Postgres,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[cfg(feature = "sqlite")]
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+    #[cfg(feature = "postgres")]
+    #[error(transparent)]
+    Postgres(#[from] postgres::Error),
+    #[error("failed to decode column: {0}")]
+    Decode(String),
+    #[error("unsupported database url: {0}")]
+    Unsupported(String),
+}
+
+pub trait Storage {
+    fn dialect(&self) -> Dialect;
+
+    // returns the number of rows changed
+    fn execute(&self, sql: &str, params: &[Value]) -> Result<u64, StorageError>;
+
+    // runs several statements without parameters
+    fn execute_batch(&self, sql: &str) -> Result<(), StorageError>;
+
+    fn query(&self, sql: &str, params: &[Value]) -> Result<Vec<Row>, StorageError>;
+
+    fn query_row(&self, sql: &str, params: &[Value]) -> Result<Option<Row>, StorageError> {
+        Ok(self.query(sql, params)?.into_iter().next())
+    }
+
+    fn table_exists(&self, table: &str) -> Result<bool, StorageError>;
+
+    fn schema_version(&self) -> Result<u32, StorageError>;
+
+    fn set_schema_version(&self, version: u32) -> Result<(), StorageError>;
+}
+
+// Runs `f` in a transaction that is rolled back if it returns an error
+pub fn in_transaction<T, E>(
+    storage: &dyn Storage,
+    f: impl FnOnce(&dyn Storage) -> Result<T, E>,
+) -> Result<T, E>
+where
+    E: From<StorageError>,
+{
+    storage.execute_batch("BEGIN")?;
+
+    match f(storage) {
+        Ok(result) => {
+            storage.execute_batch("COMMIT")?;
+            Ok(result)
+        }
+        Err(e) => {
+            // the original error is more useful than a failed rollback
+            let _ = storage.execute_batch("ROLLBACK");
+            Err(e)
+        }
+    }
+}
+
+pub fn is_postgres_url(url: &str) -> bool {
+    url.starts_with("postgres://") || url.starts_with("postgresql://")
+}
+
+// Opens a standalone connection, a Postgres URL or a SQLite file path
+pub fn open(url: &str) -> Result<Box<dyn Storage>, StorageError> {
+    if is_postgres_url(url) {
+        #[cfg(feature = "postgres")]
+        return Ok(Box::new(super::postgres::PostgresStorage::connect(url)?));
+
+        #[cfg(not(feature = "postgres"))]
+        return Err(StorageError::Unsupported(String::from(
+            "Postgres support is not compiled in, rebuild with the `postgres` feature",
+        )));
+    }
+
+    #[cfg(feature = "sqlite")]
+    return Ok(Box::new(super::sqlite::SqliteStorage::open(url)?));
+
+    #[cfg(not(feature = "sqlite"))]
+    Err(StorageError::Unsupported(String::from(
+        "SQLite support is not compiled in, rebuild with the `sqlite` feature",
+    )))
+}
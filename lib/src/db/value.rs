@@ -0,0 +1,200 @@
+use super::StorageError;
+use crate::integer::BigInt;
+
+// The column types Shrike stores, shared by every backend
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+macro_rules! integer_value {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Value {
+                fn from(v: $t) -> Self {
+                    Value::Integer(v as i64)
+                }
+            }
+        )*
+    };
+}
+
+integer_value!(i8, i16, i32, i64, u8, u16, u32, u64, usize);
+
+impl From<bool> for Value {
+    fn from(v: bool) -> Self {
+        Value::Integer(v as i64)
+    }
+}
+
+impl From<f64> for Value {
+    fn from(v: f64) -> Self {
+        Value::Real(v)
+    }
+}
+
+impl From<String> for Value {
+    fn from(v: String) -> Self {
+        Value::Text(v)
+    }
+}
+
+impl From<&String> for Value {
+    fn from(v: &String) -> Self {
+        Value::Text(v.clone())
+    }
+}
+
+impl From<&str> for Value {
+    fn from(v: &str) -> Self {
+        Value::Text(v.to_string())
+    }
+}
+
+impl From<Vec<u8>> for Value {
+    fn from(v: Vec<u8>) -> Self {
+        Value::Blob(v)
+    }
+}
+
+// stored as decimal text, since amounts outgrow every numeric column type
+impl From<&BigInt> for Value {
+    fn from(v: &BigInt) -> Self {
+        Value::Text(v.to_string())
+    }
+}
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(v: Option<T>) -> Self {
+        v.map_or(Value::Null, Into::into)
+    }
+}
+
+pub trait FromValue: Sized {
+    fn from_value(value: &Value) -> Result<Self, StorageError>;
+}
+
+fn mismatch(expected: &str, value: &Value) -> StorageError {
+    StorageError::Decode(format!("expected {expected}, got {value:?}"))
+}
+
+// aggregates come back as NUMERIC text from Postgres, so numbers are parsed from text too
+macro_rules! integer_from_value {
+    ($($t:ty),*) => {
+        $(
+            impl FromValue for $t {
+                fn from_value(value: &Value) -> Result<Self, StorageError> {
+                    let n = match value {
+                        Value::Integer(i) => *i,
+                        Value::Real(f) if f.fract() == 0.0 => *f as i64,
+                        Value::Text(s) => s
+                            .trim()
+                            .parse()
+                            .map_err(|_| mismatch(stringify!($t), value))?,
+                        _ => return Err(mismatch(stringify!($t), value)),
+                    };
+
+                    <$t>::try_from(n).map_err(|_| mismatch(stringify!($t), value))
+                }
+            }
+        )*
+    };
+}
+
+integer_from_value!(i32, i64, u8, u16, u32, u64, usize);
+
+impl FromValue for bool {
+    fn from_value(value: &Value) -> Result<Self, StorageError> {
+        i64::from_value(value).map(|i| i != 0)
+    }
+}
+
+impl FromValue for f64 {
+    fn from_value(value: &Value) -> Result<Self, StorageError> {
+        match value {
+            Value::Integer(i) => Ok(*i as f64),
+            Value::Real(f) => Ok(*f),
+            Value::Text(s) => s.trim().parse().map_err(|_| mismatch("f64", value)),
+            _ => Err(mismatch("f64", value)),
+        }
+    }
+}
+
+impl FromValue for BigInt {
+    fn from_value(value: &Value) -> Result<Self, StorageError> {
+        match value {
+            Value::Integer(i) => Ok(BigInt::from(*i)),
+            Value::Text(s) => s.trim().parse().map_err(|_| mismatch("integer", value)),
+            _ => Err(mismatch("integer", value)),
+        }
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &Value) -> Result<Self, StorageError> {
+        match value {
+            Value::Text(s) => Ok(s.clone()),
+            Value::Integer(i) => Ok(i.to_string()),
+            Value::Real(f) => Ok(f.to_string()),
+            _ => Err(mismatch("text", value)),
+        }
+    }
+}
+
+impl FromValue for Vec<u8> {
+    fn from_value(value: &Value) -> Result<Self, StorageError> {
+        match value {
+            Value::Blob(b) => Ok(b.clone()),
+            Value::Text(s) => Ok(s.as_bytes().to_vec()),
+            _ => Err(mismatch("blob", value)),
+        }
+    }
+}
+
+// JSON columns are stored as text
+impl FromValue for serde_json::Value {
+    fn from_value(value: &Value) -> Result<Self, StorageError> {
+        match value {
+            Value::Text(s) => {
+                serde_json::from_str(s).map_err(|e| StorageError::Decode(e.to_string()))
+            }
+            Value::Null => Ok(serde_json::Value::Null),
+            _ => Err(mismatch("json text", value)),
+        }
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &Value) -> Result<Self, StorageError> {
+        match value {
+            Value::Null => Ok(None),
+            v => T::from_value(v).map(Some),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row(pub Vec<Value>);
+
+impl Row {
+    pub fn get<T: FromValue>(&self, index: usize) -> Result<T, StorageError> {
+        let value = self
+            .0
+            .get(index)
+            .ok_or_else(|| StorageError::Decode(format!("no column at index {index}")))?;
+
+        T::from_value(value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
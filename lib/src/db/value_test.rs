@@ -0,0 +1,39 @@
+#[cfg(test)]
+mod tests {
+    use crate::db::value::{Row, Value};
+    use crate::integer::BigInt;
+
+    #[test]
+    fn test_value_round_trip() {
+        let row = Row(vec![
+            Value::from(42_u64),
+            Value::from("0xabc"),
+            Value::from(None::<String>),
+            Value::from(0.5),
+            Value::Text(String::from("1234567890123")),
+            Value::Text(String::from("[1,2]")),
+            Value::from(&"-123456789012345678901234567890".parse::<BigInt>().unwrap()),
+        ]);
+
+        assert_eq!(row.get::<u64>(0).unwrap(), 42);
+        assert_eq!(row.get::<String>(1).unwrap(), "0xabc");
+        assert_eq!(row.get::<Option<String>>(2).unwrap(), None);
+        assert_eq!(row.get::<f64>(3).unwrap(), 0.5);
+        assert_eq!(row.get::<u64>(4).unwrap(), 1234567890123);
+        assert_eq!(
+            row.get::<serde_json::Value>(5).unwrap(),
+            serde_json::json!([1, 2])
+        );
+
+        assert_eq!(
+            row.get::<BigInt>(6).unwrap().to_string(),
+            "-123456789012345678901234567890"
+        );
+        assert_eq!(row.get::<BigInt>(0).unwrap(), BigInt::from(42));
+        assert!(row.get::<u64>(6).is_err());
+
+        assert!(row.get::<u8>(4).is_err());
+        assert!(row.get::<u64>(1).is_err());
+        assert!(row.get::<u64>(7).is_err());
+    }
+}
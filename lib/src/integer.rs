@@ -0,0 +1,66 @@
+// NeoVM integers: arbitrary precision, stored as little-endian two's complement byte strings
+pub use num_bigint::BigInt;
+use num_bigint::Sign;
+
+// the largest integer the VM operates on
+pub const MAX_INTEGER_SIZE: usize = 32;
+
+pub const NEO_DECIMALS: u32 = 0;
+pub const GAS_DECIMALS: u32 = 8;
+
+// an empty byte string is zero
+pub fn decode(bytes: &[u8]) -> BigInt {
+    BigInt::from_signed_bytes_le(bytes)
+}
+
+// the shortest form, as the VM writes it, so zero encodes to no bytes at all
+pub fn encode(value: &BigInt) -> Vec<u8> {
+    match value.sign() {
+        Sign::NoSign => Vec::new(),
+        _ => value.to_signed_bytes_le(),
+    }
+}
+
+// `value` in units of 10^-decimals, without trailing zeros: 40300000 with 8 decimals is "0.403"
+pub fn format_fixed(value: &BigInt, decimals: u32) -> String {
+    let digits = value.magnitude().to_string();
+    let decimals = decimals as usize;
+    let sign = if value.sign() == Sign::Minus { "-" } else { "" };
+
+    if decimals == 0 {
+        return format!("{sign}{digits}");
+    }
+
+    let padded = format!("{digits:0>width$}", width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+
+    match fraction.is_empty() {
+        true => format!("{sign}{whole}"),
+        false => format!("{sign}{whole}.{fraction}"),
+    }
+}
+
+// the inverse of `format_fixed`, rejecting anything more precise than the token allows
+pub fn parse_fixed(s: &str, decimals: u32) -> Option<BigInt> {
+    let (negative, unsigned) = match s.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, s),
+    };
+    let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+
+    if whole.is_empty()
+        || fraction.len() > decimals as usize
+        || !whole
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+
+    let digits = format!("{whole}{fraction:0<width$}", width = decimals as usize);
+    let magnitude: BigInt = digits.parse().ok()?;
+
+    Some(if negative { -magnitude } else { magnitude })
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod tests {
+    use crate::integer::{
+        decode, encode, format_fixed, parse_fixed, BigInt, GAS_DECIMALS, NEO_DECIMALS,
+    };
+
+    #[test]
+    fn test_encode_decode() {
+        for (value, encoded) in [
+            ("0", ""),
+            ("1", "01"),
+            ("-1", "ff"),
+            ("127", "7f"),
+            ("128", "8000"),
+            ("-128", "80"),
+            ("-129", "7fff"),
+            ("255", "ff00"),
+            ("100000000", "00e1f505"),
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00",
+            ),
+        ] {
+            let value: BigInt = value.parse().unwrap();
+            assert_eq!(hex::encode(encode(&value)), encoded);
+            assert_eq!(decode(&hex::decode(encoded).unwrap()), value);
+        }
+
+        // non-minimal encodings still decode
+        assert_eq!(decode(&[0x01, 0x00, 0x00]), BigInt::from(1));
+        assert_eq!(decode(&[0xff, 0xff]), BigInt::from(-1));
+    }
+
+    #[test]
+    fn test_fixed_point() {
+        for (value, decimals, formatted) in [
+            ("40300000", GAS_DECIMALS, "0.403"),
+            ("100000000", GAS_DECIMALS, "1"),
+            ("1", GAS_DECIMALS, "0.00000001"),
+            ("-1", GAS_DECIMALS, "-0.00000001"),
+            ("0", GAS_DECIMALS, "0"),
+            ("123", NEO_DECIMALS, "123"),
+            ("1500000", 6, "1.5"),
+            (
+                "123456789012345678901234567890",
+                18,
+                "123456789012.34567890123456789",
+            ),
+        ] {
+            let value: BigInt = value.parse().unwrap();
+            assert_eq!(format_fixed(&value, decimals), formatted);
+            assert_eq!(parse_fixed(formatted, decimals), Some(value));
+        }
+
+        assert_eq!(parse_fixed("1.000", 8), Some(BigInt::from(100_000_000)));
+        assert_eq!(parse_fixed("0.000000001", 8), None);
+        assert_eq!(parse_fixed("1.5", 0), None);
+        assert_eq!(parse_fixed(".5", 8), None);
+        assert_eq!(parse_fixed("1e8", 8), None);
+        assert_eq!(parse_fixed("-", 8), None);
+    }
+}
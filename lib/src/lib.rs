@@ -1,2 +1,16 @@
+pub mod binary;
+mod binary_test;
 pub mod db;
+pub mod integer;
+mod integer_test;
+pub mod nef;
+mod nef_test;
 pub mod neo;
+pub mod network;
+mod network_test;
+pub mod payloads;
+mod payloads_test;
+pub mod script;
+mod script_test;
+pub mod stack_item;
+mod stack_item_test;
@@ -0,0 +1,159 @@
+// NEF, the executable format contracts are deployed in
+use serde_json::{json, Value};
+
+use crate::binary::{BinaryError, BinaryReader, BinaryWriter, Serializable};
+use crate::neo::checksum;
+use crate::payloads::{hash_to_hex, UInt160};
+use crate::script::call_flags_name;
+
+pub const NEF_MAGIC: u32 = 0x3346454e; // "NEF3"
+pub const COMPILER_LENGTH: usize = 64;
+pub const MAX_SOURCE_LENGTH: usize = 256;
+pub const MAX_TOKENS: usize = 128;
+pub const MAX_METHOD_LENGTH: usize = 32;
+pub const MAX_NEF_SCRIPT_LENGTH: usize = 512 * 1024;
+
+fn read_var_string(reader: &mut BinaryReader, max: usize) -> Result<String, BinaryError> {
+    String::from_utf8(reader.read_var_bytes(max)?).map_err(|_| BinaryError::InvalidValue("string"))
+}
+
+// a contract call the script makes with CALLT
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodToken {
+    pub hash: UInt160,
+    pub method: String,
+    pub parameters_count: u16,
+    pub has_return_value: bool,
+    pub call_flags: u8,
+}
+
+impl Serializable for MethodToken {
+    fn serialize(&self, writer: &mut BinaryWriter) {
+        self.hash.serialize(writer);
+        writer.write_var_bytes(self.method.as_bytes());
+        writer.write_u16(self.parameters_count);
+        writer.write_bool(self.has_return_value);
+        writer.write_u8(self.call_flags);
+    }
+
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError> {
+        let hash = UInt160::deserialize(reader)?;
+        let method = read_var_string(reader, MAX_METHOD_LENGTH)?;
+        if method.starts_with('_') {
+            return Err(BinaryError::InvalidValue("method token name"));
+        }
+
+        Ok(MethodToken {
+            hash,
+            method,
+            parameters_count: reader.read_u16()?,
+            has_return_value: reader.read_bool()?,
+            call_flags: reader.read_u8()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NefFile {
+    pub compiler: String,
+    pub source: String,
+    pub tokens: Vec<MethodToken>,
+    pub script: Vec<u8>,
+    pub checksum: u32,
+}
+
+impl NefFile {
+    fn serialize_unchecked(&self, writer: &mut BinaryWriter) {
+        let mut compiler = self.compiler.as_bytes().to_vec();
+        compiler.resize(COMPILER_LENGTH, 0);
+
+        writer.write_u32(NEF_MAGIC);
+        writer.write_bytes(&compiler);
+        writer.write_var_bytes(self.source.as_bytes());
+        writer.write_u8(0); // reserved
+        writer.write_items(&self.tokens);
+        writer.write_u16(0); // reserved
+        writer.write_var_bytes(&self.script);
+    }
+
+    // the first four bytes of the double SHA256 of everything before the checksum
+    pub fn compute_checksum(&self) -> u32 {
+        let mut writer = BinaryWriter::new();
+        self.serialize_unchecked(&mut writer);
+        let hash = checksum(&writer.into_bytes());
+
+        u32::from_le_bytes([hash[0], hash[1], hash[2], hash[3]])
+    }
+
+    // as NeoGo returns it in getcontractstate
+    pub fn to_json(&self) -> Value {
+        let tokens: Vec<Value> = self
+            .tokens
+            .iter()
+            .map(|token| {
+                json!({
+                    "hash": hash_to_hex(&token.hash),
+                    "method": token.method,
+                    "paramcount": token.parameters_count,
+                    "hasreturnvalue": token.has_return_value,
+                    "callflags": call_flags_name(token.call_flags),
+                })
+            })
+            .collect();
+
+        json!({
+            "magic": NEF_MAGIC,
+            "compiler": self.compiler,
+            "source": self.source,
+            "tokens": tokens,
+            "script": base64::encode(&self.script),
+            "checksum": self.checksum,
+        })
+    }
+}
+
+impl Serializable for NefFile {
+    fn serialize(&self, writer: &mut BinaryWriter) {
+        self.serialize_unchecked(writer);
+        writer.write_u32(self.checksum);
+    }
+
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError> {
+        if reader.read_u32()? != NEF_MAGIC {
+            return Err(BinaryError::InvalidValue("NEF magic"));
+        }
+
+        let compiler = reader.read_array::<COMPILER_LENGTH>()?;
+        let compiler = std::str::from_utf8(&compiler)
+            .map_err(|_| BinaryError::InvalidValue("compiler"))?
+            .trim_end_matches('\0')
+            .to_string();
+        let source = read_var_string(reader, MAX_SOURCE_LENGTH)?;
+
+        if reader.read_u8()? != 0 {
+            return Err(BinaryError::InvalidValue("reserved byte"));
+        }
+        let tokens = reader.read_items(MAX_TOKENS)?;
+        if reader.read_u16()? != 0 {
+            return Err(BinaryError::InvalidValue("reserved bytes"));
+        }
+
+        let script = reader.read_var_bytes(MAX_NEF_SCRIPT_LENGTH)?;
+        if script.is_empty() {
+            return Err(BinaryError::InvalidValue("empty NEF script"));
+        }
+
+        let nef = NefFile {
+            compiler,
+            source,
+            tokens,
+            script,
+            checksum: reader.read_u32()?,
+        };
+        if nef.checksum != nef.compute_checksum() {
+            return Err(BinaryError::InvalidValue("NEF checksum"));
+        }
+
+        Ok(nef)
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(test)]
+mod tests {
+    use crate::binary::{BinaryError, Serializable};
+    use crate::nef::{MethodToken, NefFile};
+
+    #[test]
+    fn test_nef_round_trip() {
+        let mut nef = NefFile {
+            compiler: String::from("neo-core-v3.0"),
+            source: String::new(),
+            tokens: vec![MethodToken {
+                hash: crate::payloads::hash160_from_hex(
+                    "0xfffdc93764dbaddd97c48f252a53ea4643faa3fd",
+                )
+                .unwrap(),
+                method: String::from("update"),
+                parameters_count: 3,
+                has_return_value: false,
+                call_flags: 0x0f,
+            }],
+            script: vec![0x10, 0x41, 0x1a, 0xf7, 0x7b, 0x67, 0x40],
+            checksum: 0,
+        };
+        nef.checksum = nef.compute_checksum();
+
+        let bytes = nef.to_bytes();
+        assert_eq!(&bytes[..4], b"NEF3");
+        assert_eq!(NefFile::from_bytes(&bytes), Ok(nef.clone()));
+
+        let json = nef.to_json();
+        assert_eq!(json["magic"], 860243278);
+        assert_eq!(json["script"], "EEEa93tnQA==");
+        assert_eq!(
+            json["tokens"][0]["hash"],
+            "0xfffdc93764dbaddd97c48f252a53ea4643faa3fd"
+        );
+        assert_eq!(json["tokens"][0]["callflags"], "All");
+
+        let mut tampered = bytes.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            NefFile::from_bytes(&tampered),
+            Err(BinaryError::InvalidValue("NEF checksum"))
+        );
+    }
+}
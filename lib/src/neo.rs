@@ -578,6 +578,811 @@ static OPCODETABLE: Lazy<HashMap<u8, OpcodeData>> = Lazy::new(|| {
                 size: 2,
             },
         ),
+        (
+            0x58,
+            OpcodeData {
+                name: "LDSFLD0",
+                size: 0,
+            },
+        ),
+        (
+            0x59,
+            OpcodeData {
+                name: "LDSFLD1",
+                size: 0,
+            },
+        ),
+        (
+            0x5a,
+            OpcodeData {
+                name: "LDSFLD2",
+                size: 0,
+            },
+        ),
+        (
+            0x5b,
+            OpcodeData {
+                name: "LDSFLD3",
+                size: 0,
+            },
+        ),
+        (
+            0x5c,
+            OpcodeData {
+                name: "LDSFLD4",
+                size: 0,
+            },
+        ),
+        (
+            0x5d,
+            OpcodeData {
+                name: "LDSFLD5",
+                size: 0,
+            },
+        ),
+        (
+            0x5e,
+            OpcodeData {
+                name: "LDSFLD6",
+                size: 0,
+            },
+        ),
+        (
+            0x5f,
+            OpcodeData {
+                name: "LDSFLD",
+                size: 1,
+            },
+        ),
+        (
+            0x60,
+            OpcodeData {
+                name: "STSFLD0",
+                size: 0,
+            },
+        ),
+        (
+            0x61,
+            OpcodeData {
+                name: "STSFLD1",
+                size: 0,
+            },
+        ),
+        (
+            0x62,
+            OpcodeData {
+                name: "STSFLD2",
+                size: 0,
+            },
+        ),
+        (
+            0x63,
+            OpcodeData {
+                name: "STSFLD3",
+                size: 0,
+            },
+        ),
+        (
+            0x64,
+            OpcodeData {
+                name: "STSFLD4",
+                size: 0,
+            },
+        ),
+        (
+            0x65,
+            OpcodeData {
+                name: "STSFLD5",
+                size: 0,
+            },
+        ),
+        (
+            0x66,
+            OpcodeData {
+                name: "STSFLD6",
+                size: 0,
+            },
+        ),
+        (
+            0x67,
+            OpcodeData {
+                name: "STSFLD",
+                size: 1,
+            },
+        ),
+        (
+            0x68,
+            OpcodeData {
+                name: "LDLOC0",
+                size: 0,
+            },
+        ),
+        (
+            0x69,
+            OpcodeData {
+                name: "LDLOC1",
+                size: 0,
+            },
+        ),
+        (
+            0x6a,
+            OpcodeData {
+                name: "LDLOC2",
+                size: 0,
+            },
+        ),
+        (
+            0x6b,
+            OpcodeData {
+                name: "LDLOC3",
+                size: 0,
+            },
+        ),
+        (
+            0x6c,
+            OpcodeData {
+                name: "LDLOC4",
+                size: 0,
+            },
+        ),
+        (
+            0x6d,
+            OpcodeData {
+                name: "LDLOC5",
+                size: 0,
+            },
+        ),
+        (
+            0x6e,
+            OpcodeData {
+                name: "LDLOC6",
+                size: 0,
+            },
+        ),
+        (
+            0x6f,
+            OpcodeData {
+                name: "LDLOC",
+                size: 1,
+            },
+        ),
+        (
+            0x70,
+            OpcodeData {
+                name: "STLOC0",
+                size: 0,
+            },
+        ),
+        (
+            0x71,
+            OpcodeData {
+                name: "STLOC1",
+                size: 0,
+            },
+        ),
+        (
+            0x72,
+            OpcodeData {
+                name: "STLOC2",
+                size: 0,
+            },
+        ),
+        (
+            0x73,
+            OpcodeData {
+                name: "STLOC3",
+                size: 0,
+            },
+        ),
+        (
+            0x74,
+            OpcodeData {
+                name: "STLOC4",
+                size: 0,
+            },
+        ),
+        (
+            0x75,
+            OpcodeData {
+                name: "STLOC5",
+                size: 0,
+            },
+        ),
+        (
+            0x76,
+            OpcodeData {
+                name: "STLOC6",
+                size: 0,
+            },
+        ),
+        (
+            0x77,
+            OpcodeData {
+                name: "STLOC",
+                size: 1,
+            },
+        ),
+        (
+            0x78,
+            OpcodeData {
+                name: "LDARG0",
+                size: 0,
+            },
+        ),
+        (
+            0x79,
+            OpcodeData {
+                name: "LDARG1",
+                size: 0,
+            },
+        ),
+        (
+            0x7a,
+            OpcodeData {
+                name: "LDARG2",
+                size: 0,
+            },
+        ),
+        (
+            0x7b,
+            OpcodeData {
+                name: "LDARG3",
+                size: 0,
+            },
+        ),
+        (
+            0x7c,
+            OpcodeData {
+                name: "LDARG4",
+                size: 0,
+            },
+        ),
+        (
+            0x7d,
+            OpcodeData {
+                name: "LDARG5",
+                size: 0,
+            },
+        ),
+        (
+            0x7e,
+            OpcodeData {
+                name: "LDARG6",
+                size: 0,
+            },
+        ),
+        (
+            0x7f,
+            OpcodeData {
+                name: "LDARG",
+                size: 1,
+            },
+        ),
+        (
+            0x80,
+            OpcodeData {
+                name: "STARG0",
+                size: 0,
+            },
+        ),
+        (
+            0x81,
+            OpcodeData {
+                name: "STARG1",
+                size: 0,
+            },
+        ),
+        (
+            0x82,
+            OpcodeData {
+                name: "STARG2",
+                size: 0,
+            },
+        ),
+        (
+            0x83,
+            OpcodeData {
+                name: "STARG3",
+                size: 0,
+            },
+        ),
+        (
+            0x84,
+            OpcodeData {
+                name: "STARG4",
+                size: 0,
+            },
+        ),
+        (
+            0x85,
+            OpcodeData {
+                name: "STARG5",
+                size: 0,
+            },
+        ),
+        (
+            0x86,
+            OpcodeData {
+                name: "STARG6",
+                size: 0,
+            },
+        ),
+        (
+            0x87,
+            OpcodeData {
+                name: "STARG",
+                size: 1,
+            },
+        ),
+        (
+            0x88,
+            OpcodeData {
+                name: "NEWBUFFER",
+                size: 0,
+            },
+        ),
+        (
+            0x89,
+            OpcodeData {
+                name: "MEMCPY",
+                size: 0,
+            },
+        ),
+        (
+            0x8b,
+            OpcodeData {
+                name: "CAT",
+                size: 0,
+            },
+        ),
+        (
+            0x8c,
+            OpcodeData {
+                name: "SUBSTR",
+                size: 0,
+            },
+        ),
+        (
+            0x8d,
+            OpcodeData {
+                name: "LEFT",
+                size: 0,
+            },
+        ),
+        (
+            0x8e,
+            OpcodeData {
+                name: "RIGHT",
+                size: 0,
+            },
+        ),
+        (
+            0x90,
+            OpcodeData {
+                name: "INVERT",
+                size: 0,
+            },
+        ),
+        (
+            0x91,
+            OpcodeData {
+                name: "AND",
+                size: 0,
+            },
+        ),
+        (
+            0x92,
+            OpcodeData {
+                name: "OR",
+                size: 0,
+            },
+        ),
+        (
+            0x93,
+            OpcodeData {
+                name: "XOR",
+                size: 0,
+            },
+        ),
+        (
+            0x97,
+            OpcodeData {
+                name: "EQUAL",
+                size: 0,
+            },
+        ),
+        (
+            0x98,
+            OpcodeData {
+                name: "NOTEQUAL",
+                size: 0,
+            },
+        ),
+        (
+            0x99,
+            OpcodeData {
+                name: "SIGN",
+                size: 0,
+            },
+        ),
+        (
+            0x9a,
+            OpcodeData {
+                name: "ABS",
+                size: 0,
+            },
+        ),
+        (
+            0x9b,
+            OpcodeData {
+                name: "NEGATE",
+                size: 0,
+            },
+        ),
+        (
+            0x9c,
+            OpcodeData {
+                name: "INC",
+                size: 0,
+            },
+        ),
+        (
+            0x9d,
+            OpcodeData {
+                name: "DEC",
+                size: 0,
+            },
+        ),
+        (
+            0x9e,
+            OpcodeData {
+                name: "ADD",
+                size: 0,
+            },
+        ),
+        (
+            0x9f,
+            OpcodeData {
+                name: "SUB",
+                size: 0,
+            },
+        ),
+        (
+            0xa0,
+            OpcodeData {
+                name: "MUL",
+                size: 0,
+            },
+        ),
+        (
+            0xa1,
+            OpcodeData {
+                name: "DIV",
+                size: 0,
+            },
+        ),
+        (
+            0xa2,
+            OpcodeData {
+                name: "MOD",
+                size: 0,
+            },
+        ),
+        (
+            0xa3,
+            OpcodeData {
+                name: "POW",
+                size: 0,
+            },
+        ),
+        (
+            0xa4,
+            OpcodeData {
+                name: "SQRT",
+                size: 0,
+            },
+        ),
+        (
+            0xa5,
+            OpcodeData {
+                name: "MODMUL",
+                size: 0,
+            },
+        ),
+        (
+            0xa6,
+            OpcodeData {
+                name: "MODPOW",
+                size: 0,
+            },
+        ),
+        (
+            0xa8,
+            OpcodeData {
+                name: "SHL",
+                size: 0,
+            },
+        ),
+        (
+            0xa9,
+            OpcodeData {
+                name: "SHR",
+                size: 0,
+            },
+        ),
+        (
+            0xaa,
+            OpcodeData {
+                name: "NOT",
+                size: 0,
+            },
+        ),
+        (
+            0xab,
+            OpcodeData {
+                name: "BOOLAND",
+                size: 0,
+            },
+        ),
+        (
+            0xac,
+            OpcodeData {
+                name: "BOOLOR",
+                size: 0,
+            },
+        ),
+        (
+            0xb1,
+            OpcodeData {
+                name: "NZ",
+                size: 0,
+            },
+        ),
+        (
+            0xb3,
+            OpcodeData {
+                name: "NUMEQUAL",
+                size: 0,
+            },
+        ),
+        (
+            0xb4,
+            OpcodeData {
+                name: "NUMNOTEQUAL",
+                size: 0,
+            },
+        ),
+        (
+            0xb5,
+            OpcodeData {
+                name: "LT",
+                size: 0,
+            },
+        ),
+        (
+            0xb6,
+            OpcodeData {
+                name: "LE",
+                size: 0,
+            },
+        ),
+        (
+            0xb7,
+            OpcodeData {
+                name: "GT",
+                size: 0,
+            },
+        ),
+        (
+            0xb8,
+            OpcodeData {
+                name: "GE",
+                size: 0,
+            },
+        ),
+        (
+            0xb9,
+            OpcodeData {
+                name: "MIN",
+                size: 0,
+            },
+        ),
+        (
+            0xba,
+            OpcodeData {
+                name: "MAX",
+                size: 0,
+            },
+        ),
+        (
+            0xbb,
+            OpcodeData {
+                name: "WITHIN",
+                size: 0,
+            },
+        ),
+        (
+            0xbe,
+            OpcodeData {
+                name: "PACKMAP",
+                size: 0,
+            },
+        ),
+        (
+            0xbf,
+            OpcodeData {
+                name: "PACKSTRUCT",
+                size: 0,
+            },
+        ),
+        (
+            0xc0,
+            OpcodeData {
+                name: "PACK",
+                size: 0,
+            },
+        ),
+        (
+            0xc1,
+            OpcodeData {
+                name: "UNPACK",
+                size: 0,
+            },
+        ),
+        (
+            0xc2,
+            OpcodeData {
+                name: "NEWARRAY0",
+                size: 0,
+            },
+        ),
+        (
+            0xc3,
+            OpcodeData {
+                name: "NEWARRAY",
+                size: 0,
+            },
+        ),
+        (
+            0xc4,
+            OpcodeData {
+                name: "NEWARRAY_T",
+                size: 1,
+            },
+        ),
+        (
+            0xc5,
+            OpcodeData {
+                name: "NEWSTRUCT0",
+                size: 0,
+            },
+        ),
+        (
+            0xc6,
+            OpcodeData {
+                name: "NEWSTRUCT",
+                size: 0,
+            },
+        ),
+        (
+            0xc8,
+            OpcodeData {
+                name: "NEWMAP",
+                size: 0,
+            },
+        ),
+        (
+            0xca,
+            OpcodeData {
+                name: "SIZE",
+                size: 0,
+            },
+        ),
+        (
+            0xcb,
+            OpcodeData {
+                name: "HASKEY",
+                size: 0,
+            },
+        ),
+        (
+            0xcc,
+            OpcodeData {
+                name: "KEYS",
+                size: 0,
+            },
+        ),
+        (
+            0xcd,
+            OpcodeData {
+                name: "VALUES",
+                size: 0,
+            },
+        ),
+        (
+            0xce,
+            OpcodeData {
+                name: "PICKITEM",
+                size: 0,
+            },
+        ),
+        (
+            0xcf,
+            OpcodeData {
+                name: "APPEND",
+                size: 0,
+            },
+        ),
+        (
+            0xd0,
+            OpcodeData {
+                name: "SETITEM",
+                size: 0,
+            },
+        ),
+        (
+            0xd1,
+            OpcodeData {
+                name: "REVERSEITEMS",
+                size: 0,
+            },
+        ),
+        (
+            0xd2,
+            OpcodeData {
+                name: "REMOVE",
+                size: 0,
+            },
+        ),
+        (
+            0xd3,
+            OpcodeData {
+                name: "CLEARITEMS",
+                size: 0,
+            },
+        ),
+        (
+            0xd4,
+            OpcodeData {
+                name: "POPITEM",
+                size: 0,
+            },
+        ),
+        (
+            0xd8,
+            OpcodeData {
+                name: "ISNULL",
+                size: 0,
+            },
+        ),
+        (
+            0xd9,
+            OpcodeData {
+                name: "ISTYPE",
+                size: 1,
+            },
+        ),
+        (
+            0xdb,
+            OpcodeData {
+                name: "CONVERT",
+                size: 1,
+            },
+        ),
+        (
+            0xe0,
+            OpcodeData {
+                name: "ABORTMSG",
+                size: 0,
+            },
+        ),
+        (
+            0xe1,
+            OpcodeData {
+                name: "ASSERTMSG",
+                size: 0,
+            },
+        ),
     ]
     .into()
 });
@@ -623,11 +1428,20 @@ pub fn base64_to_address(encoded: &str) -> String {
     scripthash_to_address(&script_hash)
 }
 
+// the second level NNS domain a name belongs to, e.g. neo.com for docs.neo.com
+pub fn nns_domain(name: &str) -> String {
+    let labels: Vec<&str> = name.rsplitn(3, '.').collect();
+
+    match labels.as_slice() {
+        [tld, second, ..] => format!("{second}.{tld}"),
+        _ => name.to_string(),
+    }
+}
+
 pub fn checksum(data: &[u8]) -> Vec<u8> {
     Sha256::digest(Sha256::digest(data)).to_vec()
 }
 
-#[allow(dead_code)]
 pub fn reverse_hex(hex: &str) -> String {
     let mut value = hex::decode(hex).unwrap();
     value.reverse();
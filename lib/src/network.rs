@@ -0,0 +1,105 @@
+// Native contract hashes are derived from the contract names, so they are the same on every N3 network
+pub const CONTRACT_MANAGEMENT: &str = "0xfffdc93764dbaddd97c48f252a53ea4643faa3fd";
+pub const STD_LIB: &str = "0xacce6fd80d44e1796aa0c2c625e9e4e0ce39efc0";
+pub const CRYPTO_LIB: &str = "0x726cb6e0cd8628a1350a611384688911ab75f51b";
+pub const LEDGER_CONTRACT: &str = "0xda65b600f7124ce6c79950c1772a36403104f2be";
+pub const NEO_TOKEN: &str = "0xef4073a0f2b305a38ec4050e4d3d28bc40ea63f5";
+pub const GAS_TOKEN: &str = "0xd2a4cff31913016155e38e474a2c06d08be276cf";
+pub const POLICY_CONTRACT: &str = "0xcc5e4edd9f5f8dba8bb65734541df7a1c081c67b";
+pub const ROLE_MANAGEMENT: &str = "0x49cf4e5378ffcd4dec034fd98a174c5491e395e2";
+pub const ORACLE_CONTRACT: &str = "0xfe924b7cfe89ddd271abaf7210a80a7e11178758";
+
+pub const NATIVE_CONTRACTS: [(&str, &str); 9] = [
+    ("ContractManagement", CONTRACT_MANAGEMENT),
+    ("StdLib", STD_LIB),
+    ("CryptoLib", CRYPTO_LIB),
+    ("LedgerContract", LEDGER_CONTRACT),
+    ("NeoToken", NEO_TOKEN),
+    ("GasToken", GAS_TOKEN),
+    ("PolicyContract", POLICY_CONTRACT),
+    ("RoleManagement", ROLE_MANAGEMENT),
+    ("OracleContract", ORACLE_CONTRACT),
+];
+
+pub const DEFAULT_ADDRESS_VERSION: u8 = 53;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeConfig {
+    MainNet,
+    TestNet,
+    // path to a NeoGo protocol configuration file
+    File(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NetworkProfile {
+    pub name: String,
+    // private networks learn their magic from the node on first run
+    pub magic: Option<u32>,
+    pub address_version: u8,
+    pub rpc_url: String,
+    pub node_config: NodeConfig,
+}
+
+impl NetworkProfile {
+    pub fn mainnet() -> Self {
+        Self {
+            name: String::from("mainnet"),
+            magic: Some(860_833_102),
+            address_version: DEFAULT_ADDRESS_VERSION,
+            rpc_url: String::from("http://localhost:10332"),
+            node_config: NodeConfig::MainNet,
+        }
+    }
+
+    pub fn testnet() -> Self {
+        Self {
+            name: String::from("testnet"),
+            magic: Some(894_710_606),
+            address_version: DEFAULT_ADDRESS_VERSION,
+            rpc_url: String::from("http://localhost:20332"),
+            node_config: NodeConfig::TestNet,
+        }
+    }
+
+    pub fn private(name: &str, node_config: &str, rpc_url: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            magic: None,
+            address_version: DEFAULT_ADDRESS_VERSION,
+            rpc_url: rpc_url.to_string(),
+            node_config: NodeConfig::File(node_config.to_string()),
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "mainnet" => Some(Self::mainnet()),
+            "testnet" => Some(Self::testnet()),
+            _ => None,
+        }
+    }
+}
+
+pub fn native_contract(name: &str) -> Option<&'static str> {
+    NATIVE_CONTRACTS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, hash)| *hash)
+}
+
+// native contracts are never deployed, so this is the only place their names are found
+pub fn native_contract_name(hash: &str) -> Option<&'static str> {
+    NATIVE_CONTRACTS
+        .iter()
+        .find(|(_, h)| *h == hash)
+        .map(|(name, _)| *name)
+}
+
+// network names end up in file names and URL paths
+pub fn is_valid_network_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use crate::network::{
+        is_valid_network_name, native_contract, native_contract_name, NetworkProfile, NodeConfig,
+        NEO_TOKEN,
+    };
+
+    #[test]
+    fn test_network_profiles() {
+        let mainnet = NetworkProfile::from_name("mainnet").unwrap();
+        assert_eq!(mainnet.magic, Some(860_833_102));
+        assert_eq!(mainnet.node_config, NodeConfig::MainNet);
+
+        let testnet = NetworkProfile::from_name("testnet").unwrap();
+        assert_eq!(testnet.magic, Some(894_710_606));
+
+        assert!(NetworkProfile::from_name("privnet").is_none());
+        let private = NetworkProfile::private(
+            "privnet",
+            "./config/protocol.privnet.yml",
+            "http://localhost:30333",
+        );
+        assert_eq!(private.magic, None);
+        assert_eq!(
+            private.node_config,
+            NodeConfig::File("./config/protocol.privnet.yml".to_string())
+        );
+    }
+
+    #[test]
+    fn test_native_contracts() {
+        assert_eq!(
+            native_contract("GasToken"),
+            Some("0xd2a4cff31913016155e38e474a2c06d08be276cf")
+        );
+        assert_eq!(native_contract("Gas"), None);
+        assert_eq!(native_contract_name(NEO_TOKEN), Some("NeoToken"));
+        assert_eq!(
+            native_contract_name("0x0000000000000000000000000000000000000000"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_is_valid_network_name() {
+        assert!(is_valid_network_name("mainnet"));
+        assert!(is_valid_network_name("my-privnet_2"));
+        assert!(!is_valid_network_name(""));
+        assert!(!is_valid_network_name("../mainnet"));
+        assert!(!is_valid_network_name("Main Net"));
+    }
+}
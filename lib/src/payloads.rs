@@ -0,0 +1,506 @@
+use sha2::{Digest, Sha256};
+
+use crate::binary::{BinaryError, BinaryReader, BinaryWriter, Serializable};
+use crate::neo::checksum;
+
+// hashes and public keys as they are serialized, i.e. script hashes little-endian
+pub type UInt160 = [u8; 20];
+pub type UInt256 = [u8; 32];
+pub type ECPoint = [u8; 33];
+
+pub const MAX_TRANSACTION_ATTRIBUTES: usize = 16; // signers and attributes together
+pub const MAX_SUBITEMS: usize = 16;
+pub const MAX_NESTING_DEPTH: usize = 3;
+pub const MAX_SCRIPT_LENGTH: usize = u16::MAX as usize;
+pub const MAX_WITNESS_SCRIPT_LENGTH: usize = 1024;
+pub const MAX_BLOCK_TRANSACTIONS: usize = u16::MAX as usize;
+
+pub const CALLED_BY_ENTRY: u8 = 0x01;
+pub const CUSTOM_CONTRACTS: u8 = 0x10;
+pub const CUSTOM_GROUPS: u8 = 0x20;
+pub const WITNESS_RULES: u8 = 0x40;
+pub const GLOBAL: u8 = 0x80;
+
+// how the node displays a hash, e.g. 0x1f4d...7c15
+pub fn hash_to_hex(hash: &[u8]) -> String {
+    let mut bytes = hash.to_vec();
+    bytes.reverse();
+
+    format!("0x{}", hex::encode(bytes))
+}
+
+// the inverse of `hash_to_hex` for script hashes, with or without the 0x
+pub fn hash160_from_hex(hash: &str) -> Option<UInt160> {
+    let mut bytes = hex::decode(hash.strip_prefix("0x").unwrap_or(hash)).ok()?;
+    bytes.reverse();
+
+    bytes.try_into().ok()
+}
+
+// N3 hashes the unsigned data with a single SHA256
+fn hash_unsigned(serialize_unsigned: impl FnOnce(&mut BinaryWriter)) -> UInt256 {
+    let mut writer = BinaryWriter::new();
+    serialize_unsigned(&mut writer);
+
+    Sha256::digest(writer.into_bytes()).into()
+}
+
+// Pairs of hashes are hashed together with double SHA256 until one is left, the last of an
+// odd level being paired with itself. No hashes give a zero root.
+pub fn merkle_root(hashes: &[UInt256]) -> UInt256 {
+    let mut level = hashes.to_vec();
+    if level.is_empty() {
+        return [0; 32];
+    }
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| {
+                let right = pair.get(1).unwrap_or(&pair[0]);
+                checksum(&[pair[0], *right].concat()).try_into().unwrap()
+            })
+            .collect();
+    }
+
+    level[0]
+}
+
+impl<const N: usize> Serializable for [u8; N] {
+    fn serialize(&self, writer: &mut BinaryWriter) {
+        writer.write_bytes(self);
+    }
+
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError> {
+        reader.read_array()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Witness {
+    pub invocation: Vec<u8>,
+    pub verification: Vec<u8>,
+}
+
+impl Serializable for Witness {
+    fn serialize(&self, writer: &mut BinaryWriter) {
+        writer.write_var_bytes(&self.invocation);
+        writer.write_var_bytes(&self.verification);
+    }
+
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError> {
+        Ok(Witness {
+            invocation: reader.read_var_bytes(MAX_WITNESS_SCRIPT_LENGTH)?,
+            verification: reader.read_var_bytes(MAX_WITNESS_SCRIPT_LENGTH)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum WitnessCondition {
+    Boolean(bool),
+    Not(Box<WitnessCondition>),
+    And(Vec<WitnessCondition>),
+    Or(Vec<WitnessCondition>),
+    ScriptHash(UInt160),
+    Group(ECPoint),
+    CalledByEntry,
+    CalledByContract(UInt160),
+    CalledByGroup(ECPoint),
+}
+
+impl WitnessCondition {
+    // `depth` bounds the nesting of Not, And and Or
+    fn read(reader: &mut BinaryReader, depth: usize) -> Result<Self, BinaryError> {
+        let condition_type = reader.read_u8()?;
+        if depth == 0 && matches!(condition_type, 0x01..=0x03) {
+            return Err(BinaryError::TooLarge(
+                "witness condition nesting",
+                MAX_NESTING_DEPTH as u64,
+            ));
+        }
+
+        let read_all = |reader: &mut BinaryReader| -> Result<Vec<Self>, BinaryError> {
+            let count = reader.read_var_int(MAX_SUBITEMS as u64)?;
+            (0..count).map(|_| Self::read(reader, depth - 1)).collect()
+        };
+
+        let condition = match condition_type {
+            0x00 => WitnessCondition::Boolean(reader.read_bool()?),
+            0x01 => WitnessCondition::Not(Box::new(Self::read(reader, depth - 1)?)),
+            0x02 => WitnessCondition::And(read_all(reader)?),
+            0x03 => WitnessCondition::Or(read_all(reader)?),
+            0x18 => WitnessCondition::ScriptHash(reader.read_array()?),
+            0x19 => WitnessCondition::Group(reader.read_array()?),
+            0x20 => WitnessCondition::CalledByEntry,
+            0x28 => WitnessCondition::CalledByContract(reader.read_array()?),
+            0x29 => WitnessCondition::CalledByGroup(reader.read_array()?),
+            _ => return Err(BinaryError::InvalidValue("witness condition type")),
+        };
+
+        Ok(condition)
+    }
+}
+
+impl Serializable for WitnessCondition {
+    fn serialize(&self, writer: &mut BinaryWriter) {
+        match self {
+            WitnessCondition::Boolean(value) => {
+                writer.write_u8(0x00);
+                writer.write_bool(*value);
+            }
+            WitnessCondition::Not(condition) => {
+                writer.write_u8(0x01);
+                condition.serialize(writer);
+            }
+            WitnessCondition::And(conditions) => {
+                writer.write_u8(0x02);
+                writer.write_items(conditions);
+            }
+            WitnessCondition::Or(conditions) => {
+                writer.write_u8(0x03);
+                writer.write_items(conditions);
+            }
+            WitnessCondition::ScriptHash(hash) => {
+                writer.write_u8(0x18);
+                writer.write_bytes(hash);
+            }
+            WitnessCondition::Group(group) => {
+                writer.write_u8(0x19);
+                writer.write_bytes(group);
+            }
+            WitnessCondition::CalledByEntry => writer.write_u8(0x20),
+            WitnessCondition::CalledByContract(hash) => {
+                writer.write_u8(0x28);
+                writer.write_bytes(hash);
+            }
+            WitnessCondition::CalledByGroup(group) => {
+                writer.write_u8(0x29);
+                writer.write_bytes(group);
+            }
+        }
+    }
+
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError> {
+        Self::read(reader, MAX_NESTING_DEPTH)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WitnessRule {
+    pub allow: bool, // Deny is 0, Allow 1
+    pub condition: WitnessCondition,
+}
+
+impl Serializable for WitnessRule {
+    fn serialize(&self, writer: &mut BinaryWriter) {
+        writer.write_bool(self.allow);
+        self.condition.serialize(writer);
+    }
+
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError> {
+        let allow = reader
+            .read_bool()
+            .map_err(|_| BinaryError::InvalidValue("witness rule action"))?;
+
+        Ok(WitnessRule {
+            allow,
+            condition: WitnessCondition::deserialize(reader)?,
+        })
+    }
+}
+
+// The lists are only written when `scopes` has their flag
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signer {
+    pub account: UInt160,
+    pub scopes: u8,
+    pub allowed_contracts: Vec<UInt160>,
+    pub allowed_groups: Vec<ECPoint>,
+    pub rules: Vec<WitnessRule>,
+}
+
+impl Serializable for Signer {
+    fn serialize(&self, writer: &mut BinaryWriter) {
+        writer.write_bytes(&self.account);
+        writer.write_u8(self.scopes);
+        if self.scopes & CUSTOM_CONTRACTS != 0 {
+            writer.write_items(&self.allowed_contracts);
+        }
+        if self.scopes & CUSTOM_GROUPS != 0 {
+            writer.write_items(&self.allowed_groups);
+        }
+        if self.scopes & WITNESS_RULES != 0 {
+            writer.write_items(&self.rules);
+        }
+    }
+
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError> {
+        let account = reader.read_array()?;
+        let scopes = reader.read_u8()?;
+
+        let known = CALLED_BY_ENTRY | CUSTOM_CONTRACTS | CUSTOM_GROUPS | WITNESS_RULES | GLOBAL;
+        if scopes & !known != 0 || (scopes & GLOBAL != 0 && scopes != GLOBAL) {
+            return Err(BinaryError::InvalidValue("witness scope"));
+        }
+
+        let has = |flag: u8| scopes & flag != 0;
+        Ok(Signer {
+            account,
+            scopes,
+            allowed_contracts: match has(CUSTOM_CONTRACTS) {
+                true => reader.read_items(MAX_SUBITEMS)?,
+                false => vec![],
+            },
+            allowed_groups: match has(CUSTOM_GROUPS) {
+                true => reader.read_items(MAX_SUBITEMS)?,
+                false => vec![],
+            },
+            rules: match has(WITNESS_RULES) {
+                true => reader.read_items(MAX_SUBITEMS)?,
+                false => vec![],
+            },
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransactionAttribute {
+    HighPriority,
+    OracleResponse { id: u64, code: u8, result: Vec<u8> },
+    NotValidBefore { height: u32 },
+    Conflicts { hash: UInt256 },
+    NotaryAssisted { nkeys: u8 },
+}
+
+impl Serializable for TransactionAttribute {
+    fn serialize(&self, writer: &mut BinaryWriter) {
+        match self {
+            TransactionAttribute::HighPriority => writer.write_u8(0x01),
+            TransactionAttribute::OracleResponse { id, code, result } => {
+                writer.write_u8(0x11);
+                writer.write_u64(*id);
+                writer.write_u8(*code);
+                writer.write_var_bytes(result);
+            }
+            TransactionAttribute::NotValidBefore { height } => {
+                writer.write_u8(0x20);
+                writer.write_u32(*height);
+            }
+            TransactionAttribute::Conflicts { hash } => {
+                writer.write_u8(0x21);
+                writer.write_bytes(hash);
+            }
+            TransactionAttribute::NotaryAssisted { nkeys } => {
+                writer.write_u8(0x22);
+                writer.write_u8(*nkeys);
+            }
+        }
+    }
+
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError> {
+        let attribute = match reader.read_u8()? {
+            0x01 => TransactionAttribute::HighPriority,
+            0x11 => TransactionAttribute::OracleResponse {
+                id: reader.read_u64()?,
+                code: reader.read_u8()?,
+                result: reader.read_var_bytes(u16::MAX as usize)?,
+            },
+            0x20 => TransactionAttribute::NotValidBefore {
+                height: reader.read_u32()?,
+            },
+            0x21 => TransactionAttribute::Conflicts {
+                hash: reader.read_array()?,
+            },
+            0x22 => TransactionAttribute::NotaryAssisted {
+                nkeys: reader.read_u8()?,
+            },
+            _ => return Err(BinaryError::InvalidValue("transaction attribute type")),
+        };
+
+        Ok(attribute)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transaction {
+    pub version: u8,
+    pub nonce: u32,
+    pub system_fee: i64,
+    pub network_fee: i64,
+    pub valid_until_block: u32,
+    pub signers: Vec<Signer>,
+    pub attributes: Vec<TransactionAttribute>,
+    pub script: Vec<u8>,
+    pub witnesses: Vec<Witness>, // one per signer
+}
+
+impl Transaction {
+    pub fn serialize_unsigned(&self, writer: &mut BinaryWriter) {
+        writer.write_u8(self.version);
+        writer.write_u32(self.nonce);
+        writer.write_i64(self.system_fee);
+        writer.write_i64(self.network_fee);
+        writer.write_u32(self.valid_until_block);
+        writer.write_items(&self.signers);
+        writer.write_items(&self.attributes);
+        writer.write_var_bytes(&self.script);
+    }
+
+    pub fn hash(&self) -> UInt256 {
+        hash_unsigned(|writer| self.serialize_unsigned(writer))
+    }
+}
+
+impl Serializable for Transaction {
+    fn serialize(&self, writer: &mut BinaryWriter) {
+        self.serialize_unsigned(writer);
+        writer.write_items(&self.witnesses);
+    }
+
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError> {
+        let version = reader.read_u8()?;
+        if version != 0 {
+            return Err(BinaryError::InvalidValue("transaction version"));
+        }
+
+        let nonce = reader.read_u32()?;
+        let system_fee = reader.read_i64()?;
+        let network_fee = reader.read_i64()?;
+        if system_fee < 0 || network_fee < 0 {
+            return Err(BinaryError::InvalidValue("transaction fee"));
+        }
+        let valid_until_block = reader.read_u32()?;
+
+        let signers: Vec<Signer> = reader.read_items(MAX_TRANSACTION_ATTRIBUTES)?;
+        if signers.is_empty() {
+            return Err(BinaryError::InvalidValue("transaction without signers"));
+        }
+        let attributes = reader.read_items(MAX_TRANSACTION_ATTRIBUTES - signers.len())?;
+
+        let script = reader.read_var_bytes(MAX_SCRIPT_LENGTH)?;
+        if script.is_empty() {
+            return Err(BinaryError::InvalidValue("empty transaction script"));
+        }
+
+        let witnesses: Vec<Witness> = reader.read_items(signers.len())?;
+        if witnesses.len() != signers.len() {
+            return Err(BinaryError::InvalidValue("witness count"));
+        }
+
+        Ok(Transaction {
+            version,
+            nonce,
+            system_fee,
+            network_fee,
+            valid_until_block,
+            signers,
+            attributes,
+            script,
+            witnesses,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Header {
+    pub version: u32,
+    pub prev_hash: UInt256,
+    pub merkle_root: UInt256,
+    pub timestamp: u64, // milliseconds
+    pub nonce: u64,
+    pub index: u32,
+    pub primary_index: u8,
+    pub next_consensus: UInt160,
+    pub witness: Witness,
+}
+
+impl Header {
+    pub fn serialize_unsigned(&self, writer: &mut BinaryWriter) {
+        writer.write_u32(self.version);
+        writer.write_bytes(&self.prev_hash);
+        writer.write_bytes(&self.merkle_root);
+        writer.write_u64(self.timestamp);
+        writer.write_u64(self.nonce);
+        writer.write_u32(self.index);
+        writer.write_u8(self.primary_index);
+        writer.write_bytes(&self.next_consensus);
+    }
+
+    pub fn hash(&self) -> UInt256 {
+        hash_unsigned(|writer| self.serialize_unsigned(writer))
+    }
+}
+
+impl Serializable for Header {
+    // the witness is written as a list that always has one item
+    fn serialize(&self, writer: &mut BinaryWriter) {
+        self.serialize_unsigned(writer);
+        writer.write_var_int(1);
+        self.witness.serialize(writer);
+    }
+
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError> {
+        let version = reader.read_u32()?;
+        if version != 0 {
+            return Err(BinaryError::InvalidValue("block version"));
+        }
+
+        let prev_hash = reader.read_array()?;
+        let merkle_root = reader.read_array()?;
+        let timestamp = reader.read_u64()?;
+        let nonce = reader.read_u64()?;
+        let index = reader.read_u32()?;
+        let primary_index = reader.read_u8()?;
+        let next_consensus = reader.read_array()?;
+
+        if reader.read_var_int(1)? != 1 {
+            return Err(BinaryError::InvalidValue("block witness count"));
+        }
+
+        Ok(Header {
+            version,
+            prev_hash,
+            merkle_root,
+            timestamp,
+            nonce,
+            index,
+            primary_index,
+            next_consensus,
+            witness: Witness::deserialize(reader)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Block {
+    pub header: Header,
+    pub transactions: Vec<Transaction>,
+}
+
+impl Block {
+    pub fn hash(&self) -> UInt256 {
+        self.header.hash()
+    }
+
+    // what the header's merkle root should be
+    pub fn compute_merkle_root(&self) -> UInt256 {
+        let hashes: Vec<UInt256> = self.transactions.iter().map(Transaction::hash).collect();
+
+        merkle_root(&hashes)
+    }
+}
+
+impl Serializable for Block {
+    fn serialize(&self, writer: &mut BinaryWriter) {
+        self.header.serialize(writer);
+        writer.write_items(&self.transactions);
+    }
+
+    fn deserialize(reader: &mut BinaryReader) -> Result<Self, BinaryError> {
+        Ok(Block {
+            header: Header::deserialize(reader)?,
+            transactions: reader.read_items(MAX_BLOCK_TRANSACTIONS)?,
+        })
+    }
+}
@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+    use crate::binary::{BinaryError, Serializable};
+    use crate::neo::checksum;
+    use crate::payloads::{
+        hash_to_hex, merkle_root, Block, Header, Signer, Transaction, TransactionAttribute,
+        UInt256, Witness, WitnessCondition, WitnessRule, CALLED_BY_ENTRY, CUSTOM_CONTRACTS,
+        CUSTOM_GROUPS, GLOBAL, MAX_NESTING_DEPTH, WITNESS_RULES,
+    };
+    use sha2::{Digest, Sha256};
+
+    fn mainnet_genesis() -> Block {
+        // NVg7LjGcUSrgxgjX3zEgqaksfMaiS8Z6e1
+        let next_consensus = hex::decode("6b123dd8bec718648852bbc78595e3536a058f9f").unwrap();
+
+        Block {
+            header: Header {
+                version: 0,
+                prev_hash: [0; 32],
+                merkle_root: [0; 32],
+                timestamp: 1468595301000,
+                nonce: 2083236893,
+                index: 0,
+                primary_index: 0,
+                next_consensus: next_consensus.try_into().unwrap(),
+                witness: Witness {
+                    invocation: vec![],
+                    verification: vec![0x11],
+                },
+            },
+            transactions: vec![],
+        }
+    }
+
+    #[test]
+    fn test_genesis_block_hash() {
+        let genesis = mainnet_genesis();
+
+        assert_eq!(
+            hash_to_hex(&genesis.hash()),
+            "0x1f4d1defa46faa5e7b9b8d3f79a06bec777d7c26c4aa5f6f5899a291daa87c15"
+        );
+        assert_eq!(genesis.compute_merkle_root(), genesis.header.merkle_root);
+
+        let bytes = genesis.to_bytes();
+        assert_eq!(Block::from_bytes(&bytes), Ok(genesis));
+        assert_eq!(
+            Block::from_bytes(&[bytes.as_slice(), &[0]].concat()),
+            Err(BinaryError::TrailingBytes(1))
+        );
+        assert!(Block::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn test_transaction_round_trip() {
+        let transaction = Transaction {
+            version: 0,
+            nonce: 1,
+            system_fee: 2,
+            network_fee: 3,
+            valid_until_block: 4,
+            signers: vec![Signer {
+                account: [0; 20],
+                scopes: CALLED_BY_ENTRY,
+                allowed_contracts: vec![],
+                allowed_groups: vec![],
+                rules: vec![],
+            }],
+            attributes: vec![],
+            script: vec![0x11],
+            witnesses: vec![Witness {
+                invocation: vec![],
+                verification: vec![0x11],
+            }],
+        };
+
+        // version, nonce, fees, valid until, one signer, no attributes, then the script
+        let unsigned = "00010000000200000000000000030000000000000004000000\
+            0100000000000000000000000000000000000000000100\
+            0111";
+        let bytes = transaction.to_bytes();
+        assert_eq!(hex::encode(&bytes), format!("{unsigned}01000111"));
+        assert_eq!(
+            transaction.hash().to_vec(),
+            Sha256::digest(hex::decode(unsigned).unwrap()).to_vec()
+        );
+        assert_eq!(Transaction::from_bytes(&bytes), Ok(transaction.clone()));
+
+        let mut detailed = transaction;
+        detailed.signers[0].scopes = CUSTOM_CONTRACTS | CUSTOM_GROUPS | WITNESS_RULES;
+        detailed.signers[0].allowed_contracts = vec![[1; 20]];
+        detailed.signers[0].allowed_groups = vec![[2; 33]];
+        detailed.signers[0].rules = vec![WitnessRule {
+            allow: true,
+            condition: WitnessCondition::And(vec![
+                WitnessCondition::Not(Box::new(WitnessCondition::CalledByEntry)),
+                WitnessCondition::Or(vec![
+                    WitnessCondition::ScriptHash([3; 20]),
+                    WitnessCondition::CalledByGroup([4; 33]),
+                ]),
+            ]),
+        }];
+        detailed.attributes = vec![
+            TransactionAttribute::HighPriority,
+            TransactionAttribute::OracleResponse {
+                id: 7,
+                code: 0,
+                result: b"neo".to_vec(),
+            },
+            TransactionAttribute::NotValidBefore { height: 100 },
+            TransactionAttribute::Conflicts { hash: [5; 32] },
+            TransactionAttribute::NotaryAssisted { nkeys: 1 },
+        ];
+        let bytes = detailed.to_bytes();
+        assert_eq!(Transaction::from_bytes(&bytes), Ok(detailed.clone()));
+
+        // Global can't be combined with other scopes
+        let mut global = detailed;
+        global.signers[0].scopes = GLOBAL | CALLED_BY_ENTRY;
+        assert_eq!(
+            Transaction::from_bytes(&global.to_bytes()),
+            Err(BinaryError::InvalidValue("witness scope"))
+        );
+    }
+
+    #[test]
+    fn test_merkle_root() {
+        let a = [1; 32];
+        let b = [2; 32];
+        let c = [3; 32];
+        let pair = |left: UInt256, right: UInt256| -> UInt256 {
+            checksum(&[left, right].concat()).try_into().unwrap()
+        };
+
+        assert_eq!(merkle_root(&[]), [0; 32]);
+        assert_eq!(merkle_root(&[a]), a);
+        assert_eq!(merkle_root(&[a, b]), pair(a, b));
+        // the odd one out is paired with itself
+        assert_eq!(merkle_root(&[a, b, c]), pair(pair(a, b), pair(c, c)));
+    }
+
+    #[test]
+    fn test_witness_condition_nesting() {
+        let mut condition = WitnessCondition::Boolean(true);
+        for _ in 0..MAX_NESTING_DEPTH {
+            condition = WitnessCondition::Not(Box::new(condition));
+        }
+        let bytes = condition.to_bytes();
+        assert_eq!(WitnessCondition::from_bytes(&bytes), Ok(condition.clone()));
+
+        let deeper = WitnessCondition::Not(Box::new(condition));
+        assert!(WitnessCondition::from_bytes(&deeper.to_bytes()).is_err());
+    }
+}
@@ -0,0 +1,256 @@
+use sha2::{Digest, Sha256};
+
+use crate::integer::{self, BigInt, MAX_INTEGER_SIZE};
+use crate::payloads::UInt160;
+use crate::stack_item::StackItem;
+
+pub const SYSTEM_CONTRACT_CALL: &str = "System.Contract.Call";
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum ScriptError {
+    #[error("integer exceeds {0} bytes")]
+    IntegerTooLarge(usize),
+    #[error("{0} stack items can't be pushed")]
+    Unsupported(&'static str),
+}
+
+// the opcodes scripts built here use, see `neo3_disassemble` for the full table
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum OpCode {
+    PushInt8 = 0x00,
+    PushInt16 = 0x01,
+    PushInt32 = 0x02,
+    PushInt64 = 0x03,
+    PushInt128 = 0x04,
+    PushInt256 = 0x05,
+    PushT = 0x08,
+    PushF = 0x09,
+    PushNull = 0x0b,
+    PushData1 = 0x0c,
+    PushData2 = 0x0d,
+    PushData4 = 0x0e,
+    PushM1 = 0x0f,
+    Push0 = 0x10,
+    Nop = 0x21,
+    Abort = 0x38,
+    Assert = 0x39,
+    Throw = 0x3a,
+    Ret = 0x40,
+    Syscall = 0x41,
+    Drop = 0x45,
+    Dup = 0x4a,
+    Swap = 0x50,
+    PackMap = 0xbe,
+    PackStruct = 0xbf,
+    Pack = 0xc0,
+    Unpack = 0xc1,
+    NewArray0 = 0xc2,
+    NewMap = 0xc8,
+    Size = 0xca,
+    PickItem = 0xce,
+    Append = 0xcf,
+    SetItem = 0xd0,
+    IsNull = 0xd8,
+}
+
+// what a called contract may do. States, ReadOnly and All are the common combinations
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[repr(u8)]
+pub enum CallFlags {
+    None = 0x00,
+    ReadStates = 0x01,
+    WriteStates = 0x02,
+    AllowCall = 0x04,
+    AllowNotify = 0x08,
+    States = 0x03,
+    ReadOnly = 0x05,
+    All = 0x0f,
+}
+
+// how Neo prints call flags: a combination's own name when it has one, e.g. "All",
+// otherwise the flags joined, e.g. "ReadStates, AllowNotify"
+pub fn call_flags_name(flags: u8) -> String {
+    let named = [
+        CallFlags::None,
+        CallFlags::States,
+        CallFlags::ReadOnly,
+        CallFlags::All,
+        CallFlags::ReadStates,
+        CallFlags::WriteStates,
+        CallFlags::AllowCall,
+        CallFlags::AllowNotify,
+    ];
+    if let Some(flag) = named.iter().find(|flag| **flag as u8 == flags) {
+        return format!("{flag:?}");
+    }
+
+    named[4..]
+        .iter()
+        .filter(|flag| flags & **flag as u8 != 0)
+        .map(|flag| format!("{flag:?}"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// syscalls are called by the first four bytes of the SHA256 of their name
+pub fn interop_hash(name: &str) -> u32 {
+    let digest = Sha256::digest(name.as_bytes());
+
+    u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+// Emits scripts the way Neo's ScriptBuilder does, so built scripts match those the
+// wallets send, byte for byte
+#[derive(Default)]
+pub struct ScriptBuilder {
+    script: Vec<u8>,
+}
+
+impl ScriptBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.script
+    }
+
+    pub fn emit(&mut self, opcode: OpCode) -> &mut Self {
+        self.script.push(opcode as u8);
+        self
+    }
+
+    fn emit_with_operand(&mut self, opcode: OpCode, operand: &[u8]) -> &mut Self {
+        self.emit(opcode);
+        self.script.extend_from_slice(operand);
+        self
+    }
+
+    pub fn push_bool(&mut self, value: bool) -> &mut Self {
+        self.emit(if value { OpCode::PushT } else { OpCode::PushF })
+    }
+
+    pub fn push_null(&mut self) -> &mut Self {
+        self.emit(OpCode::PushNull)
+    }
+
+    // -1 to 16 have their own opcodes, anything else the smallest PUSHINT it fits
+    pub fn push_integer(&mut self, value: impl Into<BigInt>) -> Result<&mut Self, ScriptError> {
+        let value = value.into();
+
+        if value >= BigInt::from(-1) && value <= BigInt::from(16) {
+            let offset = i8::try_from(&value).unwrap();
+            self.script
+                .push((OpCode::Push0 as u8).wrapping_add_signed(offset));
+            return Ok(self);
+        }
+
+        let mut bytes = integer::encode(&value);
+        let (opcode, size) = match bytes.len() {
+            1 => (OpCode::PushInt8, 1),
+            2 => (OpCode::PushInt16, 2),
+            3..=4 => (OpCode::PushInt32, 4),
+            5..=8 => (OpCode::PushInt64, 8),
+            9..=16 => (OpCode::PushInt128, 16),
+            17..=MAX_INTEGER_SIZE => (OpCode::PushInt256, 32),
+            _ => return Err(ScriptError::IntegerTooLarge(MAX_INTEGER_SIZE)),
+        };
+
+        // sign extended to the operand size
+        let fill = if value < BigInt::default() {
+            0xff
+        } else {
+            0x00
+        };
+        bytes.resize(size, fill);
+
+        Ok(self.emit_with_operand(opcode, &bytes))
+    }
+
+    pub fn push_bytes(&mut self, data: &[u8]) -> &mut Self {
+        let length = data.len();
+
+        if length <= u8::MAX as usize {
+            self.emit_with_operand(OpCode::PushData1, &[length as u8]);
+        } else if length <= u16::MAX as usize {
+            self.emit_with_operand(OpCode::PushData2, &(length as u16).to_le_bytes());
+        } else {
+            self.emit_with_operand(OpCode::PushData4, &(length as u32).to_le_bytes());
+        }
+
+        self.script.extend_from_slice(data);
+        self
+    }
+
+    pub fn push_string(&mut self, value: &str) -> &mut Self {
+        self.push_bytes(value.as_bytes())
+    }
+
+    // script hashes are pushed little-endian, as they are serialized
+    pub fn push_hash160(&mut self, hash: &UInt160) -> &mut Self {
+        self.push_bytes(hash)
+    }
+
+    // the items in reverse, so the first ends up on top, then packed
+    pub fn push_array(&mut self, items: &[StackItem]) -> Result<&mut Self, ScriptError> {
+        self.push_packed(items, OpCode::Pack)
+    }
+
+    fn push_packed(&mut self, items: &[StackItem], pack: OpCode) -> Result<&mut Self, ScriptError> {
+        if items.is_empty() && pack == OpCode::Pack {
+            return Ok(self.emit(OpCode::NewArray0));
+        }
+
+        for item in items.iter().rev() {
+            self.push_item(item)?;
+        }
+        self.push_integer(items.len())?;
+
+        Ok(self.emit(pack))
+    }
+
+    pub fn push_item(&mut self, item: &StackItem) -> Result<&mut Self, ScriptError> {
+        match item {
+            StackItem::Any => Ok(self.push_null()),
+            StackItem::Boolean(value) => Ok(self.push_bool(*value)),
+            StackItem::Integer(value) => self.push_integer(value.clone()),
+            StackItem::ByteString(bytes) | StackItem::Buffer(bytes) => Ok(self.push_bytes(bytes)),
+            StackItem::Array(items) => self.push_array(items),
+            StackItem::Struct(items) => self.push_packed(items, OpCode::PackStruct),
+            StackItem::Map(entries) => {
+                for (key, value) in entries.iter().rev() {
+                    self.push_item(value)?;
+                    self.push_item(key)?;
+                }
+                self.push_integer(entries.len())?;
+
+                Ok(self.emit(OpCode::PackMap))
+            }
+            StackItem::Pointer(_) | StackItem::InteropInterface => {
+                Err(ScriptError::Unsupported(item.type_name()))
+            }
+        }
+    }
+
+    pub fn syscall(&mut self, name: &str) -> &mut Self {
+        self.emit_with_operand(OpCode::Syscall, &interop_hash(name).to_le_bytes())
+    }
+
+    // `contract.method(args)`, pushed in the order System.Contract.Call takes them
+    pub fn contract_call(
+        &mut self,
+        contract: &UInt160,
+        method: &str,
+        flags: CallFlags,
+        args: &[StackItem],
+    ) -> Result<&mut Self, ScriptError> {
+        self.push_array(args)?
+            .push_integer(flags as u8)?
+            .push_string(method)
+            .push_hash160(contract)
+            .syscall(SYSTEM_CONTRACT_CALL);
+
+        Ok(self)
+    }
+}
@@ -0,0 +1,183 @@
+#[cfg(test)]
+mod tests {
+    use crate::integer::BigInt;
+    use crate::script::{
+        call_flags_name, interop_hash, CallFlags, OpCode, ScriptBuilder, ScriptError,
+        SYSTEM_CONTRACT_CALL,
+    };
+    use crate::stack_item::StackItem;
+
+    fn build(
+        f: impl FnOnce(&mut ScriptBuilder) -> Result<&mut ScriptBuilder, ScriptError>,
+    ) -> String {
+        let mut builder = ScriptBuilder::new();
+        f(&mut builder).unwrap();
+
+        hex::encode(builder.into_bytes())
+    }
+
+    #[test]
+    fn test_push_integer() {
+        for (value, encoded) in [
+            (-1, "0f"),
+            (0, "10"),
+            (16, "20"),
+            (17, "0011"),
+            (-2, "00fe"),
+            (127, "007f"),
+            (128, "018000"),
+            (-129, "017fff"),
+            (256, "010001"),
+            (65536, "0200000100"),
+            (-65536, "020000ffff"),
+            (i64::MAX, "03ffffffffffffff7f"),
+            (i64::MIN, "030000000000000080"),
+        ] {
+            assert_eq!(build(|b| b.push_integer(value)), encoded, "{value}");
+        }
+
+        let two_to_the_64 = BigInt::from(u64::MAX) + 1;
+        assert_eq!(
+            build(|b| b.push_integer(two_to_the_64)),
+            "0400000000000000000100000000000000"
+        );
+
+        let too_large = BigInt::from(1) << 256;
+        assert_eq!(
+            ScriptBuilder::new().push_integer(too_large).err(),
+            Some(ScriptError::IntegerTooLarge(32))
+        );
+    }
+
+    #[test]
+    fn test_push_bytes() {
+        assert_eq!(build(|b| Ok(b.push_string(""))), "0c00");
+        assert_eq!(
+            build(|b| Ok(b.push_string("test.neo"))),
+            "0c08746573742e6e656f"
+        );
+
+        let data = vec![0xab; 0x100];
+        assert_eq!(build(|b| Ok(b.push_bytes(&data)))[..6], *"0d0001");
+        let data = vec![0xab; 0x10000];
+        assert_eq!(build(|b| Ok(b.push_bytes(&data)))[..10], *"0e00000100");
+    }
+
+    #[test]
+    fn test_interop_hash() {
+        // SYSCALL 627d5b52 in every contract call
+        assert_eq!(interop_hash(SYSTEM_CONTRACT_CALL), 0x525b7d62);
+        assert_eq!(interop_hash("System.Runtime.CheckWitness"), 0x8cec27f8);
+    }
+
+    // scripts from indexed transactions, see the indexer's conversion tests
+    #[test]
+    fn test_known_scripts() {
+        let nns = crate::payloads::hash160_from_hex("0x50ac1c37690cc2cfc594472833cf57505d5f46de")
+            .unwrap();
+
+        let set_record = build(|b| {
+            b.contract_call(
+                &nns,
+                "setRecord",
+                CallFlags::All,
+                &[
+                    StackItem::ByteString(b"test.neo".to_vec()),
+                    StackItem::Integer(BigInt::from(16)),
+                    StackItem::ByteString(b"NKuzPnNtxubufwT9MvNKJ557TRPWe1jGDF".to_vec()),
+                ],
+            )
+        });
+        assert_eq!(set_record, "0c224e4b757a506e4e7478756275667754394d764e4b4a3535375452505765316a474446200c08746573742e6e656f13c01f0c097365745265636f72640c14de465f5d5057cf33284794c5cfc20c69371cac5041627d5b52");
+
+        let renew = build(|b| {
+            b.emit(OpCode::Drop).contract_call(
+                &nns,
+                "renew",
+                CallFlags::All,
+                &[
+                    StackItem::ByteString(b"test.neo".to_vec()),
+                    StackItem::Integer(BigInt::from(256)),
+                ],
+            )
+        });
+        assert_eq!(renew, "450100010c08746573742e6e656f12c01f0c0572656e65770c14de465f5d5057cf33284794c5cfc20c69371cac5041627d5b52");
+
+        // what wallets send for NEP-17 balances and metadata
+        let gas = crate::payloads::hash160_from_hex("0xd2a4cff31913016155e38e474a2c06d08be276cf")
+            .unwrap();
+        let account =
+            crate::neo::base58_to_bytes("NVg7LjGcUSrgxgjX3zEgqaksfMaiS8Z6e1")[1..21].to_vec();
+        let balance_of = build(|b| {
+            b.contract_call(
+                &gas,
+                "balanceOf",
+                CallFlags::All,
+                &[StackItem::ByteString(account.clone())],
+            )
+        });
+        assert_eq!(balance_of, format!("0c14{}11c01f0c0962616c616e63654f660c14cf76e28bd0062c4a478ee35561011319f3cfa4d241627d5b52", hex::encode(&account)));
+
+        let symbol = build(|b| b.contract_call(&gas, "symbol", CallFlags::ReadOnly, &[]));
+        assert_eq!(
+            symbol,
+            "c2150c0673796d626f6c0c14cf76e28bd0062c4a478ee35561011319f3cfa4d241627d5b52"
+        );
+    }
+
+    #[test]
+    fn test_disassembler_round_trip() {
+        let mut builder = ScriptBuilder::new();
+        builder
+            .push_item(&StackItem::Array(vec![
+                StackItem::Any,
+                StackItem::Boolean(true),
+                StackItem::Integer(BigInt::from(-1000)),
+                StackItem::Struct(vec![StackItem::Integer(BigInt::from(3))]),
+                StackItem::Map(vec![(
+                    StackItem::ByteString(b"k".to_vec()),
+                    StackItem::Integer(BigInt::from(0)),
+                )]),
+            ]))
+            .unwrap()
+            .emit(OpCode::Drop)
+            .syscall("System.Runtime.CheckWitness")
+            .emit(OpCode::Ret);
+        let script = builder.into_bytes();
+
+        let disassembled = crate::neo::neo3_disassemble(&base64::encode(&script));
+        let expected = [
+            "PUSH0",
+            "PUSHDATA1 6b",
+            "PUSH1",
+            "PACKMAP",
+            "PUSH3",
+            "PUSH1",
+            "PACKSTRUCT",
+            "PUSHINT16 18fc",
+            "PUSHT",
+            "PUSHNULL",
+            "PUSH5",
+            "PACK",
+            "DROP",
+            &format!("SYSCALL {}", 0x8cec27f8_u32),
+            "RET",
+        ];
+        assert_eq!(disassembled.lines().collect::<Vec<_>>(), expected);
+
+        assert_eq!(
+            ScriptBuilder::new()
+                .push_item(&StackItem::InteropInterface)
+                .err(),
+            Some(ScriptError::Unsupported("InteropInterface"))
+        );
+    }
+
+    #[test]
+    fn test_call_flags_name() {
+        assert_eq!(call_flags_name(0x0f), "All");
+        assert_eq!(call_flags_name(0x05), "ReadOnly");
+        assert_eq!(call_flags_name(0x00), "None");
+        assert_eq!(call_flags_name(0x09), "ReadStates, AllowNotify");
+    }
+}
@@ -0,0 +1,225 @@
+pub use crate::integer::BigInt;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{json, Value};
+
+use crate::integer;
+use crate::neo::scripthash_to_address;
+
+// A NeoVM stack item, as found in notification states and invocation results
+#[derive(Debug, Clone, PartialEq)]
+pub enum StackItem {
+    Any,
+    Boolean(bool),
+    Integer(BigInt),
+    ByteString(Vec<u8>),
+    Buffer(Vec<u8>),
+    Array(Vec<StackItem>),
+    Struct(Vec<StackItem>),
+    Map(Vec<(StackItem, StackItem)>),
+    Pointer(u64),
+    InteropInterface,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StackItemError {
+    #[error("stack item has no type")]
+    MissingType,
+    #[error("unknown stack item type {0}")]
+    UnknownType(String),
+    #[error("invalid {0} stack item value")]
+    InvalidValue(&'static str),
+}
+
+impl StackItem {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            StackItem::Any => "Any",
+            StackItem::Boolean(_) => "Boolean",
+            StackItem::Integer(_) => "Integer",
+            StackItem::ByteString(_) => "ByteString",
+            StackItem::Buffer(_) => "Buffer",
+            StackItem::Array(_) => "Array",
+            StackItem::Struct(_) => "Struct",
+            StackItem::Map(_) => "Map",
+            StackItem::Pointer(_) => "Pointer",
+            StackItem::InteropInterface => "InteropInterface",
+        }
+    }
+
+    // NeoGo's JSON form: {"type": "Integer", "value": "1"}, with byte strings in base64
+    pub fn from_json(json: &Value) -> Result<Self, StackItemError> {
+        let item_type = json["type"].as_str().ok_or(StackItemError::MissingType)?;
+        let value = &json["value"];
+
+        let item = match item_type {
+            "Any" => StackItem::Any,
+            "Boolean" => StackItem::Boolean(
+                value
+                    .as_bool()
+                    .ok_or(StackItemError::InvalidValue("Boolean"))?,
+            ),
+            "Integer" => {
+                // NeoGo writes integers as strings, since they can exceed any JSON number
+                let integer = match value {
+                    Value::String(s) => s.parse().ok(),
+                    Value::Number(n) => n.to_string().parse().ok(),
+                    _ => None,
+                };
+                StackItem::Integer(integer.ok_or(StackItemError::InvalidValue("Integer"))?)
+            }
+            "ByteString" => StackItem::ByteString(decode_bytes(value, "ByteString")?),
+            "Buffer" => StackItem::Buffer(decode_bytes(value, "Buffer")?),
+            "Array" => StackItem::Array(decode_items(value, "Array")?),
+            "Struct" => StackItem::Struct(decode_items(value, "Struct")?),
+            "Map" => {
+                let entries = value
+                    .as_array()
+                    .ok_or(StackItemError::InvalidValue("Map"))?;
+                StackItem::Map(
+                    entries
+                        .iter()
+                        .map(|entry| {
+                            Ok((
+                                StackItem::from_json(&entry["key"])?,
+                                StackItem::from_json(&entry["value"])?,
+                            ))
+                        })
+                        .collect::<Result<_, StackItemError>>()?,
+                )
+            }
+            "Pointer" => StackItem::Pointer(
+                value
+                    .as_u64()
+                    .ok_or(StackItemError::InvalidValue("Pointer"))?,
+            ),
+            "InteropInterface" => StackItem::InteropInterface,
+            other => return Err(StackItemError::UnknownType(other.to_string())),
+        };
+
+        Ok(item)
+    }
+
+    pub fn to_json(&self) -> Value {
+        let value = match self {
+            StackItem::Any | StackItem::InteropInterface => {
+                return json!({ "type": self.type_name() })
+            }
+            StackItem::Boolean(b) => json!(b),
+            StackItem::Integer(i) => json!(i.to_string()),
+            StackItem::ByteString(bytes) | StackItem::Buffer(bytes) => json!(base64::encode(bytes)),
+            StackItem::Array(items) | StackItem::Struct(items) => {
+                Value::Array(items.iter().map(StackItem::to_json).collect())
+            }
+            StackItem::Map(entries) => Value::Array(
+                entries
+                    .iter()
+                    .map(|(key, value)| json!({ "key": key.to_json(), "value": value.to_json() }))
+                    .collect(),
+            ),
+            StackItem::Pointer(p) => json!(p),
+        };
+
+        json!({ "type": self.type_name(), "value": value })
+    }
+
+    pub fn is_null(&self) -> bool {
+        matches!(self, StackItem::Any)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            StackItem::Boolean(b) => Some(*b),
+            StackItem::Integer(i) => Some(*i != BigInt::default()),
+            _ => None,
+        }
+    }
+
+    // byte strings are read as little-endian two's complement, like the VM does
+    pub fn as_integer(&self) -> Option<BigInt> {
+        match self {
+            StackItem::Integer(i) => Some(i.clone()),
+            StackItem::Boolean(b) => Some(BigInt::from(*b as u8)),
+            StackItem::ByteString(bytes) if bytes.len() <= integer::MAX_INTEGER_SIZE => {
+                Some(integer::decode(bytes))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            StackItem::ByteString(bytes) | StackItem::Buffer(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    pub fn as_utf8(&self) -> Option<String> {
+        self.as_bytes()
+            .and_then(|bytes| String::from_utf8(bytes.to_vec()).ok())
+    }
+
+    pub fn as_array(&self) -> Option<&[StackItem]> {
+        match self {
+            StackItem::Array(items) | StackItem::Struct(items) => Some(items),
+            _ => None,
+        }
+    }
+
+    // a Hash160 as a Neo address
+    pub fn as_address(&self) -> Option<String> {
+        self.as_bytes()
+            .filter(|bytes| bytes.len() == 20)
+            .map(|bytes| scripthash_to_address(&hex::encode(bytes)))
+    }
+
+    // a Hash160 as a 0x-prefixed script hash
+    pub fn as_script_hash(&self) -> Option<String> {
+        self.as_bytes()
+            .filter(|bytes| bytes.len() == 20)
+            .map(reversed_hex)
+    }
+
+    // a Hash256, e.g. a transaction or block hash
+    pub fn as_u256(&self) -> Option<String> {
+        self.as_bytes()
+            .filter(|bytes| bytes.len() == 32)
+            .map(reversed_hex)
+    }
+}
+
+fn reversed_hex(bytes: &[u8]) -> String {
+    let mut bytes = bytes.to_vec();
+    bytes.reverse();
+
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn decode_bytes(value: &Value, item_type: &'static str) -> Result<Vec<u8>, StackItemError> {
+    value
+        .as_str()
+        .and_then(|encoded| base64::decode(encoded).ok())
+        .ok_or(StackItemError::InvalidValue(item_type))
+}
+
+fn decode_items(value: &Value, item_type: &'static str) -> Result<Vec<StackItem>, StackItemError> {
+    value
+        .as_array()
+        .ok_or(StackItemError::InvalidValue(item_type))?
+        .iter()
+        .map(StackItem::from_json)
+        .collect()
+}
+
+impl Serialize for StackItem {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_json().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StackItem {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let json = Value::deserialize(deserializer)?;
+
+        StackItem::from_json(&json).map_err(de::Error::custom)
+    }
+}
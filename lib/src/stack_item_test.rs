@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod tests {
+    use crate::stack_item::{BigInt, StackItem};
+    use serde_json::json;
+
+    #[test]
+    fn test_stack_item_json_round_trip() {
+        let json = json!({
+            "type": "Array",
+            "value": [
+                { "type": "Any" },
+                { "type": "ByteString", "value": "AAECAwQFBgcICQoLDA0ODxAREhM=" },
+                { "type": "Integer", "value": "-340282366920938463463374607431768211456" },
+                { "type": "Boolean", "value": true },
+                { "type": "Map", "value": [
+                    { "key": { "type": "ByteString", "value": "bmVv" }, "value": { "type": "Buffer", "value": "" } }
+                ] },
+                { "type": "Struct", "value": [{ "type": "Pointer", "value": 42 }] },
+                { "type": "InteropInterface" }
+            ]
+        });
+
+        let item: StackItem = serde_json::from_value(json.clone()).unwrap();
+        let items = item.as_array().unwrap();
+
+        assert_eq!(items.len(), 7);
+        assert!(items[0].is_null());
+        assert_eq!(
+            items[2].as_integer().unwrap().to_string(),
+            "-340282366920938463463374607431768211456"
+        );
+        assert_eq!(serde_json::to_value(&item).unwrap(), json);
+
+        assert!(StackItem::from_json(&json!({ "type": "Integer", "value": "1.5" })).is_err());
+        assert!(StackItem::from_json(&json!({ "type": "Iterator" })).is_err());
+        assert!(StackItem::from_json(&json!({ "value": "1" })).is_err());
+    }
+
+    #[test]
+    fn test_stack_item_accessors() {
+        let hash160 = StackItem::ByteString((0..20).collect());
+        assert_eq!(
+            hash160.as_address().as_deref(),
+            Some("NKuzPnNtxubufwT9MvNKJ557TRPWe1jGDF")
+        );
+        assert_eq!(
+            hash160.as_script_hash().as_deref(),
+            Some("0x131211100f0e0d0c0b0a09080706050403020100")
+        );
+        assert_eq!(hash160.as_u256(), None);
+
+        let hash256 = StackItem::ByteString((0..32).collect());
+        assert_eq!(
+            hash256.as_u256().as_deref(),
+            Some("0x1f1e1d1c1b1a191817161514131211100f0e0d0c0b0a09080706050403020100")
+        );
+        assert_eq!(hash256.as_address(), None);
+
+        assert_eq!(
+            StackItem::ByteString(b"neo".to_vec()).as_utf8().as_deref(),
+            Some("neo")
+        );
+        assert_eq!(StackItem::ByteString(vec![0xff]).as_utf8(), None);
+
+        // little-endian two's complement
+        assert_eq!(
+            StackItem::ByteString(vec![0x00, 0x80]).as_integer(),
+            Some(BigInt::from(-32768))
+        );
+        assert_eq!(StackItem::Integer(BigInt::from(0)).as_bool(), Some(false));
+        assert_eq!(StackItem::Any.as_integer(), None);
+    }
+}